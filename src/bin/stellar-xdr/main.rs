@@ -6,6 +6,13 @@ fn main() {
     if let Err(e) = cli::run(env::args_os()) {
         match e {
             cli::Error::Clap(e) => e.exit(),
+            cli::Error::Diff(cli::diff::Error::Differ) => std::process::exit(1),
+            cli::Error::Guess(
+                e @ (cli::guess::Error::NotGuessed | cli::guess::Error::PartiallyGuessed { .. }),
+            ) => {
+                let _ = Error::raw(clap::error::ErrorKind::ValueValidation, e).print();
+                std::process::exit(1)
+            }
             _ => Error::raw(clap::error::ErrorKind::ValueValidation, e).exit(),
         }
     }
@@ -0,0 +1,540 @@
+use super::{
+    BucketEntry, BucketListType, BucketMetadata, BucketMetadataExt, Error, Hash,
+    HotArchiveBucketEntry, LedgerKey, Limited, Limits, ReadXdr, WriteXdr,
+};
+
+use core::{cmp::Ordering, fmt, marker::PhantomData};
+use sha2::{Digest, Sha256};
+use std::io::{BufRead, BufReader, Read, Write};
+
+/// Error returned when reading, writing, or merging bucket files fails.
+#[derive(Debug, PartialEq)]
+pub enum BucketError {
+    /// An entry in the bucket could not be decoded or encoded.
+    Xdr(Error),
+    /// A metadata entry was found somewhere other than the start of the
+    /// bucket.
+    MisplacedMetadata,
+    /// The bucket's metadata declares a different bucket list type than the
+    /// type of entries being read.
+    BucketListTypeMismatch {
+        expected: BucketListType,
+        actual: BucketListType,
+    },
+    /// An entry's key is not strictly greater than the key of the entry
+    /// before it.
+    UnsortedKeys {
+        previous: Box<LedgerKey>,
+        key: Box<LedgerKey>,
+    },
+    /// The bucket file does not hash to the expected bucket hash.
+    HashMismatch { expected: Hash, actual: Hash },
+    /// An `INITENTRY` in the newer bucket of a merge has the same key as an
+    /// entry in the older bucket that is not a `DEADENTRY`.
+    InvalidInitEntry { key: Box<LedgerKey> },
+}
+
+impl fmt::Display for BucketError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Xdr(e) => write!(f, "{e}"),
+            Self::MisplacedMetadata => {
+                write!(
+                    f,
+                    "bucket metadata entry found after the start of the bucket"
+                )
+            }
+            Self::BucketListTypeMismatch { expected, actual } => write!(
+                f,
+                "bucket list type mismatch: expected {expected}, got {actual}"
+            ),
+            Self::UnsortedKeys { .. } => write!(f, "bucket entries are not in sorted key order"),
+            Self::HashMismatch { expected, actual } => {
+                write!(f, "bucket hash mismatch: expected {expected}, got {actual}")
+            }
+            Self::InvalidInitEntry { .. } => write!(
+                f,
+                "bucket merge of init entry over an entry that is not a dead entry"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BucketError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Xdr(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for BucketError {
+    fn from(e: Error) -> Self {
+        Self::Xdr(e)
+    }
+}
+
+impl From<std::io::Error> for BucketError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Xdr(Error::Io(e))
+    }
+}
+
+impl BucketMetadata {
+    /// Returns the type of bucket list the bucket belongs to, or `None` if
+    /// the metadata predates bucket list types being recorded.
+    #[must_use]
+    pub fn bucket_list_type(&self) -> Option<BucketListType> {
+        match self.ext {
+            BucketMetadataExt::V0 => None,
+            BucketMetadataExt::V1(t) => Some(t),
+        }
+    }
+}
+
+/// An entry that is stored in a bucket file.
+///
+/// Implemented for [`BucketEntry`], the entries of the live bucket list, and
+/// [`HotArchiveBucketEntry`], the entries of the hot archive bucket list.
+pub trait BucketFileEntry: ReadXdr + WriteXdr + Sized {
+    /// The type of bucket list that stores this type of entry.
+    const BUCKET_LIST_TYPE: BucketListType;
+
+    /// Constructs the metadata entry for the metadata.
+    fn from_metadata(metadata: BucketMetadata) -> Self;
+
+    /// Returns the metadata if the entry is a metadata entry.
+    fn metadata(&self) -> Option<&BucketMetadata>;
+
+    /// Returns the key of the ledger entry the entry is for, or `None` if the
+    /// entry is a metadata entry.
+    fn key(&self) -> Option<LedgerKey>;
+
+    /// Returns true if the entry is a tombstone, an entry that only exists to
+    /// shadow entries with the same key in older buckets.
+    fn is_tombstone(&self) -> bool;
+
+    /// Merges an entry from a newer bucket with the entry with the same key
+    /// from an older bucket, returning the entry to keep in the merged bucket
+    /// or `None` if the entries annihilate each other.
+    ///
+    /// # Errors
+    ///
+    /// If the entries cannot be merged.
+    fn merge(old: Self, new: Self) -> Result<Option<Self>, BucketError>;
+}
+
+impl BucketFileEntry for BucketEntry {
+    const BUCKET_LIST_TYPE: BucketListType = BucketListType::Live;
+
+    fn from_metadata(metadata: BucketMetadata) -> Self {
+        BucketEntry::Metaentry(metadata)
+    }
+
+    fn metadata(&self) -> Option<&BucketMetadata> {
+        match self {
+            BucketEntry::Metaentry(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    fn key(&self) -> Option<LedgerKey> {
+        match self {
+            BucketEntry::Liveentry(e) | BucketEntry::Initentry(e) => Some(e.to_key()),
+            BucketEntry::Deadentry(k) => Some(k.clone()),
+            BucketEntry::Metaentry(_) => None,
+        }
+    }
+
+    fn is_tombstone(&self) -> bool {
+        matches!(self, BucketEntry::Deadentry(_))
+    }
+
+    /// Merges entries with the protocol's `INITENTRY` rules:
+    ///
+    /// - An old `DEADENTRY` and a new `INITENTRY` merge into a `LIVEENTRY`
+    ///   with the new value, since the entry was recreated after being
+    ///   deleted.
+    /// - An old `INITENTRY` and a new `LIVEENTRY` merge into an `INITENTRY`
+    ///   with the new value, since the entry is still new.
+    /// - An old `INITENTRY` and a new `DEADENTRY` annihilate, since the entry
+    ///   was created and deleted within the merged range of ledgers.
+    /// - Otherwise the new entry replaces the old entry.
+    ///
+    /// A new `INITENTRY` over an old entry other than a `DEADENTRY` is an
+    /// error, since an entry cannot be created when it already exists.
+    fn merge(old: Self, new: Self) -> Result<Option<Self>, BucketError> {
+        match (old, new) {
+            (BucketEntry::Deadentry(_), BucketEntry::Initentry(e)) => {
+                Ok(Some(BucketEntry::Liveentry(e)))
+            }
+            (_, BucketEntry::Initentry(e)) => Err(BucketError::InvalidInitEntry {
+                key: Box::new(e.to_key()),
+            }),
+            (BucketEntry::Initentry(_), BucketEntry::Liveentry(e)) => {
+                Ok(Some(BucketEntry::Initentry(e)))
+            }
+            (BucketEntry::Initentry(_), BucketEntry::Deadentry(_)) => Ok(None),
+            (_, new) => Ok(Some(new)),
+        }
+    }
+}
+
+impl BucketFileEntry for HotArchiveBucketEntry {
+    const BUCKET_LIST_TYPE: BucketListType = BucketListType::HotArchive;
+
+    fn from_metadata(metadata: BucketMetadata) -> Self {
+        HotArchiveBucketEntry::Metaentry(metadata)
+    }
+
+    fn metadata(&self) -> Option<&BucketMetadata> {
+        match self {
+            HotArchiveBucketEntry::Metaentry(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    fn key(&self) -> Option<LedgerKey> {
+        match self {
+            HotArchiveBucketEntry::Archived(e) => Some(e.to_key()),
+            HotArchiveBucketEntry::Live(k) => Some(k.clone()),
+            HotArchiveBucketEntry::Metaentry(_) => None,
+        }
+    }
+
+    fn is_tombstone(&self) -> bool {
+        matches!(self, HotArchiveBucketEntry::Live(_))
+    }
+
+    /// Merges entries by keeping the new entry, since the hot archive has no
+    /// equivalent of `INITENTRY`.
+    fn merge(_old: Self, new: Self) -> Result<Option<Self>, BucketError> {
+        Ok(Some(new))
+    }
+}
+
+/// Records the key of an entry read or written to a bucket, checking that it
+/// is ordered after the key of the entry before it.
+fn push_key(last_key: &mut Option<LedgerKey>, key: LedgerKey) -> Result<(), BucketError> {
+    if let Some(previous) = last_key {
+        if key.entry_id_cmp(previous) != Ordering::Greater {
+            return Err(BucketError::UnsortedKeys {
+                previous: Box::new(previous.clone()),
+                key: Box::new(key),
+            });
+        }
+    }
+    *last_key = Some(key);
+    Ok(())
+}
+
+/// Forwards read operations to the wrapped reader, hashing every byte read.
+struct HashingReader<R: Read> {
+    inner: BufReader<R>,
+    hasher: Sha256,
+    len: u64,
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.hasher.update(&buf[..n]);
+        self.len += n as u64;
+        Ok(n)
+    }
+}
+
+/// Reads the entries of a bucket file one at a time.
+///
+/// A bucket file is a stream of entries, each framed with a record mark as
+/// defined by the Record Marking Standard in RFC 5531. Buckets created at
+/// protocol 11 and later start with a metadata entry, which is read when the
+/// reader is constructed and is not yielded by the iterator.
+///
+/// Every entry yielded is checked to have a key ordered after the key of the
+/// entry before it, using [`LedgerKey::entry_id_cmp`], and every byte read is
+/// hashed so that the bucket can be verified against its bucket hash once all
+/// entries have been read.
+///
+/// Use [`LiveBucketReader`] to read buckets of the live bucket list, and
+/// [`HotArchiveBucketReader`] to read buckets of the hot archive bucket list.
+pub struct BucketReader<R: Read, E: BucketFileEntry> {
+    reader: Limited<HashingReader<R>>,
+    metadata: Option<BucketMetadata>,
+    pending: Option<E>,
+    last_key: Option<LedgerKey>,
+    failed: bool,
+}
+
+/// Reads the [`BucketEntry`] of a bucket of the live bucket list.
+pub type LiveBucketReader<R> = BucketReader<R, BucketEntry>;
+
+/// Reads the [`HotArchiveBucketEntry`] of a bucket of the hot archive bucket
+/// list.
+pub type HotArchiveBucketReader<R> = BucketReader<R, HotArchiveBucketEntry>;
+
+impl<R: Read, E: BucketFileEntry> BucketReader<R, E> {
+    /// Constructs a new `BucketReader`, reading the bucket's metadata entry if
+    /// it has one.
+    ///
+    /// # Errors
+    ///
+    /// If the first entry cannot be read, or if the bucket's metadata
+    /// declares a bucket list type other than the one storing `E`.
+    pub fn new(r: R, limits: Limits) -> Result<Self, BucketError> {
+        let mut reader = Self {
+            reader: Limited::new(
+                HashingReader {
+                    inner: BufReader::new(r),
+                    hasher: Sha256::new(),
+                    len: 0,
+                },
+                limits,
+            ),
+            metadata: None,
+            pending: None,
+            last_key: None,
+            failed: false,
+        };
+        if let Some(e) = reader.read_entry()? {
+            match e.metadata() {
+                Some(m) => {
+                    if let Some(actual) = m.bucket_list_type() {
+                        if actual != E::BUCKET_LIST_TYPE {
+                            return Err(BucketError::BucketListTypeMismatch {
+                                expected: E::BUCKET_LIST_TYPE,
+                                actual,
+                            });
+                        }
+                    }
+                    reader.metadata = Some(m.clone());
+                }
+                None => reader.pending = Some(e),
+            }
+        }
+        Ok(reader)
+    }
+
+    /// Returns the bucket's metadata, or `None` if the bucket predates
+    /// buckets containing metadata.
+    #[must_use]
+    pub fn metadata(&self) -> Option<&BucketMetadata> {
+        self.metadata.as_ref()
+    }
+
+    /// Returns the protocol version the bucket was created with, or `None` if
+    /// the bucket has no metadata.
+    #[must_use]
+    pub fn ledger_version(&self) -> Option<u32> {
+        self.metadata.as_ref().map(|m| m.ledger_version)
+    }
+
+    /// Returns the type of bucket list the bucket belongs to.
+    #[must_use]
+    pub fn bucket_list_type(&self) -> BucketListType {
+        E::BUCKET_LIST_TYPE
+    }
+
+    /// Computes the hash of the bytes of the bucket file read so far.
+    ///
+    /// Once every entry has been read this is the bucket hash. An empty
+    /// bucket file has the all zero hash, consistent with how empty buckets
+    /// are referenced in the bucket list.
+    ///
+    /// # Returns
+    ///
+    /// The hash of the bytes read.
+    #[must_use]
+    pub fn hash(&self) -> [u8; 32] {
+        let hashing = &self.reader.inner;
+        if hashing.len == 0 {
+            [0; 32]
+        } else {
+            hashing.hasher.clone().finalize().into()
+        }
+    }
+
+    /// Verifies that the bytes of the bucket file read so far hash to the
+    /// given bucket hash. Call after every entry has been read.
+    ///
+    /// # Errors
+    ///
+    /// If the hash of the bytes read does not match the expected hash.
+    pub fn verify_hash(&self, expected: &Hash) -> Result<(), BucketError> {
+        let actual = Hash(self.hash());
+        if &actual == expected {
+            Ok(())
+        } else {
+            Err(BucketError::HashMismatch {
+                expected: expected.clone(),
+                actual,
+            })
+        }
+    }
+
+    /// Reads the next framed entry, or returns `None` if the end of the bucket
+    /// file has been reached.
+    fn read_entry(&mut self) -> Result<Option<E>, BucketError> {
+        // Peek to distinguish the end of the file from a truncated entry, since
+        // the XDR types read with `read_exact`.
+        if self.reader.inner.inner.fill_buf()?.is_empty() {
+            return Ok(None);
+        }
+        // Read the 4-byte record mark. Bit 31 is the last-fragment flag, and
+        // bits 0-30 are the length of the record that follows. Entries are
+        // always written as a single fragment.
+        let header = u32::read_xdr(&mut self.reader)?;
+        if header >> 31 != 1 {
+            return Err(Error::Unsupported.into());
+        }
+        let start = self.reader.inner.len;
+        let entry = self.reader.with_limited_depth(|r| E::read_xdr(r))?;
+        if self.reader.inner.len - start != u64::from(header & 0x7fff_ffff) {
+            return Err(Error::LengthMismatch.into());
+        }
+        Ok(Some(entry))
+    }
+
+    fn read_next(&mut self) -> Result<Option<E>, BucketError> {
+        let entry = match self.pending.take() {
+            Some(e) => e,
+            None => match self.read_entry()? {
+                Some(e) => e,
+                None => return Ok(None),
+            },
+        };
+        let Some(key) = entry.key() else {
+            return Err(BucketError::MisplacedMetadata);
+        };
+        push_key(&mut self.last_key, key)?;
+        Ok(Some(entry))
+    }
+}
+
+impl<R: Read, E: BucketFileEntry> Iterator for BucketReader<R, E> {
+    type Item = Result<E, BucketError>;
+
+    // Iteration stops at the end of the bucket file, or after the first error
+    // is returned, since the position of the underlying reader is undefined
+    // after an error.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let r = self.read_next();
+        self.failed = r.is_err();
+        r.transpose()
+    }
+}
+
+/// Writes the entries of a bucket file one at a time.
+///
+/// Entries are framed with a record mark as defined by the Record Marking
+/// Standard in RFC 5531, and must be written in key order, which is checked
+/// using [`LedgerKey::entry_id_cmp`]. Every byte written is hashed so that
+/// the bucket hash is available once the bucket is finished.
+///
+/// Use [`LiveBucketWriter`] to write buckets of the live bucket list, and
+/// [`HotArchiveBucketWriter`] to write buckets of the hot archive bucket list.
+pub struct BucketWriter<W: Write, E: BucketFileEntry> {
+    writer: W,
+    limits: Limits,
+    hasher: Sha256,
+    len: u64,
+    last_key: Option<LedgerKey>,
+    _e: PhantomData<E>,
+}
+
+/// Writes the [`BucketEntry`] of a bucket of the live bucket list.
+pub type LiveBucketWriter<W> = BucketWriter<W, BucketEntry>;
+
+/// Writes the [`HotArchiveBucketEntry`] of a bucket of the hot archive bucket
+/// list.
+pub type HotArchiveBucketWriter<W> = BucketWriter<W, HotArchiveBucketEntry>;
+
+impl<W: Write, E: BucketFileEntry> BucketWriter<W, E> {
+    /// Constructs a new `BucketWriter`, writing the metadata entry if the
+    /// bucket has metadata.
+    ///
+    /// # Errors
+    ///
+    /// If the metadata declares a bucket list type other than the one storing
+    /// `E`, or if the metadata entry cannot be written.
+    pub fn new(
+        w: W,
+        limits: Limits,
+        metadata: Option<BucketMetadata>,
+    ) -> Result<Self, BucketError> {
+        let mut writer = Self {
+            writer: w,
+            limits,
+            hasher: Sha256::new(),
+            len: 0,
+            last_key: None,
+            _e: PhantomData,
+        };
+        if let Some(m) = metadata {
+            if let Some(actual) = m.bucket_list_type() {
+                if actual != E::BUCKET_LIST_TYPE {
+                    return Err(BucketError::BucketListTypeMismatch {
+                        expected: E::BUCKET_LIST_TYPE,
+                        actual,
+                    });
+                }
+            }
+            writer.write_entry(&E::from_metadata(m))?;
+        }
+        Ok(writer)
+    }
+
+    /// Writes the next entry in the bucket.
+    ///
+    /// # Errors
+    ///
+    /// If the entry is a metadata entry, if its key is not ordered after the
+    /// key of the previous entry, or if the entry cannot be written.
+    pub fn push(&mut self, entry: &E) -> Result<(), BucketError> {
+        let Some(key) = entry.key() else {
+            return Err(BucketError::MisplacedMetadata);
+        };
+        push_key(&mut self.last_key, key)?;
+        self.write_entry(entry)
+    }
+
+    /// Completes the bucket, flushing and returning the underlying writer and
+    /// the bucket hash.
+    ///
+    /// An empty bucket file has the all zero hash, consistent with how empty
+    /// buckets are referenced in the bucket list.
+    ///
+    /// # Errors
+    ///
+    /// If the underlying writer cannot be flushed.
+    pub fn finish(mut self) -> Result<(W, [u8; 32]), BucketError> {
+        self.writer.flush()?;
+        let hash = if self.len == 0 {
+            [0; 32]
+        } else {
+            self.hasher.finalize().into()
+        };
+        Ok((self.writer, hash))
+    }
+
+    fn write_entry(&mut self, entry: &E) -> Result<(), BucketError> {
+        let xdr = entry.to_xdr(self.limits.clone())?;
+        // Write the record as a single fragment, with the last-fragment flag
+        // set in bit 31 of the record mark and the length in bits 0-30.
+        let len = u32::try_from(xdr.len())
+            .ok()
+            .filter(|len| len >> 31 == 0)
+            .ok_or(Error::LengthExceedsMax)?;
+        let mark = (len | 0x8000_0000).to_be_bytes();
+        for bytes in [&mark[..], &xdr] {
+            self.writer.write_all(bytes)?;
+            self.hasher.update(bytes);
+        }
+        self.len += 4 + u64::from(len);
+        Ok(())
+    }
+}
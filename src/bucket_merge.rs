@@ -0,0 +1,134 @@
+use super::{
+    BucketError, BucketFileEntry, BucketListType, BucketMetadata, BucketMetadataExt, BucketReader,
+    BucketWriter, LedgerKey, Limits,
+};
+
+use core::cmp::Ordering;
+use std::io::{Read, Write};
+
+/// The first protocol version in which buckets contain a metadata entry and
+/// `INITENTRY` entries.
+const FIRST_PROTOCOL_SUPPORTING_INITENTRY_AND_METAENTRY: u32 = 11;
+
+/// The first protocol version in which the metadata of live buckets records
+/// the bucket list type.
+const FIRST_PROTOCOL_SUPPORTING_PERSISTENT_EVICTION: u32 = 23;
+
+/// Merges an older and a newer bucket into a single bucket, streaming the
+/// entries of both buckets in key order and writing the merged bucket.
+///
+/// Entries with keys that appear in only one bucket are kept. Entries with
+/// keys that appear in both buckets are merged with
+/// [`BucketFileEntry::merge`], which for live buckets applies the protocol's
+/// `INITENTRY`, `LIVEENTRY`, and `DEADENTRY` annihilation rules, and for hot
+/// archive buckets keeps the newer entry.
+///
+/// Tombstones are dropped from the merged bucket unless
+/// `keep_tombstone_entries` is true. Tombstones are only needed while there
+/// are older buckets for them to shadow, and so are dropped when merging into
+/// the last level of a bucket list.
+///
+/// The merged bucket's metadata has the greater of the protocol versions of
+/// the two buckets, and is omitted if that version predates bucket metadata.
+///
+/// # Returns
+///
+/// The underlying writer and the hash of the merged bucket.
+///
+/// # Errors
+///
+/// If either bucket cannot be read or is not sorted, if entries cannot be
+/// merged, or if the merged bucket cannot be written.
+pub fn merge_buckets<E, R1, R2, W>(
+    old: BucketReader<R1, E>,
+    new: BucketReader<R2, E>,
+    w: W,
+    limits: Limits,
+    keep_tombstone_entries: bool,
+) -> Result<(W, [u8; 32]), BucketError>
+where
+    E: BucketFileEntry,
+    R1: Read,
+    R2: Read,
+    W: Write,
+{
+    let metadata = merged_metadata::<E>(old.metadata(), new.metadata());
+    let mut writer = BucketWriter::<W, E>::new(w, limits, metadata)?;
+
+    let mut old = old;
+    let mut new = new;
+    let mut old_next = next_keyed(&mut old)?;
+    let mut new_next = next_keyed(&mut new)?;
+    loop {
+        let entry = match (old_next.take(), new_next.take()) {
+            (None, None) => break,
+            (Some((_, o)), None) => {
+                old_next = next_keyed(&mut old)?;
+                Some(o)
+            }
+            (None, Some((_, n))) => {
+                new_next = next_keyed(&mut new)?;
+                Some(n)
+            }
+            (Some((ok, o)), Some((nk, n))) => match ok.entry_id_cmp(&nk) {
+                Ordering::Less => {
+                    new_next = Some((nk, n));
+                    old_next = next_keyed(&mut old)?;
+                    Some(o)
+                }
+                Ordering::Greater => {
+                    old_next = Some((ok, o));
+                    new_next = next_keyed(&mut new)?;
+                    Some(n)
+                }
+                Ordering::Equal => {
+                    old_next = next_keyed(&mut old)?;
+                    new_next = next_keyed(&mut new)?;
+                    E::merge(o, n)?
+                }
+            },
+        };
+        if let Some(entry) = entry {
+            if keep_tombstone_entries || !entry.is_tombstone() {
+                writer.push(&entry)?;
+            }
+        }
+    }
+
+    writer.finish()
+}
+
+/// Reads the next entry of the bucket paired with its key.
+fn next_keyed<R: Read, E: BucketFileEntry>(
+    reader: &mut BucketReader<R, E>,
+) -> Result<Option<(LedgerKey, E)>, BucketError> {
+    match reader.next().transpose()? {
+        Some(e) => {
+            let key = e.key().ok_or(BucketError::MisplacedMetadata)?;
+            Ok(Some((key, e)))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Returns the metadata of the bucket produced by merging buckets with the
+/// given metadata.
+fn merged_metadata<E: BucketFileEntry>(
+    old: Option<&BucketMetadata>,
+    new: Option<&BucketMetadata>,
+) -> Option<BucketMetadata> {
+    let ledger_version = old
+        .map(|m| m.ledger_version)
+        .max(new.map(|m| m.ledger_version))
+        .filter(|v| *v >= FIRST_PROTOCOL_SUPPORTING_INITENTRY_AND_METAENTRY)?;
+    let ext = match E::BUCKET_LIST_TYPE {
+        BucketListType::Live if ledger_version < FIRST_PROTOCOL_SUPPORTING_PERSISTENT_EVICTION => {
+            BucketMetadataExt::V0
+        }
+        t => BucketMetadataExt::V1(t),
+    };
+    Some(BucketMetadata {
+        ledger_version,
+        ext,
+    })
+}
@@ -0,0 +1,271 @@
+use std::ffi::OsString;
+use std::io::{stdout, Read, Write};
+use std::{fmt::Debug, str::FromStr};
+
+use base64::Engine;
+use clap::{Args, ValueEnum};
+
+use crate::cli::{decode, util};
+use crate::WriteXdr;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("unknown type {0}, choose one of {1:?}")]
+    UnknownType(String, &'static [&'static str]),
+    #[error(transparent)]
+    Decode(#[from] decode::Error),
+    #[error("error decoding XDR: {0}")]
+    ReadXdr(crate::Error),
+    #[error("error decoding JSON: {0}")]
+    ReadJson(crate::Error),
+    #[error("error decoding hex: {0}")]
+    ReadHex(hex::FromHexError),
+    #[error("error reading file: {0}")]
+    ReadFile(std::io::Error),
+    #[error("error writing output: {0}")]
+    WriteOutput(std::io::Error),
+    #[error("error generating XDR: {0}")]
+    WriteXdr(crate::Error),
+    #[error("error generating JSON: {0}")]
+    GenerateJson(#[from] serde_json::Error),
+}
+
+/// Convert XDR values between formats
+///
+/// Reads every value in the input format, and outputs every value in the
+/// output format. The formats are:
+///
+///   `single`: one binary XDR value,
+///   `single-base64`: one base64 XDR value, output one per line,
+///   `stream`: binary XDR values one after another,
+///   `stream-base64`: a stream, base64 encoded,
+///   `stream-framed`: binary XDR values each preceded by a record mark,
+///   `json`, `json-formatted`: one JSON value, output one per line,
+///   `ndjson`: JSON values one per line,
+///   `hex`: one hex XDR value, output one per line.
+#[derive(Args, Debug, Clone)]
+#[command()]
+pub struct Cmd {
+    /// XDR, JSON, or files containing them to convert, or stdin if empty
+    #[arg()]
+    pub input: Vec<OsString>,
+
+    /// XDR type to convert
+    #[arg(long)]
+    pub r#type: String,
+
+    // Input format
+    #[arg(long = "input", value_enum)]
+    pub input_format: Format,
+
+    // Output format
+    #[arg(long = "output", value_enum)]
+    pub output_format: Format,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Format {
+    Single,
+    SingleBase64,
+    Stream,
+    StreamBase64,
+    StreamFramed,
+    Json,
+    JsonFormatted,
+    Ndjson,
+    Hex,
+}
+
+impl Cmd {
+    /// Run the CLIs convert command.
+    ///
+    /// ## Errors
+    ///
+    /// If the command is configured with state that is invalid.
+    pub fn run(&self) -> Result<(), Error> {
+        let result = self.run_inner();
+        match result {
+            Ok(()) => Ok(()),
+            Err(Error::WriteOutput(e)) if e.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn run_inner(&self) -> Result<(), Error> {
+        let r#type = crate::TypeVariant::from_str(&self.r#type).map_err(|_| {
+            Error::UnknownType(self.r#type.clone(), &crate::TypeVariant::VARIANTS_STR)
+        })?;
+        let mut input = util::parse_input(&self.input).map_err(Error::ReadFile)?;
+        for f in &mut input {
+            self.convert(r#type, f, &mut stdout())?;
+        }
+        Ok(())
+    }
+
+    /// Writes every value read from f to out in the output format.
+    fn convert(
+        &self,
+        r#type: crate::TypeVariant,
+        f: &mut impl Read,
+        out: &mut impl Write,
+    ) -> Result<(), Error> {
+        // A base64 stream is output once all of the input's values are
+        // written to it.
+        let mut stream = Vec::new();
+        each_value(r#type, self.input_format, f, |t| {
+            self.out(t, out, &mut stream)
+        })?;
+        if self.output_format == Format::StreamBase64 && !stream.is_empty() {
+            let b64 = base64::engine::general_purpose::STANDARD.encode(&stream);
+            writeln!(out, "{b64}").map_err(Error::WriteOutput)?;
+        }
+        Ok(())
+    }
+
+    fn out(
+        &self,
+        t: &crate::Type,
+        out: &mut impl Write,
+        stream: &mut Vec<u8>,
+    ) -> Result<(), Error> {
+        let xdr = || t.to_xdr(crate::Limits::none()).map_err(Error::WriteXdr);
+        match self.output_format {
+            Format::Single | Format::Stream => out.write_all(&xdr()?),
+            Format::SingleBase64 => {
+                let b64 = base64::engine::general_purpose::STANDARD.encode(xdr()?);
+                writeln!(out, "{b64}")
+            }
+            Format::StreamBase64 => {
+                stream.extend(xdr()?);
+                Ok(())
+            }
+            Format::StreamFramed => util::write_framed(out, &xdr()?),
+            Format::Json | Format::Ndjson => writeln!(out, "{}", serde_json::to_string(t)?),
+            Format::JsonFormatted => writeln!(out, "{}", serde_json::to_string_pretty(t)?),
+            Format::Hex => writeln!(out, "{}", hex::encode(xdr()?)),
+        }
+        .map_err(Error::WriteOutput)
+    }
+}
+
+/// Calls out with each value in the input format read from f.
+pub(crate) fn each_value<E: From<Error> + From<decode::Error>>(
+    r#type: crate::TypeVariant,
+    input_format: Format,
+    f: &mut impl Read,
+    mut out: impl FnMut(&crate::Type) -> Result<(), E>,
+) -> Result<(), E> {
+    let xdr_format = match input_format {
+        Format::Single => decode::InputFormat::Single,
+        Format::SingleBase64 => decode::InputFormat::SingleBase64,
+        Format::Stream => decode::InputFormat::Stream,
+        Format::StreamBase64 => decode::InputFormat::StreamBase64,
+        Format::StreamFramed => decode::InputFormat::StreamFramed,
+        Format::Json | Format::JsonFormatted => {
+            let t = crate::Type::from_json(r#type, f).map_err(Error::ReadJson)?;
+            return out(&t);
+        }
+        Format::Ndjson => {
+            let mut de = serde_json::Deserializer::new(serde_json::de::IoRead::new(f));
+            loop {
+                match crate::Type::deserialize_json(r#type, &mut de) {
+                    Ok(t) => out(&t)?,
+                    Err(crate::Error::Json(ref e)) if e.is_eof() => return Ok(()),
+                    Err(e) => return Err(Error::ReadJson(e).into()),
+                }
+            }
+        }
+        Format::Hex => {
+            let mut text = String::new();
+            f.read_to_string(&mut text).map_err(Error::ReadFile)?;
+            let text = text.split_whitespace().collect::<String>();
+            let bytes = hex::decode(text).map_err(Error::ReadHex)?;
+            let mut l = crate::Limited::new(bytes.as_slice(), crate::Limits::none());
+            let t = crate::Type::read_xdr_to_end(r#type, &mut l).map_err(Error::ReadXdr)?;
+            return out(&t);
+        }
+    };
+    decode::each_value(r#type, xdr_format, f, out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ScVal, TypeVariant};
+
+    fn convert(input_format: Format, output_format: Format, input: &[u8]) -> String {
+        let cmd = Cmd {
+            input: Vec::new(),
+            r#type: "ScVal".to_string(),
+            input_format,
+            output_format,
+        };
+        let mut out = Vec::new();
+        cmd.convert(TypeVariant::ScVal, &mut &input[..], &mut out)
+            .unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    fn values() -> Vec<ScVal> {
+        vec![
+            ScVal::U32(1),
+            ScVal::Bool(true),
+            ScVal::Symbol("abc".try_into().unwrap()),
+        ]
+    }
+
+    #[test]
+    fn convert_to_stream_base64() {
+        let ndjson = values()
+            .iter()
+            .map(|v| serde_json::to_string(v).unwrap() + "\n")
+            .collect::<String>();
+        let stream = values()
+            .iter()
+            .flat_map(|v| v.to_xdr(crate::Limits::none()).unwrap())
+            .collect::<Vec<_>>();
+        let b64 = base64::engine::general_purpose::STANDARD.encode(&stream);
+        // Every value of the input is in one line.
+        let out = convert(Format::Ndjson, Format::StreamBase64, ndjson.as_bytes());
+        assert_eq!(out, format!("{b64}\n"));
+        let out = convert(Format::Stream, Format::StreamBase64, &stream);
+        assert_eq!(out, format!("{b64}\n"));
+        // An input without values outputs nothing.
+        assert_eq!(convert(Format::Stream, Format::StreamBase64, &[]), "");
+    }
+
+    #[test]
+    fn convert_ndjson_round_trip() {
+        let ndjson = values()
+            .iter()
+            .map(|v| serde_json::to_string(v).unwrap() + "\n")
+            .collect::<String>();
+        let b64 = convert(Format::Ndjson, Format::StreamBase64, ndjson.as_bytes());
+        let out = convert(Format::StreamBase64, Format::Ndjson, b64.as_bytes());
+        assert_eq!(out, ndjson);
+        // Values may be separated by any whitespace, not only newlines.
+        let out = convert(
+            Format::Ndjson,
+            Format::Ndjson,
+            ndjson.replace('\n', " ").as_bytes(),
+        );
+        assert_eq!(out, ndjson);
+    }
+
+    #[test]
+    fn convert_hex_round_trip() {
+        for v in values() {
+            let b64 = v.to_xdr_base64(crate::Limits::none()).unwrap();
+            let hex = convert(Format::SingleBase64, Format::Hex, b64.as_bytes());
+            assert_eq!(
+                hex,
+                format!(
+                    "{}\n",
+                    hex::encode(v.to_xdr(crate::Limits::none()).unwrap())
+                )
+            );
+            let out = convert(Format::Hex, Format::SingleBase64, hex.as_bytes());
+            assert_eq!(out, format!("{b64}\n"));
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use std::ffi::OsString;
-use std::io::{stdout, Write};
+use std::io::{stdout, Read, Write};
 use std::{fmt::Debug, str::FromStr};
 
 use clap::{Args, ValueEnum};
@@ -21,6 +21,12 @@ pub enum Error {
     GenerateJson(#[from] serde_json::Error),
     #[error("type doesn't have a text representation, use 'json' as output")]
     TextUnsupported,
+    #[error("error reading wasm: {0}")]
+    ReadWasm(#[from] crate::WasmError),
+    #[error(
+        "type {0} is not stored in wasm, choose one of ScSpecEntry, ScMetaEntry, ScEnvMetaEntry"
+    )]
+    WasmUnsupportedType(String),
 }
 
 #[derive(Args, Debug, Clone)]
@@ -50,6 +56,7 @@ pub enum InputFormat {
     Stream,
     StreamBase64,
     StreamFramed,
+    Wasm,
 }
 
 impl Default for InputFormat {
@@ -83,36 +90,7 @@ macro_rules! run_x {
                 Error::UnknownType(self.r#type.clone(), &crate::TypeVariant::VARIANTS_STR)
             })?;
             for f in &mut input {
-                match self.input_format {
-                    InputFormat::Single => {
-                        let mut l = crate::Limited::new(f, crate::Limits::none());
-                        let t = crate::Type::read_xdr_to_end(r#type, &mut l)?;
-                        self.out(&t)?;
-                    }
-                    InputFormat::SingleBase64 => {
-                        let mut l = crate::Limited::new(f, crate::Limits::none());
-                        let t = crate::Type::read_xdr_base64_to_end(r#type, &mut l)?;
-                        self.out(&t)?;
-                    }
-                    InputFormat::Stream => {
-                        let mut l = crate::Limited::new(f, crate::Limits::none());
-                        for t in crate::Type::read_xdr_iter(r#type, &mut l) {
-                            self.out(&t?)?;
-                        }
-                    }
-                    InputFormat::StreamBase64 => {
-                        let mut l = crate::Limited::new(f, crate::Limits::none());
-                        for t in crate::Type::read_xdr_base64_iter(r#type, &mut l) {
-                            self.out(&t?)?;
-                        }
-                    }
-                    InputFormat::StreamFramed => {
-                        let mut l = crate::Limited::new(f, crate::Limits::none());
-                        for t in crate::Type::read_xdr_framed_iter(r#type, &mut l) {
-                            self.out(&t?)?;
-                        }
-                    }
-                };
+                each_value(r#type, self.input_format, f, |t| self.out(t))?;
             }
             Ok(())
         }
@@ -151,3 +129,69 @@ impl Cmd {
         Ok(())
     }
 }
+
+/// Decodes each value of the type in the input, in the input format, and
+/// calls the function with the value.
+pub(crate) fn each_value<E: From<Error>>(
+    r#type: crate::TypeVariant,
+    input_format: InputFormat,
+    f: &mut impl Read,
+    mut out: impl FnMut(&crate::Type) -> Result<(), E>,
+) -> Result<(), E> {
+    match input_format {
+        InputFormat::Single => {
+            let mut l = crate::Limited::new(f, crate::Limits::none());
+            let t = crate::Type::read_xdr_to_end(r#type, &mut l).map_err(Error::from)?;
+            out(&t)?;
+        }
+        InputFormat::SingleBase64 => {
+            let mut l = crate::Limited::new(f, crate::Limits::none());
+            let t = crate::Type::read_xdr_base64_to_end(r#type, &mut l).map_err(Error::from)?;
+            out(&t)?;
+        }
+        InputFormat::Stream => {
+            let mut l = crate::Limited::new(f, crate::Limits::none());
+            for t in crate::Type::read_xdr_iter(r#type, &mut l) {
+                out(&t.map_err(Error::from)?)?;
+            }
+        }
+        InputFormat::StreamBase64 => {
+            let mut l = crate::Limited::new(f, crate::Limits::none());
+            for t in crate::Type::read_xdr_base64_iter(r#type, &mut l) {
+                out(&t.map_err(Error::from)?)?;
+            }
+        }
+        InputFormat::StreamFramed => {
+            let mut l = crate::Limited::new(f, crate::Limits::none());
+            for t in crate::Type::read_xdr_framed_iter(r#type, &mut l) {
+                out(&t.map_err(Error::from)?)?;
+            }
+        }
+        InputFormat::Wasm => {
+            let mut wasm = Vec::new();
+            f.read_to_end(&mut wasm).map_err(Error::ReadFile)?;
+            let limits = crate::Limits::none();
+            let values = match r#type {
+                crate::TypeVariant::ScSpecEntry => crate::ScSpecEntry::from_wasm(&wasm, limits)
+                    .map_err(Error::from)?
+                    .into_iter()
+                    .map(|e| crate::Type::ScSpecEntry(Box::new(e)))
+                    .collect(),
+                crate::TypeVariant::ScMetaEntry => crate::ScMetaEntry::from_wasm(&wasm, limits)
+                    .map_err(Error::from)?
+                    .into_iter()
+                    .map(|e| crate::Type::ScMetaEntry(Box::new(e)))
+                    .collect(),
+                crate::TypeVariant::ScEnvMetaEntry => {
+                    let e = crate::ScEnvMetaEntry::from_wasm(&wasm, limits).map_err(Error::from)?;
+                    vec![crate::Type::ScEnvMetaEntry(Box::new(e))]
+                }
+                _ => return Err(Error::WasmUnsupportedType(r#type.name().to_string()).into()),
+            };
+            for t in &values {
+                out(t)?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,314 @@
+use std::{
+    ffi::OsString,
+    fmt::Debug,
+    io::{stdout, Write},
+    str::FromStr,
+};
+
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::cli::convert::{self, Format};
+use crate::cli::{decode, util};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("unknown type {0}, choose one of {1:?}")]
+    UnknownType(String, &'static [&'static str]),
+    #[error(transparent)]
+    Convert(#[from] convert::Error),
+    #[error(transparent)]
+    Decode(#[from] decode::Error),
+    #[error("error reading file: {0}")]
+    ReadFile(std::io::Error),
+    #[error("error writing output: {0}")]
+    WriteOutput(std::io::Error),
+    #[error("error generating JSON: {0}")]
+    GenerateJson(#[from] serde_json::Error),
+    #[error("values differ")]
+    Differ,
+}
+
+/// Diff two XDR values
+///
+/// Outputs one line for each difference between the values, with the path of
+/// the field that differs and the left and right values as JSON. The paths
+/// and values are those of the JSON representation of the values.
+///
+/// If either input contains more than one value, the values are compared in
+/// order, and paths start with the index of the value, e.g. `[1].fee`.
+///
+/// Exits with status `0` when the values are equal, `1` when they differ,
+/// and `2` on error.
+#[derive(Args, Debug, Clone)]
+#[command()]
+pub struct Cmd {
+    /// XDR, JSON, or file containing them to decode and diff with the right
+    /// value
+    #[arg()]
+    pub left: OsString,
+
+    /// XDR, JSON, or file containing them to decode and diff with the left
+    /// value
+    #[arg()]
+    pub right: OsString,
+
+    /// XDR type of both inputs
+    #[arg(long)]
+    pub r#type: String,
+
+    // Input format of both inputs
+    #[arg(long = "input", value_enum, default_value_t = Format::SingleBase64)]
+    pub input_format: Format,
+
+    // Output format
+    #[arg(long = "output", value_enum, default_value_t)]
+    pub output_format: OutputFormat,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    /// Lines of `~ path: left -> right`, `- path: left`, and `+ path: right`
+    Text,
+    /// Lines of JSON objects with the path, kind of change, left and right
+    Json,
+}
+
+impl Default for OutputFormat {
+    fn default() -> Self {
+        Self::Text
+    }
+}
+
+/// A difference between two JSON values.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Change {
+    /// Path to the value that differs, e.g. `.tx.operations[0].body`, or `.`
+    /// for the root.
+    pub path: String,
+    pub kind: ChangeKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub left: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub right: Option<Value>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    /// The value is only in the left.
+    Removed,
+    /// The value is only in the right.
+    Added,
+    /// The value is in both, but differs.
+    Changed,
+}
+
+/// Returns the differences between two JSON values.
+///
+/// Objects are compared field by field, and arrays element by element.
+/// Values that are not both objects or both arrays are reported as changed
+/// in whole.
+#[must_use]
+pub fn diff(left: &Value, right: &Value) -> Vec<Change> {
+    let mut changes = Vec::new();
+    diff_into(&mut changes, &mut String::new(), left, right);
+    changes
+}
+
+fn diff_into(changes: &mut Vec<Change>, path: &mut String, left: &Value, right: &Value) {
+    let len = path.len();
+    match (left, right) {
+        (Value::Object(l), Value::Object(r)) => {
+            for (k, lv) in l {
+                path.push('.');
+                path.push_str(k);
+                match r.get(k) {
+                    Some(rv) => diff_into(changes, path, lv, rv),
+                    None => changes.push(change(path, Some(lv), None)),
+                }
+                path.truncate(len);
+            }
+            for (k, rv) in r.iter().filter(|(k, _)| !l.contains_key(*k)) {
+                path.push('.');
+                path.push_str(k);
+                changes.push(change(path, None, Some(rv)));
+                path.truncate(len);
+            }
+        }
+        (Value::Array(l), Value::Array(r)) => {
+            for i in 0..l.len().max(r.len()) {
+                path.push('[');
+                path.push_str(&i.to_string());
+                path.push(']');
+                match (l.get(i), r.get(i)) {
+                    (Some(lv), Some(rv)) => diff_into(changes, path, lv, rv),
+                    (lv, rv) => changes.push(change(path, lv, rv)),
+                }
+                path.truncate(len);
+            }
+        }
+        _ if left != right => changes.push(change(path, Some(left), Some(right))),
+        _ => {}
+    }
+}
+
+fn change(path: &str, left: Option<&Value>, right: Option<&Value>) -> Change {
+    let kind = match (left, right) {
+        (Some(_), None) => ChangeKind::Removed,
+        (None, Some(_)) => ChangeKind::Added,
+        _ => ChangeKind::Changed,
+    };
+    Change {
+        path: if path.is_empty() { "." } else { path }.to_string(),
+        kind,
+        left: left.cloned(),
+        right: right.cloned(),
+    }
+}
+
+impl Cmd {
+    /// Run the CLIs diff command.
+    ///
+    /// ## Errors
+    ///
+    /// If the command is configured with state that is invalid, or the
+    /// values differ.
+    pub fn run(&self) -> Result<(), Error> {
+        let result = self.run_inner();
+        match result {
+            Ok(()) => Ok(()),
+            // Output is only written when the values differ.
+            Err(Error::WriteOutput(e)) if e.kind() == std::io::ErrorKind::BrokenPipe => {
+                Err(Error::Differ)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn run_inner(&self) -> Result<(), Error> {
+        let r#type = crate::TypeVariant::from_str(&self.r#type).map_err(|_| {
+            Error::UnknownType(self.r#type.clone(), &crate::TypeVariant::VARIANTS_STR)
+        })?;
+        let left = self.read(r#type, &self.left)?;
+        let right = self.read(r#type, &self.right)?;
+        let changes = match (&left[..], &right[..]) {
+            ([left], [right]) => diff(left, right),
+            _ => diff(&Value::Array(left), &Value::Array(right)),
+        };
+        let mut out = stdout();
+        for c in &changes {
+            match self.output_format {
+                OutputFormat::Text => {
+                    let json = |v: &Option<Value>| v.as_ref().map(Value::to_string);
+                    match (json(&c.left), json(&c.right)) {
+                        (Some(l), Some(r)) => writeln!(out, "~ {}: {l} -> {r}", c.path),
+                        (Some(l), None) => writeln!(out, "- {}: {l}", c.path),
+                        (None, Some(r)) => writeln!(out, "+ {}: {r}", c.path),
+                        (None, None) => Ok(()),
+                    }
+                }
+                OutputFormat::Json => writeln!(out, "{}", serde_json::to_string(c)?),
+            }
+            .map_err(Error::WriteOutput)?;
+        }
+        if changes.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Differ)
+        }
+    }
+
+    /// Reads the values of the input, as JSON.
+    fn read(&self, r#type: crate::TypeVariant, input: &OsString) -> Result<Vec<Value>, Error> {
+        let mut f = util::parse_input(std::slice::from_ref(input))
+            .map_err(Error::ReadFile)?
+            .remove(0);
+        let mut values = Vec::new();
+        convert::each_value(r#type, self.input_format, &mut f, |t| {
+            values.push(serde_json::to_value(t)?);
+            Ok::<(), Error>(())
+        })?;
+        Ok(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn changes(left: &Value, right: &Value) -> Vec<(String, ChangeKind)> {
+        diff(left, right)
+            .into_iter()
+            .map(|c| (c.path, c.kind))
+            .collect()
+    }
+
+    #[test]
+    fn diff_equal() {
+        let v = json!({"tx": {"fee": 100, "operations": [{"body": "inflation"}]}});
+        assert_eq!(diff(&v, &v), []);
+    }
+
+    #[test]
+    fn diff_changed_fields() {
+        let left = json!({"fee": 100, "memo": "none", "ext": "v0"});
+        let right = json!({"fee": 200, "memo": {"text": "hi"}, "ext": "v0"});
+        assert_eq!(
+            diff(&left, &right),
+            [
+                Change {
+                    path: ".fee".to_string(),
+                    kind: ChangeKind::Changed,
+                    left: Some(json!(100)),
+                    right: Some(json!(200)),
+                },
+                Change {
+                    path: ".memo".to_string(),
+                    kind: ChangeKind::Changed,
+                    left: Some(json!("none")),
+                    right: Some(json!({"text": "hi"})),
+                },
+            ]
+        );
+        assert_eq!(
+            changes(&json!(1), &json!(2)),
+            [(".".to_string(), ChangeKind::Changed)]
+        );
+    }
+
+    #[test]
+    fn diff_vec_elements() {
+        let left = json!({"ops": [1, 2, 3]});
+        let right = json!({"ops": [1, 4]});
+        assert_eq!(
+            changes(&left, &right),
+            [
+                (".ops[1]".to_string(), ChangeKind::Changed),
+                (".ops[2]".to_string(), ChangeKind::Removed),
+            ]
+        );
+        assert_eq!(
+            changes(&right, &left),
+            [
+                (".ops[1]".to_string(), ChangeKind::Changed),
+                (".ops[2]".to_string(), ChangeKind::Added),
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_map_entries() {
+        let left = json!({"a": {"x": 1, "y": 2}});
+        let right = json!({"a": {"y": 2, "z": 3}});
+        let d = diff(&left, &right);
+        assert_eq!(
+            d.iter().map(|c| (&c.path[..], c.kind)).collect::<Vec<_>>(),
+            [(".a.x", ChangeKind::Removed), (".a.z", ChangeKind::Added)]
+        );
+        assert_eq!((&d[0].left, &d[0].right), (&Some(json!(1)), &None));
+        assert_eq!((&d[1].left, &d[1].right), (&None, &Some(json!(3))));
+    }
+}
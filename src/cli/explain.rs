@@ -0,0 +1,749 @@
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::io::{stdout, Read, Write};
+use std::{fmt::Debug, str::FromStr};
+
+use base64::Engine;
+use clap::{Args, ValueEnum};
+use serde::Deserialize;
+
+use crate::cli::query::Segment;
+use crate::cli::util;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("unknown type {0}, choose one of {1:?}")]
+    UnknownType(String, &'static [&'static str]),
+    #[error("type {0} has no XDR definition")]
+    UnknownDefinition(String),
+    #[error("error reading XDR definitions: {0}")]
+    ReadDefinitions(serde_json::Error),
+    #[error("error reading file: {0}")]
+    ReadFile(std::io::Error),
+    #[error("error decoding base64: {0}")]
+    ReadBase64(base64::DecodeError),
+    #[error("error decoding hex: {0}")]
+    ReadHex(hex::FromHexError),
+    #[error("error writing output: {0}")]
+    WriteOutput(std::io::Error),
+    #[error("error decoding XDR at offset {offset:#x}: {reason}")]
+    Decode { offset: usize, reason: String },
+}
+
+/// Explain the bytes of an XDR value
+///
+/// Decodes the value and outputs each field nested under the value it is a
+/// field of, with its byte offset, byte length, type, and for values that
+/// have no fields the decoded value and the raw hex.
+///
+/// If the value fails to decode, outputs the fields decoded up to the offset
+/// the decode failed at, with an unknown length for the values that were
+/// being decoded.
+///
+/// Values are decoded using the XDR definitions with no XDR features enabled,
+/// and so types and arms that only exist with an XDR feature enabled are not
+/// explained.
+#[derive(Args, Debug, Clone)]
+#[command()]
+pub struct Cmd {
+    /// XDR or files containing XDR to explain, or stdin if empty
+    #[arg()]
+    pub input: Vec<OsString>,
+
+    /// XDR type to decode
+    #[arg(long)]
+    pub r#type: String,
+
+    // Input format of the XDR
+    #[arg(long = "input", value_enum, default_value_t)]
+    pub input_format: InputFormat,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum InputFormat {
+    Single,
+    SingleBase64,
+    Hex,
+}
+
+impl Default for InputFormat {
+    fn default() -> Self {
+        Self::SingleBase64
+    }
+}
+
+impl Cmd {
+    /// Run the CLIs explain command.
+    ///
+    /// ## Errors
+    ///
+    /// If the command is configured with state that is invalid, or the input
+    /// fails to decode.
+    pub fn run(&self) -> Result<(), Error> {
+        let result = self.run_inner();
+        match result {
+            Ok(()) => Ok(()),
+            Err(Error::WriteOutput(e)) if e.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn run_inner(&self) -> Result<(), Error> {
+        let r#type = crate::TypeVariant::from_str(&self.r#type).map_err(|_| {
+            Error::UnknownType(self.r#type.clone(), &crate::TypeVariant::VARIANTS_STR)
+        })?;
+        let defs = Definitions::load()?;
+        let name = defs
+            .name_of(r#type)
+            .ok_or_else(|| Error::UnknownDefinition(self.r#type.clone()))?;
+        let mut input = util::parse_input(&self.input).map_err(Error::ReadFile)?;
+        for f in &mut input {
+            let bytes = self.read(f)?;
+            let (node, result) = explain(&defs, name, &bytes);
+            node.write(&mut stdout(), &bytes, 0)
+                .map_err(Error::WriteOutput)?;
+            if let Err(DecodeError { offset, reason, .. }) = result {
+                return Err(Error::Decode { offset, reason });
+            }
+        }
+        Ok(())
+    }
+
+    fn read(&self, f: &mut impl Read) -> Result<Vec<u8>, Error> {
+        let mut bytes = Vec::new();
+        f.read_to_end(&mut bytes).map_err(Error::ReadFile)?;
+        match self.input_format {
+            InputFormat::Single => Ok(bytes),
+            InputFormat::SingleBase64 => {
+                let text = bytes
+                    .into_iter()
+                    .filter(|b| !b.is_ascii_whitespace())
+                    .collect::<Vec<_>>();
+                base64::engine::general_purpose::STANDARD
+                    .decode(text)
+                    .map_err(Error::ReadBase64)
+            }
+            InputFormat::Hex => {
+                let text = bytes
+                    .into_iter()
+                    .filter(|b| !b.is_ascii_whitespace())
+                    .collect::<Vec<_>>();
+                hex::decode(text).map_err(Error::ReadHex)
+            }
+        }
+    }
+}
+
+/// The XDR definitions of every type, as generated from the .x files into
+/// `xdr-definitions-json/xdr.json`.
+pub(crate) struct Definitions(HashMap<String, Definition>);
+
+#[derive(Deserialize)]
+struct DefinitionsFile {
+    definitions: Vec<Definition>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Definition {
+    Typedef {
+        name: String,
+        #[serde(rename = "type")]
+        r#type: Decl,
+    },
+    Struct {
+        name: String,
+        fields: Vec<Field>,
+    },
+    Union {
+        name: String,
+        discriminant: Field,
+        arms: Vec<Arm>,
+    },
+    Enum {
+        name: String,
+        members: Vec<Member>,
+    },
+    Const {
+        name: String,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Decl {
+    Int,
+    UnsignedInt,
+    Hyper,
+    UnsignedHyper,
+    Bool,
+    OpaqueFixed {
+        size: u32,
+    },
+    OpaqueVar {
+        max_size: Option<u32>,
+    },
+    String {
+        max_size: Option<u32>,
+    },
+    Array {
+        element: Box<Decl>,
+        count: u32,
+    },
+    VarArray {
+        element: Box<Decl>,
+        max_count: Option<u32>,
+    },
+    Optional {
+        element: Box<Decl>,
+    },
+    Ref {
+        name: String,
+    },
+}
+
+#[derive(Deserialize)]
+struct Field {
+    name: String,
+    #[serde(rename = "type")]
+    r#type: Decl,
+}
+
+#[derive(Deserialize)]
+struct Arm {
+    cases: Vec<Case>,
+    name: Option<String>,
+    #[serde(rename = "type")]
+    r#type: Option<Decl>,
+}
+
+#[derive(Deserialize)]
+struct Case {
+    /// The name of the enum member, for unions with an enum discriminant.
+    name: Option<String>,
+    value: i64,
+}
+
+#[derive(Deserialize)]
+struct Member {
+    name: String,
+    value: i64,
+}
+
+impl Definition {
+    fn name(&self) -> &str {
+        match self {
+            Definition::Typedef { name, .. }
+            | Definition::Struct { name, .. }
+            | Definition::Union { name, .. }
+            | Definition::Enum { name, .. }
+            | Definition::Const { name } => name,
+        }
+    }
+}
+
+impl Definitions {
+    pub(crate) fn load() -> Result<Self, Error> {
+        let file: DefinitionsFile =
+            serde_json::from_str(include_str!("../../xdr-definitions-json/xdr.json"))
+                .map_err(Error::ReadDefinitions)?;
+        Ok(Self(
+            file.definitions
+                .into_iter()
+                .map(|d| (d.name().to_string(), d))
+                .collect(),
+        ))
+    }
+
+    /// Returns the XDR name of the type, which differs from the Rust name in
+    /// case only, e.g. `SCVal` is `ScVal`, except for `Error` which is
+    /// `SError` so as not to conflict with the crate's `Error`.
+    pub(crate) fn name_of(&self, r#type: crate::TypeVariant) -> Option<&str> {
+        let name = match r#type.name() {
+            "SError" => "Error",
+            name => name,
+        };
+        self.0
+            .keys()
+            .find(|n| n.eq_ignore_ascii_case(name))
+            .map(String::as_str)
+    }
+
+    /// Returns the type of the value at the path in the JSON representation
+    /// of a value of the named type, or `None` if the path is not in the type
+    /// or the value at the path is not of a named type, e.g. is an array.
+    ///
+    /// The path must contain only fields and indexes.
+    pub(crate) fn type_at(&self, name: &str, path: &[Segment]) -> Option<crate::TypeVariant> {
+        let root = Decl::Ref {
+            name: name.to_string(),
+        };
+        let mut decl = &root;
+        for segment in path {
+            decl = self.child(decl, segment)?;
+        }
+        self.variant(decl)
+    }
+
+    /// Returns the decl of the value at the segment of a value of the decl.
+    fn child<'a>(&'a self, decl: &'a Decl, segment: &Segment) -> Option<&'a Decl> {
+        match (decl, segment) {
+            // Optional values are the value itself or null in JSON.
+            (Decl::Optional { element }, _) => self.child(element, segment),
+            (Decl::Array { element, .. } | Decl::VarArray { element, .. }, Segment::Index(_)) => {
+                Some(element)
+            }
+            (Decl::Ref { name }, Segment::Field(key)) => match self.0.get(name)? {
+                Definition::Typedef { r#type, .. } => self.child(r#type, segment),
+                Definition::Struct { fields, .. } => fields
+                    .iter()
+                    .find(|f| json_name(&f.name) == json_name(key))
+                    .map(|f| &f.r#type),
+                Definition::Union { arms, .. } => {
+                    // The JSON field of an arm is named after the case, with
+                    // the prefix common to the members of the discriminant
+                    // enum removed, or `v` and the value for int cases. The
+                    // case with the shortest name ending with the field has
+                    // the least prefix, the common prefix.
+                    let key = json_name(key);
+                    arms.iter()
+                        .flat_map(|a| a.cases.iter().map(move |c| (a, c)))
+                        .filter_map(|(a, c)| {
+                            let case = c
+                                .name
+                                .as_deref()
+                                .map_or_else(|| format!("v{}", c.value), json_name);
+                            case.ends_with(&key).then_some((case.len(), a))
+                        })
+                        .min_by_key(|(len, _)| *len)
+                        .and_then(|(_, a)| a.r#type.as_ref())
+                }
+                _ => None,
+            },
+            (Decl::Ref { name }, Segment::Index(_)) => match self.0.get(name)? {
+                Definition::Typedef { r#type, .. } => self.child(r#type, segment),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn variant(&self, decl: &Decl) -> Option<crate::TypeVariant> {
+        let name = match decl {
+            Decl::Int => "Int32",
+            Decl::UnsignedInt => "Uint32",
+            Decl::Hyper => "Int64",
+            Decl::UnsignedHyper => "Uint64",
+            Decl::Optional { element } => return self.variant(element),
+            Decl::Ref { name } => name,
+            _ => return None,
+        };
+        crate::TypeVariant::VARIANTS
+            .into_iter()
+            .find(|v| self.name_of(*v).is_some_and(|n| n == name))
+    }
+}
+
+/// Returns the name normalised to compare the names of fields and cases in
+/// the XDR definitions with the names of fields in JSON, which are in snake
+/// case.
+fn json_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| *c != '_')
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// A decoded value, and the values decoded for its fields, elements, or arm.
+pub(crate) struct Node {
+    pub(crate) offset: usize,
+    /// The length of the value, or `None` if it failed to decode.
+    pub(crate) len: Option<usize>,
+    /// The field name, element index, or arm name of the value, or `None`
+    /// for the value being explained.
+    pub(crate) label: Option<String>,
+    pub(crate) r#type: String,
+    /// The decoded value, for values that have no children.
+    value: Option<String>,
+    /// The number of bytes of the value that could only have been those
+    /// bytes for the value to decode, i.e. enum values, union discriminants,
+    /// bools, optional flags, lengths, and padding.
+    pub(crate) checked: usize,
+    pub(crate) children: Vec<Node>,
+}
+
+impl Node {
+    /// Returns whether the value is a string, the only values that are output
+    /// quoted.
+    pub(crate) fn is_string(&self) -> bool {
+        self.value.as_ref().is_some_and(|v| v.starts_with('"'))
+    }
+
+    /// Returns the depth of the most deeply nested value.
+    pub(crate) fn depth(&self) -> usize {
+        1 + self
+            .children
+            .iter()
+            .map(Node::depth)
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn write(&self, out: &mut impl Write, bytes: &[u8], depth: usize) -> std::io::Result<()> {
+        let len = self.len.map_or_else(|| "?".to_string(), |l| l.to_string());
+        let indent = "  ".repeat(depth);
+        write!(out, "{:08x} {len:>6}  {indent}", self.offset)?;
+        if let Some(label) = &self.label {
+            write!(out, "{label}: ")?;
+        }
+        write!(out, "{}", self.r#type)?;
+        if let Some(value) = &self.value {
+            write!(out, " = {value}")?;
+            if let Some(len) = self.len {
+                write!(
+                    out,
+                    "  [{}]",
+                    hex::encode(&bytes[self.offset..self.offset + len])
+                )?;
+            }
+        }
+        writeln!(out)?;
+        for child in &self.children {
+            child.write(out, bytes, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) struct DecodeError {
+    pub(crate) offset: usize,
+    pub(crate) reason: String,
+    /// Whether the decode failed because the input ended.
+    pub(crate) truncated: bool,
+}
+
+/// Maximum depth of nested values, to bound the recursion on malicious
+/// input. Far deeper than any value the network accepts.
+const MAX_DEPTH: usize = 1000;
+
+/// Decodes the bytes as the named type, returning the decoded values, and
+/// the error that stopped the decode, if any.
+pub(crate) fn explain(
+    defs: &Definitions,
+    name: &str,
+    bytes: &[u8],
+) -> (Node, Result<(), DecodeError>) {
+    let mut walker = Walker {
+        defs,
+        bytes,
+        pos: 0,
+        depth: 0,
+        checked: 0,
+    };
+    let decl = Decl::Ref {
+        name: name.to_string(),
+    };
+    let mut nodes = Vec::new();
+    let mut result = walker.decl(None, &decl, &mut nodes);
+    if result.is_ok() && walker.pos < bytes.len() {
+        result = Err(walker.error(format!(
+            "{} trailing bytes after the value",
+            bytes.len() - walker.pos
+        )));
+    }
+    (nodes.remove(0), result)
+}
+
+struct Walker<'a> {
+    defs: &'a Definitions,
+    bytes: &'a [u8],
+    pos: usize,
+    depth: usize,
+    checked: usize,
+}
+
+impl Walker<'_> {
+    fn error(&self, reason: String) -> DecodeError {
+        DecodeError {
+            offset: self.pos,
+            reason,
+            truncated: false,
+        }
+    }
+
+    fn truncated(&self, expected: usize) -> DecodeError {
+        DecodeError {
+            offset: self.pos,
+            reason: format!("unexpected end of input, expected {expected} bytes"),
+            truncated: true,
+        }
+    }
+
+    /// Decodes a value of the decl, adding it to the nodes, whether or not it
+    /// decodes successfully.
+    fn decl(
+        &mut self,
+        label: Option<String>,
+        decl: &Decl,
+        nodes: &mut Vec<Node>,
+    ) -> Result<(), DecodeError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(self.error("depth limit exceeded".to_string()));
+        }
+        let mut node = Node {
+            offset: self.pos,
+            len: None,
+            label,
+            r#type: decl_name(decl),
+            value: None,
+            checked: 0,
+            children: Vec::new(),
+        };
+        let checked = self.checked;
+        self.depth += 1;
+        let result = self.decl_into(decl, &mut node);
+        self.depth -= 1;
+        if result.is_ok() {
+            node.len = Some(self.pos - node.offset);
+        }
+        node.checked = self.checked - checked;
+        nodes.push(node);
+        result
+    }
+
+    fn decl_into(&mut self, decl: &Decl, node: &mut Node) -> Result<(), DecodeError> {
+        match decl {
+            Decl::Int => node.value = Some(i32::from_be_bytes(self.take()?).to_string()),
+            Decl::UnsignedInt => node.value = Some(u32::from_be_bytes(self.take()?).to_string()),
+            Decl::Hyper => node.value = Some(i64::from_be_bytes(self.take()?).to_string()),
+            Decl::UnsignedHyper => node.value = Some(u64::from_be_bytes(self.take()?).to_string()),
+            Decl::Bool => {
+                node.value = Some(
+                    match u32::from_be_bytes(self.take()?) {
+                        0 => "false",
+                        1 => "true",
+                        v => return Err(self.error(format!("invalid bool {v}"))),
+                    }
+                    .to_string(),
+                );
+                self.checked += 4;
+            }
+            Decl::OpaqueFixed { size } => {
+                node.value = Some(hex::encode(self.padded(*size)?));
+            }
+            Decl::OpaqueVar { max_size } => {
+                let len = self.len(*max_size)?;
+                node.value = Some(hex::encode(self.padded(len)?));
+            }
+            Decl::String { max_size } => {
+                let len = self.len(*max_size)?;
+                node.value = Some(format!("\"{}\"", self.padded(len)?.escape_ascii()));
+            }
+            Decl::Array { element, count } => {
+                for i in 0..*count {
+                    self.decl(Some(format!("[{i}]")), element, &mut node.children)?;
+                }
+            }
+            Decl::VarArray { element, max_count } => {
+                let len_decl = Decl::UnsignedInt;
+                self.decl(Some("len".to_string()), &len_decl, &mut node.children)?;
+                let offset = self.pos - 4;
+                let len = u32::from_be_bytes(self.bytes[offset..self.pos].try_into().unwrap());
+                if let Some(max) = max_count.filter(|max| len > *max) {
+                    self.pos = offset;
+                    return Err(self.error(format!("length {len} exceeds the max of {max}")));
+                }
+                self.checked += 4;
+                for i in 0..len {
+                    self.decl(Some(format!("[{i}]")), element, &mut node.children)?;
+                }
+            }
+            Decl::Optional { element } => match u32::from_be_bytes(self.take()?) {
+                0 => {
+                    node.value = Some("none".to_string());
+                    self.checked += 4;
+                }
+                1 => {
+                    self.checked += 4;
+                    self.decl(Some("some".to_string()), element, &mut node.children)?;
+                }
+                v => {
+                    self.pos -= 4;
+                    return Err(self.error(format!("invalid optional flag {v}")));
+                }
+            },
+            Decl::Ref { name } => self.definition(name, node)?,
+        }
+        Ok(())
+    }
+
+    fn definition(&mut self, name: &str, node: &mut Node) -> Result<(), DecodeError> {
+        let defs = self.defs;
+        let def = defs
+            .0
+            .get(name)
+            .ok_or_else(|| self.error(format!("no definition for {name}")))?;
+        match def {
+            Definition::Typedef { r#type, .. } => self.decl_into(r#type, node)?,
+            Definition::Struct { fields, .. } => {
+                for f in fields {
+                    self.decl(Some(f.name.clone()), &f.r#type, &mut node.children)?;
+                }
+            }
+            Definition::Enum { members, .. } => {
+                let v = i32::from_be_bytes(self.take()?);
+                let Some(m) = members.iter().find(|m| m.value == i64::from(v)) else {
+                    self.pos -= 4;
+                    return Err(self.error(format!("invalid {name} value {v}")));
+                };
+                node.value = Some(format!("{} ({v})", m.name));
+                self.checked += 4;
+            }
+            Definition::Union {
+                discriminant, arms, ..
+            } => {
+                let label = Some(discriminant.name.clone());
+                let checked = self.checked;
+                self.decl(label, &discriminant.r#type, &mut node.children)?;
+                // Discriminants are ints, unsigned ints, or enums, and match
+                // the case values whichever way they are signed.
+                let offset = self.pos - 4;
+                let raw: [u8; 4] = self.bytes[offset..self.pos].try_into().unwrap();
+                let arm = arms.iter().find(|a| {
+                    a.cases.iter().any(|c| {
+                        c.value == i64::from(i32::from_be_bytes(raw))
+                            || c.value == i64::from(u32::from_be_bytes(raw))
+                    })
+                });
+                let Some(arm) = arm else {
+                    self.pos = offset;
+                    let v = i32::from_be_bytes(raw);
+                    return Err(self.error(format!("no arm of {name} for discriminant {v}")));
+                };
+                // Discriminants that are enums are already checked as enums.
+                if self.checked == checked {
+                    self.checked += 4;
+                }
+                if let Some(r#type) = &arm.r#type {
+                    self.decl(arm.name.clone(), r#type, &mut node.children)?;
+                }
+            }
+            Definition::Const { .. } => {
+                return Err(self.error(format!("{name} is a constant, not a type")));
+            }
+        }
+        Ok(())
+    }
+
+    /// Takes the next N bytes.
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        let bytes = self
+            .bytes
+            .get(self.pos..self.pos + N)
+            .ok_or_else(|| self.truncated(N))?;
+        self.pos += N;
+        Ok(bytes.try_into().unwrap())
+    }
+
+    /// Takes the length prefix of a variable length value.
+    fn len(&mut self, max: Option<u32>) -> Result<u32, DecodeError> {
+        let len = u32::from_be_bytes(self.take()?);
+        if let Some(max) = max.filter(|max| len > *max) {
+            self.pos -= 4;
+            return Err(self.error(format!("length {len} exceeds the max of {max}")));
+        }
+        self.checked += 4;
+        Ok(len)
+    }
+
+    /// Takes the next len bytes and the zero padding that follows them.
+    fn padded(&mut self, len: u32) -> Result<&[u8], DecodeError> {
+        let len = len as usize;
+        let padded_len = len.div_ceil(4) * 4;
+        let Some(padded) = self.bytes.get(self.pos..self.pos + padded_len) else {
+            return Err(self.truncated(padded_len));
+        };
+        if padded[len..].iter().any(|b| *b != 0) {
+            return Err(self.error("non-zero padding".to_string()));
+        }
+        self.pos += padded_len;
+        self.checked += padded_len - len;
+        Ok(&self.bytes[self.pos - padded_len..self.pos - padded_len + len])
+    }
+}
+
+fn decl_name(decl: &Decl) -> String {
+    match decl {
+        Decl::Int => "int".to_string(),
+        Decl::UnsignedInt => "unsigned int".to_string(),
+        Decl::Hyper => "hyper".to_string(),
+        Decl::UnsignedHyper => "unsigned hyper".to_string(),
+        Decl::Bool => "bool".to_string(),
+        Decl::OpaqueFixed { size } => format!("opaque[{size}]"),
+        Decl::OpaqueVar { max_size } => format!("opaque<{}>", max_name(*max_size)),
+        Decl::String { max_size } => format!("string<{}>", max_name(*max_size)),
+        Decl::Array { element, count } => format!("{}[{count}]", decl_name(element)),
+        Decl::VarArray { element, max_count } => {
+            format!("{}<{}>", decl_name(element), max_name(*max_count))
+        }
+        Decl::Optional { element } => format!("{}*", decl_name(element)),
+        Decl::Ref { name } => name.clone(),
+    }
+}
+
+fn max_name(max: Option<u32>) -> String {
+    max.map(|m| m.to_string()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Limits, ScVal, WriteXdr};
+
+    // The definitions have no XDR features enabled, and so have no definitions
+    // for the types that only exist with one enabled.
+    #[test]
+    #[cfg(not(any(
+        feature = "test_feature",
+        feature = "cap_0083",
+        feature = "cap_0085_executable_ref"
+    )))]
+    fn all_types_have_definitions() {
+        let defs = Definitions::load().unwrap();
+        for v in crate::TypeVariant::VARIANTS {
+            assert!(defs.name_of(v).is_some(), "{}", v.name());
+        }
+    }
+
+    #[test]
+    fn explain_value() {
+        let defs = Definitions::load().unwrap();
+        let bytes = ScVal::Symbol("abcde".try_into().unwrap())
+            .to_xdr(Limits::none())
+            .unwrap();
+        let (node, result) = explain(&defs, "SCVal", &bytes);
+        assert!(result.is_ok());
+        assert_eq!(node.len, Some(16));
+        let [disc, sym] = &node.children[..] else {
+            panic!("expected the discriminant and arm");
+        };
+        assert_eq!(disc.value.as_deref(), Some("SCV_SYMBOL (15)"));
+        assert_eq!((sym.offset, sym.len), (4, Some(12)));
+        assert_eq!(sym.value.as_deref(), Some("\"abcde\""));
+    }
+
+    #[test]
+    fn explain_partial_value() {
+        let defs = Definitions::load().unwrap();
+        let bytes = ScVal::Symbol("abcde".try_into().unwrap())
+            .to_xdr(Limits::none())
+            .unwrap();
+        let (node, result) = explain(&defs, "SCVal", &bytes[..10]);
+        let err = result.err().unwrap();
+        assert_eq!(err.offset, 8);
+        assert_eq!(node.len, None);
+        assert_eq!(node.children.len(), 2);
+        assert_eq!(node.children[0].len, Some(4));
+        assert_eq!(node.children[1].len, None);
+    }
+}
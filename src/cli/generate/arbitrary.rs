@@ -1,5 +1,6 @@
 use arbitrary::Unstructured;
 use clap::{Args, ValueEnum};
+use rand::SeedableRng;
 use std::{
     io::{stdout, Write},
     str::FromStr,
@@ -37,6 +38,15 @@ pub struct Cmd {
     #[arg(long = "output", value_enum, default_value_t)]
     pub output_format: OutputFormat,
 
+    /// Number of values to generate
+    #[arg(long, default_value_t = 1)]
+    pub count: u64,
+
+    /// Seed for the random number generator, to generate the same values on
+    /// every run, otherwise the values are generated from fresh randomness
+    #[arg(long)]
+    pub seed: Option<u64>,
+
     /// Keep generating values until the value's JSON representation contains
     /// this string. Useful for hinting toward a particular union/enum variant
     /// or sub-value. Repeat the flag to require several substrings; all of
@@ -54,9 +64,9 @@ pub struct Cmd {
 pub enum OutputFormat {
     Single,
     SingleBase64,
-    // TODO: Stream,
-    // TODO: StreamBase64,
-    // TODO: StreamFramed,
+    Stream,
+    StreamBase64,
+    StreamFramed,
     Json,
     JsonFormatted,
     Text,
@@ -72,34 +82,60 @@ impl Default for OutputFormat {
 // channels existed and each had their own run_curr/run_next invocation.
 macro_rules! run_x {
     ($f:ident) => {
-        fn $f(&self) -> Result<(), Error> {
+        fn $f(&self, out: &mut impl Write) -> Result<(), Error> {
             use crate::WriteXdr;
             let r#type = crate::TypeVariant::from_str(&self.r#type).map_err(|_| {
                 Error::UnknownType(self.r#type.clone(), &crate::TypeVariant::VARIANTS_STR)
             })?;
-            let (v, hint_json) = self.generate(r#type)?;
-            match self.output_format {
-                OutputFormat::Single => {
-                    let l = crate::Limits::none();
-                    stdout().write_all(&v.to_xdr(l)?)?
-                }
-                OutputFormat::SingleBase64 => {
-                    let l = crate::Limits::none();
-                    println!("{}", v.to_xdr_base64(l)?)
-                }
-                OutputFormat::Json => match hint_json {
-                    Some(json) => println!("{json}"),
-                    None => println!("{}", serde_json::to_string(&v)?),
-                },
-                OutputFormat::JsonFormatted => {
-                    println!("{}", serde_json::to_string_pretty(&v)?);
-                }
-                OutputFormat::Text => {
-                    let v = serde_json::to_value(v)?;
-                    let text = util::serde_json_value_to_text(v).ok_or(Error::TextUnsupported)?;
-                    println!("{text}")
+            let mut rng: Box<dyn rand::RngCore> = match self.seed {
+                Some(seed) => Box::new(rand::rngs::StdRng::seed_from_u64(seed)),
+                None => Box::new(rand::rng()),
+            };
+            let mut stream = Vec::new();
+            for _ in 0..self.count {
+                let (v, hint_json) = self.generate(r#type, &mut rng)?;
+                match self.output_format {
+                    OutputFormat::Single | OutputFormat::Stream => {
+                        let l = crate::Limits::none();
+                        out.write_all(&v.to_xdr(l)?)?
+                    }
+                    OutputFormat::SingleBase64 => {
+                        let l = crate::Limits::none();
+                        writeln!(out, "{}", v.to_xdr_base64(l)?)?
+                    }
+                    OutputFormat::StreamBase64 => {
+                        let l = crate::Limits::none();
+                        stream.extend(v.to_xdr(l)?);
+                    }
+                    OutputFormat::StreamFramed => {
+                        let l = crate::Limits::none();
+                        util::write_framed(out, &v.to_xdr(l)?)?
+                    }
+                    OutputFormat::Json => match hint_json {
+                        Some(json) => writeln!(out, "{json}")?,
+                        None => writeln!(out, "{}", serde_json::to_string(&v)?)?,
+                    },
+                    OutputFormat::JsonFormatted => {
+                        writeln!(out, "{}", serde_json::to_string_pretty(&v)?)?;
+                    }
+                    OutputFormat::Text => {
+                        let v = serde_json::to_value(&v)?;
+                        let text =
+                            util::serde_json_value_to_text(v).ok_or(Error::TextUnsupported)?;
+                        writeln!(out, "{text}")?
+                    }
                 }
             }
+            // A base64 stream is output once all of the values are written to
+            // it.
+            if self.output_format == OutputFormat::StreamBase64 {
+                use base64::Engine;
+                writeln!(
+                    out,
+                    "{}",
+                    base64::engine::general_purpose::STANDARD.encode(&stream)
+                )?;
+            }
             Ok(())
         }
     };
@@ -112,15 +148,19 @@ impl Cmd {
     ///
     /// If the command is configured with state that is invalid.
     pub fn run(&self) -> Result<(), Error> {
-        self.run_inner()
+        self.write_values(&mut stdout())
     }
 
-    run_x!(run_inner);
+    run_x!(write_values);
 
-    /// Generate a single arbitrary value of the given type from fresh random
-    /// bytes.
-    fn generate_one(type_: crate::TypeVariant) -> Result<crate::Type, Error> {
-        let r = rand::random::<[u8; 10_240]>();
+    /// Generate a single arbitrary value of the given type from random bytes
+    /// drawn from the rng.
+    fn generate_one(
+        type_: crate::TypeVariant,
+        rng: &mut impl rand::RngCore,
+    ) -> Result<crate::Type, Error> {
+        let mut r = [0u8; 10_240];
+        rng.fill_bytes(&mut r);
         let mut u = Unstructured::new(&r);
         Ok(crate::Type::arbitrary(type_, &mut u)?)
     }
@@ -136,13 +176,18 @@ impl Cmd {
     ///
     /// An attempt that fails to generate or serialize is treated as unlucky
     /// rather than fatal: it is skipped and the search continues with fresh
-    /// randomness, so a single bad draw doesn't abort the whole search.
-    fn generate(&self, type_: crate::TypeVariant) -> Result<(crate::Type, Option<String>), Error> {
+    /// randomness from the rng, so a single bad draw doesn't abort the whole
+    /// search.
+    fn generate(
+        &self,
+        type_: crate::TypeVariant,
+        rng: &mut impl rand::RngCore,
+    ) -> Result<(crate::Type, Option<String>), Error> {
         if self.hint.is_empty() {
-            return Ok((Self::generate_one(type_)?, None));
+            return Ok((Self::generate_one(type_, rng)?, None));
         }
         for _ in 0..self.hint_attempts {
-            let Ok(v) = Self::generate_one(type_) else {
+            let Ok(v) = Self::generate_one(type_, rng) else {
                 continue;
             };
             let Ok(json) = serde_json::to_string(&v) else {
@@ -168,11 +213,13 @@ mod tests {
         let cmd = Cmd {
             r#type: "TimeBounds".to_string(),
             output_format: OutputFormat::Json,
+            count: 1,
+            seed: None,
             hint: vec!["min_time".into()],
             hint_attempts: 1000,
         };
         let type_ = crate::TypeVariant::from_str("TimeBounds").unwrap();
-        let (v, json) = cmd.generate(type_).unwrap();
+        let (v, json) = cmd.generate(type_, &mut rand::rng()).unwrap();
         let json = json.expect("a hinted search returns the computed json");
         assert!(json.contains("min_time"));
         assert_eq!(json, serde_json::to_string(&v).unwrap());
@@ -183,11 +230,13 @@ mod tests {
         let cmd = Cmd {
             r#type: "TimeBounds".to_string(),
             output_format: OutputFormat::Json,
+            count: 1,
+            seed: None,
             hint: vec!["min_time".into(), "max_time".into()],
             hint_attempts: 1000,
         };
         let type_ = crate::TypeVariant::from_str("TimeBounds").unwrap();
-        let (_v, json) = cmd.generate(type_).unwrap();
+        let (_v, json) = cmd.generate(type_, &mut rand::rng()).unwrap();
         let json = json.expect("a hinted search returns the computed json");
         assert!(json.contains("min_time"));
         assert!(json.contains("max_time"));
@@ -198,12 +247,14 @@ mod tests {
         let cmd = Cmd {
             r#type: "TimeBounds".to_string(),
             output_format: OutputFormat::Json,
+            count: 1,
+            seed: None,
             hint: vec!["zzz_does_not_exist_xyzzy".into()],
             hint_attempts: 5,
         };
         let type_ = crate::TypeVariant::from_str("TimeBounds").unwrap();
         assert!(matches!(
-            cmd.generate(type_),
+            cmd.generate(type_, &mut rand::rng()),
             Err(Error::HintNotFound { .. })
         ));
     }
@@ -213,12 +264,14 @@ mod tests {
         let cmd = Cmd {
             r#type: "TimeBounds".to_string(),
             output_format: OutputFormat::Json,
+            count: 1,
+            seed: None,
             hint: vec!["min_time".into(), "zzz_does_not_exist_xyzzy".into()],
             hint_attempts: 5,
         };
         let type_ = crate::TypeVariant::from_str("TimeBounds").unwrap();
         assert!(matches!(
-            cmd.generate(type_),
+            cmd.generate(type_, &mut rand::rng()),
             Err(Error::HintNotFound { .. })
         ));
     }
@@ -228,11 +281,40 @@ mod tests {
         let cmd = Cmd {
             r#type: "TimeBounds".to_string(),
             output_format: OutputFormat::Json,
+            count: 1,
+            seed: None,
             hint: vec![],
             hint_attempts: 1,
         };
         let type_ = crate::TypeVariant::from_str("TimeBounds").unwrap();
-        let (_v, json) = cmd.generate(type_).unwrap();
+        let (_v, json) = cmd.generate(type_, &mut rand::rng()).unwrap();
         assert!(json.is_none());
     }
+
+    #[test]
+    fn seed_generates_same_values() {
+        let cmd = |seed| Cmd {
+            r#type: "TransactionEnvelope".to_string(),
+            output_format: OutputFormat::Stream,
+            count: 3,
+            seed: Some(seed),
+            hint: vec![],
+            hint_attempts: 1,
+        };
+        let write_values = |cmd: &Cmd| {
+            let mut out = Vec::new();
+            cmd.write_values(&mut out).unwrap();
+            out
+        };
+        let first = write_values(&cmd(1));
+        assert_eq!(write_values(&cmd(1)), first);
+        assert_ne!(write_values(&cmd(2)), first);
+        // The count values are all generated from the seeded rng, and so
+        // fewer values with the same seed are the first of them.
+        let mut fewer = cmd(1);
+        fewer.count = 1;
+        let fewer = write_values(&fewer);
+        assert!(first.len() > fewer.len());
+        assert_eq!(first[..fewer.len()], fewer);
+    }
 }
@@ -31,15 +31,19 @@ pub struct Cmd {
     // Output format to encode to
     #[arg(long = "output", value_enum, default_value_t)]
     pub output_format: OutputFormat,
+
+    /// Number of values to generate
+    #[arg(long, default_value_t = 1)]
+    pub count: u64,
 }
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
 pub enum OutputFormat {
     Single,
     SingleBase64,
-    // TODO: Stream,
-    // TODO: StreamBase64,
-    // TODO: StreamFramed,
+    Stream,
+    StreamBase64,
+    StreamFramed,
     Json,
     JsonFormatted,
     Text,
@@ -61,27 +65,49 @@ macro_rules! run_x {
                 Error::UnknownType(self.r#type.clone(), &crate::TypeVariant::VARIANTS_STR)
             })?;
             let v = crate::Type::default(r#type);
-            match self.output_format {
-                OutputFormat::Single => {
-                    let l = crate::Limits::none();
-                    stdout().write_all(&v.to_xdr(l)?)?
-                }
-                OutputFormat::SingleBase64 => {
-                    let l = crate::Limits::none();
-                    println!("{}", v.to_xdr_base64(l)?)
-                }
-                OutputFormat::Json => {
-                    println!("{}", serde_json::to_string(&v)?);
-                }
-                OutputFormat::JsonFormatted => {
-                    println!("{}", serde_json::to_string_pretty(&v)?);
-                }
-                OutputFormat::Text => {
-                    let v = serde_json::to_value(v)?;
-                    let text = util::serde_json_value_to_text(v).ok_or(Error::TextUnsupported)?;
-                    println!("{text}")
+            let mut out = stdout();
+            let mut stream = Vec::new();
+            for _ in 0..self.count {
+                match self.output_format {
+                    OutputFormat::Single | OutputFormat::Stream => {
+                        let l = crate::Limits::none();
+                        out.write_all(&v.to_xdr(l)?)?
+                    }
+                    OutputFormat::SingleBase64 => {
+                        let l = crate::Limits::none();
+                        println!("{}", v.to_xdr_base64(l)?)
+                    }
+                    OutputFormat::StreamBase64 => {
+                        let l = crate::Limits::none();
+                        stream.extend(v.to_xdr(l)?);
+                    }
+                    OutputFormat::StreamFramed => {
+                        let l = crate::Limits::none();
+                        util::write_framed(&mut out, &v.to_xdr(l)?)?
+                    }
+                    OutputFormat::Json => {
+                        println!("{}", serde_json::to_string(&v)?);
+                    }
+                    OutputFormat::JsonFormatted => {
+                        println!("{}", serde_json::to_string_pretty(&v)?);
+                    }
+                    OutputFormat::Text => {
+                        let v = serde_json::to_value(&v)?;
+                        let text =
+                            util::serde_json_value_to_text(v).ok_or(Error::TextUnsupported)?;
+                        println!("{text}")
+                    }
                 }
             }
+            // A base64 stream is output once all of the values are written to
+            // it.
+            if self.output_format == OutputFormat::StreamBase64 {
+                use base64::Engine;
+                println!(
+                    "{}",
+                    base64::engine::general_purpose::STANDARD.encode(&stream)
+                );
+            }
             Ok(())
         }
     };
@@ -1,25 +1,61 @@
+use base64::Engine;
 use clap::{Args, ValueEnum};
+use serde::Serialize;
 use std::cmp;
 use std::ffi::OsString;
 use std::fs::File;
 use std::io::{self, stdin, stdout, Cursor, Read, Write};
 use std::path::Path;
 
+use crate::cli::explain;
+use crate::{ReadXdr, ScMap, ScVal, Validate, WriteXdr};
+
 #[derive(thiserror::Error, Debug)]
 #[allow(clippy::enum_variant_names)]
 pub enum Error {
     #[error("error decoding XDR: {0}")]
     ReadXdr(#[from] crate::Error),
+    #[error("error decoding JSON: {0}")]
+    ReadJson(serde_json::Error),
+    #[error("error decoding base64: {0}")]
+    ReadBase64(base64::DecodeError),
     #[error("error reading file: {0}")]
     ReadFile(std::io::Error),
     #[error("error writing output: {0}")]
     WriteOutput(std::io::Error),
+    #[error("error generating JSON: {0}")]
+    GenerateJson(serde_json::Error),
+    #[error(transparent)]
+    Explain(#[from] explain::Error),
+    #[error("no type decodes the input")]
+    NotGuessed,
+    #[error(
+        "no type decodes the input, {type_} decodes the most, failing at offset {offset:#x} of {len} bytes: {reason}"
+    )]
+    PartiallyGuessed {
+        type_: &'static str,
+        offset: usize,
+        len: usize,
+        reason: String,
+    },
 }
 
+/// Guess the XDR type of a value
+///
+/// Outputs the types that the input decodes as, ranked by how much of the
+/// input the type constrains, i.e. the bytes of enum values, union
+/// discriminants, bools, optional flags, lengths, and padding, that would
+/// fail to decode if they were different. Types that decode the input but
+/// contain invalid content, such as invalid `ScVal`s or strings that are not
+/// UTF-8, or for JSON input do not encode to the same JSON, are ranked last.
+///
+/// If no type decodes a single value, reports the type that decodes the most
+/// of the input before failing.
 #[derive(Args, Debug, Clone)]
 #[command()]
 pub struct Cmd {
-    /// XDR or file containing XDR to decode, or stdin if empty
+    /// XDR, JSON, or file containing them to guess the type of, or stdin if
+    /// empty
     #[arg()]
     pub input: Option<OsString>,
 
@@ -31,7 +67,7 @@ pub struct Cmd {
     #[arg(long = "output", value_enum, default_value_t)]
     pub output_format: OutputFormat,
 
-    /// Certainty as an arbitrary value
+    /// Number of values of a stream that must decode
     #[arg(long, default_value = "2")]
     pub certainty: usize,
 }
@@ -43,6 +79,7 @@ pub enum InputFormat {
     Stream,
     StreamBase64,
     StreamFramed,
+    Json,
 }
 
 impl Default for InputFormat {
@@ -54,6 +91,7 @@ impl Default for InputFormat {
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
 pub enum OutputFormat {
     List,
+    Json,
 }
 
 impl Default for OutputFormat {
@@ -62,77 +100,123 @@ impl Default for OutputFormat {
     }
 }
 
+/// A type that the input decodes as, and the evidence that the input is of
+/// the type.
+#[derive(Serialize)]
+struct Guess {
+    r#type: &'static str,
+    /// Percentage of the bytes of the values that the type constrains.
+    confidence: usize,
+    bytes: usize,
+    checked_bytes: usize,
+    depth: usize,
+    valid: bool,
+}
+
 // TODO: Remove run_x macro, it exists only to reduce the diff from when curr/next
 // channels existed and each had their own run_curr/run_next invocation.
 macro_rules! run_x {
     ($f:ident) => {
         fn $f(&self) -> Result<(), Error> {
+            let defs = explain::Definitions::load()?;
             let mut rr = ResetRead::new(self.input()?);
-            let mut guessed = false;
+            let json: Option<serde_json::Value> = if self.input_format == InputFormat::Json {
+                let json = serde_json::from_reader(&mut rr).map_err(Error::ReadJson)?;
+                Some(json)
+            } else {
+                None
+            };
+            let mut guesses = Vec::new();
             'variants: for v in crate::TypeVariant::VARIANTS {
                 rr.reset();
-                let count: usize = match self.input_format {
+                let values = match self.input_format {
                     InputFormat::Single => {
                         let mut l = crate::Limited::new(&mut rr, crate::Limits::none());
-                        crate::Type::read_xdr_to_end(v, &mut l)
-                            .ok()
-                            .map(|_| 1)
-                            .unwrap_or_default()
+                        match crate::Type::read_xdr_to_end(v, &mut l) {
+                            Ok(t) => vec![t],
+                            Err(_) => continue 'variants,
+                        }
                     }
                     InputFormat::SingleBase64 => {
                         let mut l = crate::Limited::new(&mut rr, crate::Limits::none());
-                        crate::Type::read_xdr_base64_to_end(v, &mut l)
-                            .ok()
-                            .map(|_| 1)
-                            .unwrap_or_default()
+                        match crate::Type::read_xdr_base64_to_end(v, &mut l) {
+                            Ok(t) => vec![t],
+                            Err(_) => continue 'variants,
+                        }
                     }
                     InputFormat::Stream => {
                         let mut l = crate::Limited::new(&mut rr, crate::Limits::none());
                         let iter = crate::Type::read_xdr_iter(v, &mut l);
                         let iter = iter.take(self.certainty);
-                        let mut count = 0;
+                        let mut values = Vec::new();
                         for v in iter {
                             match v {
-                                Ok(_) => count += 1,
+                                Ok(v) => values.push(v),
                                 Err(_) => continue 'variants,
                             }
                         }
-                        count
+                        values
                     }
                     InputFormat::StreamBase64 => {
                         let mut l = crate::Limited::new(&mut rr, crate::Limits::none());
                         let iter = crate::Type::read_xdr_base64_iter(v, &mut l);
                         let iter = iter.take(self.certainty);
-                        let mut count = 0;
+                        let mut values = Vec::new();
                         for v in iter {
                             match v {
-                                Ok(_) => count += 1,
+                                Ok(v) => values.push(v),
                                 Err(_) => continue 'variants,
                             }
                         }
-                        count
+                        values
                     }
                     InputFormat::StreamFramed => {
                         let mut l = crate::Limited::new(&mut rr, crate::Limits::none());
                         let iter = crate::Type::read_xdr_framed_iter(v, &mut l);
                         let iter = iter.take(self.certainty);
-                        let mut count = 0;
+                        let mut values = Vec::new();
                         for v in iter {
                             match v {
-                                Ok(_) => count += 1,
+                                Ok(v) => values.push(v),
                                 Err(_) => continue 'variants,
                             }
                         }
-                        count
+                        values
                     }
+                    InputFormat::Json => match crate::Type::from_json(v, &mut rr) {
+                        Ok(t) => vec![t],
+                        Err(_) => continue 'variants,
+                    },
                 };
-                if count > 0 {
-                    writeln!(stdout(), "{}", v.name()).map_err(Error::WriteOutput)?;
-                    guessed = true;
+                if values.is_empty() {
+                    continue;
+                }
+                let mut guess = Guess::new(&defs, v, &values);
+                if let Some(json) = &json {
+                    guess.valid &= serde_json::to_value(&values[0]).ok().as_ref() == Some(json);
                 }
+                guesses.push(guess);
             }
-            if (!guessed) {
-                std::process::exit(1);
+            if guesses.is_empty() {
+                rr.reset();
+                return Err(self.partial(&defs, &mut rr)?);
+            }
+            // Sorting is stable, and so guesses that rank the same stay in
+            // the order of the types.
+            guesses.sort_by(|a, b| {
+                (b.valid, b.confidence, b.depth).cmp(&(a.valid, a.confidence, a.depth))
+            });
+            let mut out = stdout();
+            match self.output_format {
+                OutputFormat::List => {
+                    for g in &guesses {
+                        writeln!(out, "{}", g.r#type).map_err(Error::WriteOutput)?;
+                    }
+                }
+                OutputFormat::Json => {
+                    let json = serde_json::to_string(&guesses).map_err(Error::GenerateJson)?;
+                    writeln!(out, "{json}").map_err(Error::WriteOutput)?;
+                }
             }
             Ok(())
         }
@@ -144,7 +228,8 @@ impl Cmd {
     ///
     /// ## Errors
     ///
-    /// If the command is configured with state that is invalid.
+    /// If the command is configured with state that is invalid, or no type
+    /// decodes the input.
     pub fn run(&self) -> Result<(), Error> {
         let result = self.run_inner();
         match result {
@@ -156,6 +241,51 @@ impl Cmd {
 
     run_x!(run_inner);
 
+    /// Returns the error for input that no type decodes, reporting for single
+    /// values the type that decodes the most of the input before failing.
+    fn partial(&self, defs: &explain::Definitions, rr: &mut impl Read) -> Result<Error, Error> {
+        let mut bytes = Vec::new();
+        match self.input_format {
+            InputFormat::Single => {
+                rr.read_to_end(&mut bytes).map_err(Error::ReadFile)?;
+            }
+            InputFormat::SingleBase64 => {
+                let mut text = Vec::new();
+                rr.read_to_end(&mut text).map_err(Error::ReadFile)?;
+                text.retain(|b| !b.is_ascii_whitespace());
+                bytes = base64::engine::general_purpose::STANDARD
+                    .decode(text)
+                    .map_err(Error::ReadBase64)?;
+            }
+            InputFormat::Stream
+            | InputFormat::StreamBase64
+            | InputFormat::StreamFramed
+            | InputFormat::Json => return Ok(Error::NotGuessed),
+        }
+        let best = crate::TypeVariant::VARIANTS
+            .into_iter()
+            .filter_map(|v| {
+                let name = defs.name_of(v)?;
+                let (node, result) = explain::explain(defs, name, &bytes);
+                Some((v, result.err()?, node.checked))
+            })
+            // Types that fail because the input ended are consistent with the
+            // input being a truncated value, and so are preferred to those
+            // that fail on the input. Of the types that decode the most, the
+            // first that constrains the most of what it decodes is chosen.
+            .rev()
+            .max_by_key(|(_, e, checked)| (e.truncated, e.offset, *checked));
+        Ok(match best {
+            Some((v, e, _)) if e.offset > 0 => Error::PartiallyGuessed {
+                type_: v.name(),
+                offset: e.offset,
+                len: bytes.len(),
+                reason: e.reason,
+            },
+            _ => Error::NotGuessed,
+        })
+    }
+
     fn input(&self) -> Result<Box<dyn Read>, Error> {
         if let Some(input) = &self.input {
             let exist = Path::new(input).try_exists();
@@ -170,6 +300,63 @@ impl Cmd {
     }
 }
 
+impl Guess {
+    fn new(defs: &explain::Definitions, v: crate::TypeVariant, values: &[crate::Type]) -> Self {
+        let mut guess = Guess {
+            r#type: v.name(),
+            confidence: 0,
+            bytes: 0,
+            checked_bytes: 0,
+            depth: 0,
+            valid: true,
+        };
+        for t in values {
+            guess.valid &= serde_json::to_string(t).is_ok();
+            let Ok(xdr) = t.to_xdr(crate::Limits::none()) else {
+                guess.valid = false;
+                continue;
+            };
+            guess.bytes += xdr.len();
+            let Some(name) = defs.name_of(v) else {
+                continue;
+            };
+            let (node, result) = explain::explain(defs, name, &xdr);
+            if result.is_ok() {
+                guess.checked_bytes += node.checked;
+                guess.depth = guess.depth.max(node.depth());
+                guess.valid &= valid_content(&node, &xdr);
+            }
+        }
+        guess.confidence = (guess.checked_bytes * 100)
+            .checked_div(guess.bytes)
+            .unwrap_or_default();
+        guess
+    }
+}
+
+/// Returns whether the `ScVal`s and `ScMap`s in the value are valid, and the
+/// strings in the value are UTF-8.
+fn valid_content(node: &explain::Node, xdr: &[u8]) -> bool {
+    let Some(len) = node.len else {
+        return false;
+    };
+    let bytes = &xdr[node.offset..node.offset + len];
+    match node.r#type.as_str() {
+        "SCVal" => {
+            ScVal::from_xdr(bytes, crate::Limits::none()).is_ok_and(|v| v.validate().is_ok())
+        }
+        "SCMap" => {
+            ScMap::from_xdr(bytes, crate::Limits::none()).is_ok_and(|m| m.validate().is_ok())
+        }
+        _ if node.is_string() => {
+            // The string is preceded by its length, and followed by padding.
+            let str_len = u32::from_be_bytes(bytes[..4].try_into().unwrap()) as usize;
+            std::str::from_utf8(&bytes[4..4 + str_len]).is_ok()
+        }
+        _ => node.children.iter().all(|c| valid_content(c, xdr)),
+    }
+}
+
 struct ResetRead<R: Read> {
     read: R,
     buf: Vec<u8>,
@@ -215,7 +402,8 @@ mod test {
         io::{Cursor, Read},
     };
 
-    use super::ResetRead;
+    use super::{explain, Guess, ResetRead};
+    use crate::{ScSymbol, ScVal, TypeVariant};
 
     #[test]
     fn test_reset_read() -> Result<(), Box<dyn error::Error>> {
@@ -292,4 +480,34 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_guess_confidence() -> Result<(), Box<dyn error::Error>> {
+        let defs = explain::Definitions::load()?;
+        let values = [crate::Type::ScVal(Box::new(ScVal::U32(5)))];
+        let guess = Guess::new(&defs, TypeVariant::ScVal, &values);
+        // Only the discriminant of the 8 bytes is constrained.
+        assert_eq!(guess.bytes, 8);
+        assert_eq!(guess.checked_bytes, 4);
+        assert_eq!(guess.confidence, 50);
+        assert_eq!(guess.depth, 2);
+        assert!(guess.valid);
+        Ok(())
+    }
+
+    #[test]
+    fn test_guess_invalid_content() -> Result<(), Box<dyn error::Error>> {
+        let defs = explain::Definitions::load()?;
+        let values = [crate::Type::ScVal(Box::new(ScVal::Symbol(ScSymbol(
+            "not a symbol".try_into()?,
+        ))))];
+        let guess = Guess::new(&defs, TypeVariant::ScVal, &values);
+        assert!(!guess.valid);
+        let values = [crate::Type::ScString(Box::new(
+            vec![0xff, 0xfe].try_into()?,
+        ))];
+        let guess = Guess::new(&defs, TypeVariant::ScString, &values);
+        assert!(!guess.valid);
+        Ok(())
+    }
 }
@@ -0,0 +1,165 @@
+use std::ffi::OsString;
+use std::io::{stdout, Write};
+use std::{fmt::Debug, str::FromStr};
+
+use clap::{Args, ValueEnum};
+use sha2::{Digest, Sha256};
+
+use crate::cli::util;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("unknown type {0}, choose one of {1:?}")]
+    UnknownType(String, &'static [&'static str]),
+    #[error("type {0} cannot be hashed, choose one of {1:?}")]
+    UnsupportedType(String, &'static [&'static str]),
+    #[error("--network-passphrase is required to hash {0}")]
+    NetworkPassphraseRequired(&'static str),
+    #[error("error decoding XDR: {0}")]
+    ReadXdr(#[from] crate::Error),
+    #[error("error reading file: {0}")]
+    ReadFile(std::io::Error),
+    #[error("error writing output: {0}")]
+    WriteOutput(std::io::Error),
+}
+
+/// Hash XDR values
+///
+/// Outputs for each value, as hex:
+///
+///   `TransactionEnvelope`, `TransactionV1Envelope`, `TransactionV0Envelope`,
+///   `FeeBumpTransactionEnvelope`, `Transaction`, `TransactionV0`,
+///   `FeeBumpTransaction`: the transaction hash on the network,
+///
+///   `LedgerHeader`: the ledger hash,
+///
+///   `LedgerKey`: the key hash of the TTL entry of the ledger entry,
+///
+///   `TransactionSet`, `GeneralizedTransactionSet`, `TransactionResultSet`:
+///   the hash committed to in the ledger header,
+///
+///   `HashIdPreimage`: the id hash.
+///
+/// And for `ContractIdPreimage`, outputs the contract id on the network, as a
+/// strkey.
+#[derive(Args, Debug, Clone)]
+#[command()]
+pub struct Cmd {
+    /// XDR or files containing XDR to hash, or stdin if empty
+    #[arg()]
+    pub input: Vec<OsString>,
+
+    /// XDR type to hash
+    #[arg(long, default_value = "TransactionEnvelope")]
+    pub r#type: String,
+
+    /// Network passphrase, required to hash transactions and contract id
+    /// preimages
+    #[arg(long)]
+    pub network_passphrase: Option<String>,
+
+    // Input format
+    #[arg(long = "input", value_enum, default_value_t)]
+    pub input_format: InputFormat,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum InputFormat {
+    Single,
+    SingleBase64,
+    Json,
+}
+
+impl Default for InputFormat {
+    fn default() -> Self {
+        Self::SingleBase64
+    }
+}
+
+const TYPES: &[&str] = &[
+    "TransactionEnvelope",
+    "TransactionV1Envelope",
+    "TransactionV0Envelope",
+    "FeeBumpTransactionEnvelope",
+    "Transaction",
+    "TransactionV0",
+    "FeeBumpTransaction",
+    "LedgerHeader",
+    "LedgerKey",
+    "TransactionSet",
+    "GeneralizedTransactionSet",
+    "TransactionResultSet",
+    "HashIdPreimage",
+    "ContractIdPreimage",
+];
+
+impl Cmd {
+    /// Run the CLIs hash command.
+    ///
+    /// ## Errors
+    ///
+    /// If the command is configured with state that is invalid.
+    pub fn run(&self) -> Result<(), Error> {
+        let result = self.run_inner();
+        match result {
+            Ok(()) => Ok(()),
+            Err(Error::WriteOutput(e)) if e.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn run_inner(&self) -> Result<(), Error> {
+        let r#type = crate::TypeVariant::from_str(&self.r#type).map_err(|_| {
+            Error::UnknownType(self.r#type.clone(), &crate::TypeVariant::VARIANTS_STR)
+        })?;
+        if !TYPES.contains(&r#type.name()) {
+            return Err(Error::UnsupportedType(self.r#type.clone(), TYPES));
+        }
+        let mut input = util::parse_input(&self.input).map_err(Error::ReadFile)?;
+        for f in &mut input {
+            let t = match self.input_format {
+                InputFormat::Single => {
+                    let mut l = crate::Limited::new(f, crate::Limits::none());
+                    crate::Type::read_xdr_to_end(r#type, &mut l)?
+                }
+                InputFormat::SingleBase64 => {
+                    let mut l = crate::Limited::new(f, crate::Limits::none());
+                    crate::Type::read_xdr_base64_to_end(r#type, &mut l)?
+                }
+                InputFormat::Json => crate::Type::from_json(r#type, f)?,
+            };
+            let out = hash(&t, self.network_passphrase.as_deref())?;
+            writeln!(stdout(), "{out}").map_err(Error::WriteOutput)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the hash of the value, or for contract id preimages the contract
+/// id, as output by the hash command.
+pub(crate) fn hash(t: &crate::Type, network_passphrase: Option<&str>) -> Result<String, Error> {
+    let network_id = || {
+        let passphrase = network_passphrase.ok_or(Error::NetworkPassphraseRequired(t.name()))?;
+        Ok::<[u8; 32], Error>(Sha256::digest(passphrase).into())
+    };
+    let hash = match t {
+        crate::Type::TransactionEnvelope(v) => v.hash(network_id()?)?,
+        crate::Type::TransactionV1Envelope(v) => v.hash(network_id()?)?,
+        crate::Type::TransactionV0Envelope(v) => v.hash(network_id()?)?,
+        crate::Type::FeeBumpTransactionEnvelope(v) => v.hash(network_id()?)?,
+        crate::Type::Transaction(v) => v.hash(network_id()?)?,
+        crate::Type::TransactionV0(v) => v.hash(network_id()?)?,
+        crate::Type::FeeBumpTransaction(v) => v.hash(network_id()?)?,
+        crate::Type::LedgerHeader(v) => v.hash()?,
+        crate::Type::LedgerKey(v) => v.ttl_key_hash()?,
+        crate::Type::TransactionSet(v) => v.hash()?,
+        crate::Type::GeneralizedTransactionSet(v) => v.hash()?,
+        crate::Type::TransactionResultSet(v) => v.hash()?,
+        crate::Type::HashIdPreimage(v) => v.hash()?,
+        crate::Type::ContractIdPreimage(v) => {
+            return Ok(v.contract_id(network_id()?)?.to_string());
+        }
+        _ => return Err(Error::UnsupportedType(t.name().to_string(), TYPES)),
+    };
+    Ok(hex::encode(hash))
+}
@@ -1,10 +1,19 @@
 pub mod compare;
+pub mod convert;
 pub mod decode;
+pub mod diff;
 pub mod encode;
+pub mod explain;
 pub mod generate;
 pub mod guess;
+pub mod hash;
+pub mod query;
+pub mod repl;
+pub mod sign;
 pub mod types;
 mod util;
+pub mod validate;
+pub mod verify;
 mod version;
 pub mod xfile;
 
@@ -38,9 +47,18 @@ impl Root {
             Cmd::Types(c) => c.run()?,
             Cmd::Guess(c) => c.run()?,
             Cmd::Decode(c) => c.run()?,
+            Cmd::Query(c) => c.run()?,
+            Cmd::Repl(c) => c.run()?,
             Cmd::Encode(c) => c.run()?,
+            Cmd::Convert(c) => c.run()?,
+            Cmd::Explain(c) => c.run()?,
             Cmd::Generate(c) => c.run()?,
             Cmd::Compare(c) => c.run()?,
+            Cmd::Diff(c) => c.run()?,
+            Cmd::Hash(c) => c.run()?,
+            Cmd::Sign(c) => c.run()?,
+            Cmd::Validate(c) => c.run()?,
+            Cmd::Verify(c) => c.run()?,
             Cmd::Xfile(c) => c.run()?,
             Cmd::Version => version::Cmd::run(),
         }
@@ -58,9 +76,18 @@ pub enum Cmd {
     Guess(guess::Cmd),
     /// Decode XDR
     Decode(decode::Cmd),
+    Query(query::Cmd),
+    Repl(repl::Cmd),
     /// Encode XDR
     Encode(encode::Cmd),
+    Convert(convert::Cmd),
+    Explain(explain::Cmd),
     Compare(compare::Cmd),
+    Diff(diff::Cmd),
+    Hash(hash::Cmd),
+    Sign(sign::Cmd),
+    Validate(validate::Cmd),
+    Verify(verify::Cmd),
     Generate(generate::Cmd),
     /// Preprocess XDR .x files
     Xfile(xfile::Cmd),
@@ -80,12 +107,30 @@ pub enum Error {
     #[error(transparent)]
     Decode(#[from] decode::Error),
     #[error(transparent)]
+    Query(#[from] query::Error),
+    #[error(transparent)]
+    Repl(#[from] repl::Error),
+    #[error(transparent)]
     Encode(#[from] encode::Error),
     #[error(transparent)]
+    Convert(#[from] convert::Error),
+    #[error(transparent)]
+    Explain(#[from] explain::Error),
+    #[error(transparent)]
     Generate(#[from] generate::Error),
     #[error(transparent)]
     Compare(#[from] compare::Error),
     #[error(transparent)]
+    Diff(#[from] diff::Error),
+    #[error(transparent)]
+    Hash(#[from] hash::Error),
+    #[error(transparent)]
+    Sign(#[from] sign::Error),
+    #[error(transparent)]
+    Validate(#[from] validate::Error),
+    #[error(transparent)]
+    Verify(#[from] verify::Error),
+    #[error(transparent)]
     Xfile(#[from] xfile::Error),
 }
 
@@ -0,0 +1,472 @@
+use std::ffi::OsString;
+use std::io::{stdout, Write};
+use std::{fmt::Debug, str::FromStr};
+
+use clap::{Args, ValueEnum};
+use serde_json::Value;
+
+use crate::cli::explain::{self, Definitions};
+use crate::cli::{decode, util};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("unknown type {0}, choose one of {1:?}")]
+    UnknownType(String, &'static [&'static str]),
+    #[error(transparent)]
+    Decode(#[from] decode::Error),
+    #[error("invalid path {0:?}: {1}")]
+    InvalidPath(String, &'static str),
+    #[error("error reading file: {0}")]
+    ReadFile(std::io::Error),
+    #[error("error writing output: {0}")]
+    WriteOutput(std::io::Error),
+    #[error("error generating JSON: {0}")]
+    GenerateJson(#[from] serde_json::Error),
+    #[error("value doesn't have a text representation, use 'json' as output")]
+    TextUnsupported,
+    #[error(transparent)]
+    Explain(#[from] explain::Error),
+    #[error("type of value at {0} cannot be inferred, use --value-type")]
+    ValueTypeUnknown(String),
+    #[error("error encoding value as {0}: {1}")]
+    WriteXdr(&'static str, crate::Error),
+}
+
+/// Query values in decoded XDR with a path
+///
+/// The path selects values in the JSON representation of the XDR, as output
+/// by `decode`. The path is a list of fields separated by `.`, each
+/// optionally followed by one or more indexes into an array, e.g.
+/// `tx.tx.operations[0].body`. A `*` in place of a field selects every field
+/// or union arm, and `[*]` in place of an index selects every element, e.g.
+/// `tx.tx.operations[*].body.*.amount`.
+///
+/// Outputs each value selected, for each value in the input. To output XDR
+/// the type of each value selected is inferred from the XDR definitions of
+/// the type decoded, unless given with `--value-type`.
+#[derive(Args, Debug, Clone)]
+#[command()]
+pub struct Cmd {
+    /// Path of the values to select
+    #[arg()]
+    pub path: String,
+
+    /// XDR or files containing XDR to decode, or stdin if empty
+    #[arg()]
+    pub input: Vec<OsString>,
+
+    /// XDR type to decode
+    #[arg(long)]
+    pub r#type: String,
+
+    /// XDR type of the selected values when outputting XDR, inferred if not
+    /// given
+    #[arg(long)]
+    pub value_type: Option<String>,
+
+    // Input format of the XDR
+    #[arg(long = "input", value_enum, default_value_t)]
+    pub input_format: decode::InputFormat,
+
+    // Output format
+    #[arg(long = "output", value_enum, default_value_t)]
+    pub output_format: OutputFormat,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    Json,
+    JsonFormatted,
+    Text,
+    Single,
+    SingleBase64,
+}
+
+impl Default for OutputFormat {
+    fn default() -> Self {
+        Self::Json
+    }
+}
+
+/// A segment of a path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Segment {
+    /// Selects the field of an object with the name.
+    Field(String),
+    /// Selects every field of an object.
+    AnyField,
+    /// Selects the element of an array at the index.
+    Index(usize),
+    /// Selects every element of an array.
+    AnyIndex,
+}
+
+/// Parses a path into its segments.
+///
+/// ## Errors
+///
+/// If the path contains an empty field, or an index that is not a number or
+/// `*`, or is unclosed.
+pub fn parse_path(path: &str) -> Result<Vec<Segment>, Error> {
+    let invalid = |reason| Error::InvalidPath(path.to_string(), reason);
+    let mut segments = Vec::new();
+    let trimmed = path.strip_prefix('.').unwrap_or(path);
+    if trimmed.is_empty() {
+        return Ok(segments);
+    }
+    for part in trimmed.split('.') {
+        let (field, mut indexes) = part.split_at(part.find('[').unwrap_or(part.len()));
+        match field {
+            "" if indexes.is_empty() || !segments.is_empty() => {
+                return Err(invalid("empty field"));
+            }
+            "" => {}
+            "*" => segments.push(Segment::AnyField),
+            _ => segments.push(Segment::Field(field.to_string())),
+        }
+        while !indexes.is_empty() {
+            let (index, rest) = indexes[1..]
+                .split_once(']')
+                .ok_or_else(|| invalid("unclosed `[`"))?;
+            segments.push(match index {
+                "*" => Segment::AnyIndex,
+                _ => Segment::Index(
+                    index
+                        .parse()
+                        .map_err(|_| invalid("index is not a number or `*`"))?,
+                ),
+            });
+            if !rest.is_empty() && !rest.starts_with('[') {
+                return Err(invalid("expected `.` or `[` after `]`"));
+            }
+            indexes = rest;
+        }
+    }
+    Ok(segments)
+}
+
+/// Returns the values selected by the path segments in the JSON value.
+#[must_use]
+pub fn select<'a>(value: &'a Value, path: &[Segment]) -> Vec<&'a Value> {
+    select_paths(value, path)
+        .into_iter()
+        .map(|(_, v)| v)
+        .collect()
+}
+
+/// Returns the values selected by the path segments in the JSON value, each
+/// with the path to the value without wildcards.
+#[must_use]
+pub fn select_paths<'a>(value: &'a Value, path: &[Segment]) -> Vec<(Vec<Segment>, &'a Value)> {
+    let mut selected = Vec::new();
+    select_into(&mut selected, &mut Vec::new(), value, path);
+    selected
+}
+
+fn select_into<'a>(
+    selected: &mut Vec<(Vec<Segment>, &'a Value)>,
+    at: &mut Vec<Segment>,
+    value: &'a Value,
+    path: &[Segment],
+) {
+    let Some((segment, rest)) = path.split_first() else {
+        selected.push((at.clone(), value));
+        return;
+    };
+    let mut select_child = |segment, v| {
+        at.push(segment);
+        select_into(selected, at, v, rest);
+        at.pop();
+    };
+    match (segment, value) {
+        (Segment::Field(name), Value::Object(o)) => {
+            if let Some(v) = o.get(name) {
+                select_child(Segment::Field(name.clone()), v);
+            }
+        }
+        (Segment::AnyField, Value::Object(o)) => {
+            for (name, v) in o {
+                select_child(Segment::Field(name.clone()), v);
+            }
+        }
+        (Segment::Index(i), Value::Array(a)) => {
+            if let Some(v) = a.get(*i) {
+                select_child(Segment::Index(*i), v);
+            }
+        }
+        (Segment::AnyIndex, Value::Array(a)) => {
+            for (i, v) in a.iter().enumerate() {
+                select_child(Segment::Index(i), v);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Returns the path as text in the syntax parsed by [`parse_path`], `.` for
+/// the empty path.
+pub(crate) fn path_string(path: &[Segment]) -> String {
+    if path.is_empty() {
+        return ".".to_string();
+    }
+    let mut s = String::new();
+    for segment in path {
+        match segment {
+            Segment::Field(name) => {
+                s.push('.');
+                s.push_str(name);
+            }
+            Segment::AnyField => s.push_str(".*"),
+            Segment::Index(i) => {
+                s.push('[');
+                s.push_str(&i.to_string());
+                s.push(']');
+            }
+            Segment::AnyIndex => s.push_str("[*]"),
+        }
+    }
+    s
+}
+
+impl Cmd {
+    /// Run the CLIs query command.
+    ///
+    /// ## Errors
+    ///
+    /// If the command is configured with state that is invalid.
+    pub fn run(&self) -> Result<(), Error> {
+        let result = self.run_inner();
+        match result {
+            Ok(()) => Ok(()),
+            Err(Error::WriteOutput(e)) if e.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn run_inner(&self) -> Result<(), Error> {
+        let path = parse_path(&self.path)?;
+        let r#type = parse_type(&self.r#type)?;
+        let value_type = self.value_type.as_deref().map(parse_type).transpose()?;
+        // The definitions are only needed to infer the type of values output
+        // as XDR.
+        let defs = if value_type.is_none()
+            && matches!(
+                self.output_format,
+                OutputFormat::Single | OutputFormat::SingleBase64
+            ) {
+            Some(Definitions::load()?)
+        } else {
+            None
+        };
+        let mut input = util::parse_input(&self.input).map_err(Error::ReadFile)?;
+        for f in &mut input {
+            decode::each_value(r#type, self.input_format, f, |t| {
+                let v = serde_json::to_value(t)?;
+                for (at, v) in select_paths(&v, &path) {
+                    let value_type = match (value_type, &defs) {
+                        (Some(value_type), _) => Some(value_type),
+                        (None, Some(defs)) => Some(infer_type(defs, r#type, &at)?),
+                        (None, None) => None,
+                    };
+                    self.out(v, value_type)?;
+                }
+                Ok::<_, Error>(())
+            })?;
+        }
+        Ok(())
+    }
+
+    fn out(&self, v: &Value, value_type: Option<crate::TypeVariant>) -> Result<(), Error> {
+        use crate::WriteXdr;
+        let to_xdr = || {
+            let value_type = value_type.ok_or(Error::ValueTypeUnknown(".".to_string()))?;
+            let t = crate::Type::from_json(value_type, v.to_string().as_bytes())
+                .map_err(|e| Error::WriteXdr(value_type.name(), e))?;
+            Ok::<_, Error>((value_type, t))
+        };
+        let mut out = stdout();
+        match self.output_format {
+            OutputFormat::Json => writeln!(out, "{}", serde_json::to_string(v)?),
+            OutputFormat::JsonFormatted => writeln!(out, "{}", serde_json::to_string_pretty(v)?),
+            OutputFormat::Text => {
+                let text =
+                    util::serde_json_value_to_text(v.clone()).ok_or(Error::TextUnsupported)?;
+                writeln!(out, "{text}")
+            }
+            OutputFormat::Single => {
+                let (value_type, t) = to_xdr()?;
+                let xdr = t
+                    .to_xdr(crate::Limits::none())
+                    .map_err(|e| Error::WriteXdr(value_type.name(), e))?;
+                out.write_all(&xdr)
+            }
+            OutputFormat::SingleBase64 => {
+                let (value_type, t) = to_xdr()?;
+                let xdr = t
+                    .to_xdr_base64(crate::Limits::none())
+                    .map_err(|e| Error::WriteXdr(value_type.name(), e))?;
+                writeln!(out, "{xdr}")
+            }
+        }
+        .map_err(Error::WriteOutput)
+    }
+}
+
+/// Returns the type of the value at the path in a value of the type.
+fn infer_type(
+    defs: &Definitions,
+    r#type: crate::TypeVariant,
+    path: &[Segment],
+) -> Result<crate::TypeVariant, Error> {
+    defs.name_of(r#type)
+        .and_then(|name| defs.type_at(name, path))
+        .ok_or_else(|| Error::ValueTypeUnknown(path_string(path)))
+}
+
+fn parse_type(r#type: &str) -> Result<crate::TypeVariant, Error> {
+    crate::TypeVariant::from_str(r#type)
+        .map_err(|_| Error::UnknownType(r#type.to_string(), &crate::TypeVariant::VARIANTS_STR))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn field(name: &str) -> Segment {
+        Segment::Field(name.to_string())
+    }
+
+    #[test]
+    fn parse_paths() {
+        assert_eq!(parse_path("").unwrap(), vec![]);
+        assert_eq!(parse_path(".").unwrap(), vec![]);
+        assert_eq!(
+            parse_path("tx.operations[0].body").unwrap(),
+            vec![
+                field("tx"),
+                field("operations"),
+                Segment::Index(0),
+                field("body"),
+            ]
+        );
+        assert_eq!(
+            parse_path(".a[*][12].*").unwrap(),
+            vec![
+                field("a"),
+                Segment::AnyIndex,
+                Segment::Index(12),
+                Segment::AnyField,
+            ]
+        );
+        assert_eq!(
+            parse_path("[1].a").unwrap(),
+            vec![Segment::Index(1), field("a")]
+        );
+    }
+
+    #[test]
+    fn parse_invalid_paths() {
+        for (path, reason) in [
+            ("a..b", "empty field"),
+            ("a.", "empty field"),
+            ("..", "empty field"),
+            ("a.[0]", "empty field"),
+            ("a[0", "unclosed `[`"),
+            ("a[x]", "index is not a number or `*`"),
+            ("a[-1]", "index is not a number or `*`"),
+            ("a[]", "index is not a number or `*`"),
+            ("a[0]b", "expected `.` or `[` after `]`"),
+        ] {
+            match parse_path(path) {
+                Err(Error::InvalidPath(p, r)) => assert_eq!((&p[..], r), (path, reason)),
+                r => panic!("{path}: {r:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn select_values() {
+        let v = json!({
+            "a": [{"b": 1, "c": 2}, {"b": 3}],
+            "d": {"e": 4},
+        });
+        let s = |path| select(&v, &parse_path(path).unwrap());
+        assert_eq!(s("."), vec![&v]);
+        assert_eq!(s("a[1].b"), vec![&json!(3)]);
+        assert_eq!(s("a[*].b"), vec![&json!(1), &json!(3)]);
+        assert_eq!(s("a[0].*"), vec![&json!(1), &json!(2)]);
+        assert_eq!(s("*.e"), vec![&json!(4)]);
+        assert_eq!(s("a[*].*"), vec![&json!(1), &json!(2), &json!(3)]);
+        // Paths that are not in the value, or that index an object or name a
+        // field of an array, select nothing.
+        assert_eq!(s("a[2]"), Vec::<&Value>::new());
+        assert_eq!(s("a.b"), Vec::<&Value>::new());
+        assert_eq!(s("d[0]"), Vec::<&Value>::new());
+        assert_eq!(s("d.e.f"), Vec::<&Value>::new());
+    }
+
+    #[test]
+    fn select_paths_without_wildcards() {
+        let v = json!({"a": [{"b": 1}, {"b": 2}]});
+        let paths = select_paths(&v, &parse_path("a[*].*").unwrap())
+            .into_iter()
+            .map(|(p, _)| path_string(&p))
+            .collect::<Vec<_>>();
+        assert_eq!(paths, ["[0].b", "[1].b"].map(|p| format!(".a{p}")));
+    }
+
+    #[test]
+    fn path_strings() {
+        for path in [".", ".a", ".a[0][*].*.b", "[1].a"] {
+            assert_eq!(path_string(&parse_path(path).unwrap()), path);
+        }
+    }
+
+    #[test]
+    fn infer_types() {
+        use crate::TypeVariant as T;
+        let defs = Definitions::load().unwrap();
+        let infer = |r#type, path| infer_type(&defs, r#type, &parse_path(path).unwrap()).ok();
+        // Fields of structs, elements of arrays, and typedefs.
+        assert_eq!(
+            infer(T::TransactionEnvelope, "tx.tx.operations[0]"),
+            Some(T::Operation)
+        );
+        assert_eq!(
+            infer(T::TransactionEnvelope, "tx.tx.source_account"),
+            Some(T::MuxedAccount)
+        );
+        assert_eq!(infer(T::TransactionEnvelope, "tx.tx.fee"), Some(T::Uint32));
+        // Arms of unions with enum and int discriminants.
+        assert_eq!(
+            infer(T::TransactionEnvelope, "tx_v0.tx.seq_num"),
+            Some(T::SequenceNumber)
+        );
+        assert_eq!(
+            infer(T::TransactionEnvelope, "tx.tx.operations[0].body.payment"),
+            Some(T::PaymentOp)
+        );
+        assert_eq!(
+            infer(
+                T::TransactionEnvelope,
+                "tx.tx.operations[0].body.path_payment_strict_receive"
+            ),
+            Some(T::PathPaymentStrictReceiveOp)
+        );
+        assert_eq!(
+            infer(T::TransactionExt, "v1"),
+            Some(T::SorobanTransactionData)
+        );
+        // Optional values.
+        assert_eq!(
+            infer(T::TransactionEnvelope, "tx.tx.operations[0].source_account"),
+            Some(T::MuxedAccount)
+        );
+        // Values of types with no name, and paths not in the type.
+        assert_eq!(infer(T::TransactionEnvelope, "tx.tx.operations"), None);
+        assert_eq!(infer(T::TransactionEnvelope, "tx.tx.memo.text"), None);
+        assert_eq!(infer(T::TransactionEnvelope, "tx.tx.nope"), None);
+        assert_eq!(infer(T::TransactionEnvelope, "tx[0]"), None);
+    }
+}
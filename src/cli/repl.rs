@@ -0,0 +1,471 @@
+use std::ffi::OsString;
+use std::io::{stdin, stdout, BufRead, IsTerminal, Write};
+use std::{fmt::Debug, str::FromStr};
+
+use clap::Args;
+use serde_json::Value;
+
+use crate::cli::convert::{self, Format};
+use crate::cli::query::{self, path_string, Segment};
+use crate::cli::{decode, hash, util};
+use crate::WriteXdr;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("unknown type {0}, choose one of {1:?}")]
+    UnknownType(String, &'static [&'static str]),
+    #[error(transparent)]
+    Convert(#[from] convert::Error),
+    #[error(transparent)]
+    Decode(#[from] decode::Error),
+    #[error(transparent)]
+    Query(#[from] query::Error),
+    #[error(transparent)]
+    Hash(#[from] hash::Error),
+    #[error("invalid value: {0}")]
+    Value(#[from] crate::Error),
+    #[error("error generating JSON: {0}")]
+    GenerateJson(serde_json::Error),
+    #[error("error decoding JSON: {0}")]
+    ReadJson(serde_json::Error),
+    #[error("error reading file: {0}")]
+    ReadFile(std::io::Error),
+    #[error("error reading command: {0}")]
+    ReadCommand(std::io::Error),
+    #[error("error writing output: {0}")]
+    WriteOutput(std::io::Error),
+    #[error("no values in the input")]
+    NoValues,
+    #[error("unknown command {0}, run `help` for the commands")]
+    UnknownCommand(String),
+    #[error("usage: {0}")]
+    Usage(&'static str),
+    #[error("no value {0}, there are {1} values")]
+    NoValue(usize, usize),
+    #[error("no value at {0}")]
+    NoValueAt(String),
+    #[error("path {0} selects more than one value")]
+    MultiplePath(String),
+    #[error("the type of the value at {0} is unknown, give its type")]
+    TypeRequired(String),
+}
+
+/// Explore XDR values interactively
+///
+/// Loads the values, then reads commands from stdin, one per line. Run `help`
+/// for the commands.
+#[derive(Args, Debug, Clone)]
+#[command()]
+pub struct Cmd {
+    /// XDR, JSON, or files containing them to load
+    #[arg(required = true)]
+    pub input: Vec<OsString>,
+
+    /// XDR type of the values
+    #[arg(long)]
+    pub r#type: String,
+
+    /// Network passphrase, required to hash transactions and contract id
+    /// preimages
+    #[arg(long)]
+    pub network_passphrase: Option<String>,
+
+    // Input format
+    #[arg(long = "input", value_enum, default_value_t = Format::SingleBase64)]
+    pub input_format: Format,
+}
+
+const HELP: &str = "\
+values               List the values
+value N              Switch to value N
+type TYPE            View the value as another type with the same XDR
+pwd                  Print the path of the current location
+ls                   List the fields or elements at the current location
+cd PATH|..           Move to the path, or up one
+json [PATH]          Print the value at the path as JSON
+xdr [TYPE]           Print the value at the current location as base64 XDR,
+                     the type is required below the root
+set PATH JSON        Set the value at the path, `.` for the current location
+hash                 Print the hash of the value
+help                 Print this help
+quit                 Exit
+
+Paths are relative to the current location, or to the root if they start
+with `/`.";
+
+impl Cmd {
+    /// Run the CLIs repl command.
+    ///
+    /// ## Errors
+    ///
+    /// If the command is configured with state that is invalid.
+    pub fn run(&self) -> Result<(), Error> {
+        let result = self.run_inner();
+        match result {
+            Ok(()) => Ok(()),
+            Err(Error::WriteOutput(e)) if e.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn run_inner(&self) -> Result<(), Error> {
+        let r#type = crate::TypeVariant::from_str(&self.r#type).map_err(|_| {
+            Error::UnknownType(self.r#type.clone(), &crate::TypeVariant::VARIANTS_STR)
+        })?;
+        let mut values = Vec::new();
+        let mut input = util::parse_input(&self.input).map_err(Error::ReadFile)?;
+        for f in &mut input {
+            convert::each_value(r#type, self.input_format, f, |t| {
+                values.push(t.clone());
+                Ok::<(), Error>(())
+            })?;
+        }
+        if values.is_empty() {
+            return Err(Error::NoValues);
+        }
+        let mut repl = Repl {
+            values,
+            current: 0,
+            path: Vec::new(),
+            network_passphrase: self.network_passphrase.clone(),
+        };
+        // The prompt is only output to terminals, so that the output of
+        // commands piped in is only the output of the commands.
+        let prompt = stdin().is_terminal();
+        let mut lines = stdin().lock().lines();
+        loop {
+            if prompt {
+                let path = path_string(&repl.path);
+                write!(stdout(), "{}:{path}> ", repl.current).map_err(Error::WriteOutput)?;
+                stdout().flush().map_err(Error::WriteOutput)?;
+            }
+            let Some(line) = lines.next() else {
+                return Ok(());
+            };
+            let line = line.map_err(Error::ReadCommand)?;
+            let (command, args) = line
+                .trim()
+                .split_once(char::is_whitespace)
+                .unwrap_or((line.trim(), ""));
+            match repl.command(&mut stdout(), command, args.trim()) {
+                Ok(true) => {}
+                Ok(false) => return Ok(()),
+                Err(e @ Error::WriteOutput(_)) => return Err(e),
+                Err(e) => writeln!(stdout(), "error: {e}").map_err(Error::WriteOutput)?,
+            }
+        }
+    }
+}
+
+struct Repl {
+    values: Vec<crate::Type>,
+    current: usize,
+    /// The path of the current location in the JSON of the current value.
+    path: Vec<Segment>,
+    network_passphrase: Option<String>,
+}
+
+impl Repl {
+    /// Runs the command, returning false if the repl should exit.
+    fn command(&mut self, out: &mut impl Write, command: &str, args: &str) -> Result<bool, Error> {
+        match command {
+            "" => {}
+            "help" => writeln!(out, "{HELP}").map_err(Error::WriteOutput)?,
+            "quit" | "exit" => return Ok(false),
+            "values" => {
+                for (i, v) in self.values.iter().enumerate() {
+                    let marker = if i == self.current { '*' } else { ' ' };
+                    writeln!(out, "{marker} {i}: {}", v.name()).map_err(Error::WriteOutput)?;
+                }
+            }
+            "value" => {
+                let i = args.parse().map_err(|_| Error::Usage("value N"))?;
+                if i >= self.values.len() {
+                    return Err(Error::NoValue(i, self.values.len()));
+                }
+                self.current = i;
+                self.path.clear();
+            }
+            "type" => {
+                let r#type = parse_type(args)?;
+                let xdr = self.values[self.current].to_xdr(crate::Limits::none())?;
+                let t = crate::Type::from_xdr(r#type, xdr, crate::Limits::none())?;
+                self.values[self.current] = t;
+                self.path.clear();
+            }
+            "pwd" => writeln!(out, "{}", path_string(&self.path)).map_err(Error::WriteOutput)?,
+            "ls" => self.ls(out)?,
+            "cd" => {
+                let path = match args {
+                    "" => return Err(Error::Usage("cd PATH|..")),
+                    ".." => {
+                        let mut path = self.path.clone();
+                        path.pop();
+                        path
+                    }
+                    _ => self.resolve(args)?,
+                };
+                at(&self.json()?, &path)?;
+                self.path = path;
+            }
+            "json" => {
+                let json = self.json()?;
+                let v = at(&json, &self.resolve(args)?)?;
+                let v = serde_json::to_string_pretty(v).map_err(Error::GenerateJson)?;
+                writeln!(out, "{v}").map_err(Error::WriteOutput)?;
+            }
+            "xdr" => {
+                let t = if args.is_empty() {
+                    if !self.path.is_empty() {
+                        return Err(Error::TypeRequired(path_string(&self.path)));
+                    }
+                    self.values[self.current].clone()
+                } else {
+                    let json = self.json()?;
+                    let v = at(&json, &self.path)?.to_string();
+                    crate::Type::from_json(parse_type(args)?, v.as_bytes())?
+                };
+                let b64 = t.to_xdr_base64(crate::Limits::none())?;
+                writeln!(out, "{b64}").map_err(Error::WriteOutput)?;
+            }
+            "set" => self.set(args)?,
+            "hash" => {
+                let hash = hash::hash(
+                    &self.values[self.current],
+                    self.network_passphrase.as_deref(),
+                )?;
+                writeln!(out, "{hash}").map_err(Error::WriteOutput)?;
+            }
+            _ => return Err(Error::UnknownCommand(command.to_string())),
+        }
+        Ok(true)
+    }
+
+    fn ls(&self, out: &mut impl Write) -> Result<(), Error> {
+        let json = self.json()?;
+        match at(&json, &self.path)? {
+            Value::Object(o) => {
+                for (k, v) in o {
+                    writeln!(out, "{k}: {}", summary(v)).map_err(Error::WriteOutput)?;
+                }
+            }
+            Value::Array(a) => {
+                for (i, v) in a.iter().enumerate() {
+                    writeln!(out, "[{i}]: {}", summary(v)).map_err(Error::WriteOutput)?;
+                }
+            }
+            v => writeln!(out, "{v}").map_err(Error::WriteOutput)?,
+        }
+        Ok(())
+    }
+
+    fn set(&mut self, args: &str) -> Result<(), Error> {
+        let (path, json) = args
+            .split_once(char::is_whitespace)
+            .ok_or(Error::Usage("set PATH JSON"))?;
+        let path = self.resolve(path)?;
+        let replacement = serde_json::from_str(json.trim()).map_err(Error::ReadJson)?;
+        let mut value = self.json()?;
+        *at_mut(&mut value, &path).ok_or_else(|| Error::NoValueAt(path_string(&path)))? =
+            replacement;
+        let r#type = self.values[self.current].variant();
+        let t = crate::Type::from_json(r#type, value.to_string().as_bytes())?;
+        self.values[self.current] = t;
+        // The edit may have changed the shape of the value such that the
+        // current location no longer exists.
+        if at(&self.json()?, &self.path).is_err() {
+            self.path.clear();
+        }
+        Ok(())
+    }
+
+    fn json(&self) -> Result<Value, Error> {
+        serde_json::to_value(&self.values[self.current]).map_err(Error::GenerateJson)
+    }
+
+    /// Returns the path relative to the current location, or to the root if
+    /// it starts with `/`.
+    fn resolve(&self, path: &str) -> Result<Vec<Segment>, Error> {
+        if let Some(path) = path.strip_prefix('/') {
+            return Ok(query::parse_path(path)?);
+        }
+        let mut resolved = self.path.clone();
+        resolved.extend(query::parse_path(path)?);
+        Ok(resolved)
+    }
+}
+
+/// Returns the value at the path, which must select one value.
+fn at<'a>(json: &'a Value, path: &[Segment]) -> Result<&'a Value, Error> {
+    match query::select(json, path)[..] {
+        [v] => Ok(v),
+        [] => Err(Error::NoValueAt(path_string(path))),
+        _ => Err(Error::MultiplePath(path_string(path))),
+    }
+}
+
+fn at_mut<'a>(value: &'a mut Value, path: &[Segment]) -> Option<&'a mut Value> {
+    path.iter()
+        .try_fold(value, |v, segment| match (segment, v) {
+            (Segment::Field(name), Value::Object(o)) => o.get_mut(name),
+            (Segment::Index(i), Value::Array(a)) => a.get_mut(*i),
+            _ => None,
+        })
+}
+
+/// Returns a one line summary of the value, the value itself for values
+/// other than objects and arrays.
+fn summary(v: &Value) -> String {
+    match v {
+        Value::Object(o) => format!("{{{} fields}}", o.len()),
+        Value::Array(a) => format!("[{} elements]", a.len()),
+        v => v.to_string(),
+    }
+}
+
+fn parse_type(r#type: &str) -> Result<crate::TypeVariant, Error> {
+    if r#type.is_empty() {
+        return Err(Error::Usage("type TYPE"));
+    }
+    crate::TypeVariant::from_str(r#type)
+        .map_err(|_| Error::UnknownType(r#type.to_string(), &crate::TypeVariant::VARIANTS_STR))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ScVal, ScVec};
+
+    fn repl(values: Vec<ScVal>) -> Repl {
+        Repl {
+            values: values
+                .into_iter()
+                .map(|v| crate::Type::ScVal(Box::new(v)))
+                .collect(),
+            current: 0,
+            path: Vec::new(),
+            network_passphrase: None,
+        }
+    }
+
+    fn vec(items: impl IntoIterator<Item = u32>) -> ScVal {
+        let items = items.into_iter().map(ScVal::U32).collect::<Vec<_>>();
+        ScVal::Vec(Some(ScVec(items.try_into().unwrap())))
+    }
+
+    /// Runs the command line, returning its output.
+    fn run(repl: &mut Repl, line: &str) -> Result<String, Error> {
+        let (command, args) = line.split_once(' ').unwrap_or((line, ""));
+        let mut out = Vec::new();
+        repl.command(&mut out, command, args)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    fn value(repl: &Repl) -> &crate::Type {
+        &repl.values[repl.current]
+    }
+
+    #[test]
+    fn set() {
+        let mut repl = repl(vec![vec([1, 2])]);
+        run(&mut repl, "cd vec[1]").unwrap();
+        run(&mut repl, r#"set . {"u32":3}"#).unwrap();
+        assert_eq!(value(&repl), &crate::Type::ScVal(Box::new(vec([1, 3]))));
+        assert_eq!(run(&mut repl, "pwd").unwrap(), ".vec[1]\n");
+        // Paths starting with `/` are from the root.
+        run(&mut repl, r#"set /vec[0] {"u32":4}"#).unwrap();
+        assert_eq!(value(&repl), &crate::Type::ScVal(Box::new(vec([4, 3]))));
+        // Values that are not of the type are not set.
+        assert!(run(&mut repl, r#"set . {"u32":-1}"#).is_err());
+        assert!(run(&mut repl, "set .nope 1").is_err());
+        assert_eq!(value(&repl), &crate::Type::ScVal(Box::new(vec([4, 3]))));
+    }
+
+    #[test]
+    fn set_shape_change_resets_path() {
+        let mut repl = repl(vec![vec([1, 2])]);
+        run(&mut repl, "cd vec[1]").unwrap();
+        run(&mut repl, r#"set /vec [{"u32":1}]"#).unwrap();
+        assert_eq!(repl.path, []);
+        run(&mut repl, "cd vec[0]").unwrap();
+        run(&mut repl, r#"set / {"u32":1}"#).unwrap();
+        assert_eq!(value(&repl), &crate::Type::ScVal(Box::new(ScVal::U32(1))));
+        assert_eq!(run(&mut repl, "pwd").unwrap(), ".\n");
+    }
+
+    #[test]
+    fn type_() {
+        let mut repl = repl(vec![ScVal::U32(1)]);
+        run(&mut repl, "cd u32").unwrap();
+        // The XDR of a u32 ScVal is its discriminant then the u32.
+        run(&mut repl, "type Uint64").unwrap();
+        assert_eq!(value(&repl).name(), "Uint64");
+        assert_eq!(repl.json().unwrap(), serde_json::json!(12_884_901_889_u64));
+        assert_eq!(repl.path, []);
+        // Types that cannot be read from the XDR leave the value unchanged.
+        assert!(run(&mut repl, "type Uint32").is_err());
+        assert!(run(&mut repl, "type").is_err());
+        assert_eq!(value(&repl).name(), "Uint64");
+    }
+
+    #[test]
+    fn cd() {
+        let mut repl = repl(vec![vec([1, 2])]);
+        run(&mut repl, "cd vec[1].u32").unwrap();
+        assert_eq!(run(&mut repl, "pwd").unwrap(), ".vec[1].u32\n");
+        run(&mut repl, "cd ..").unwrap();
+        assert_eq!(run(&mut repl, "pwd").unwrap(), ".vec[1]\n");
+        run(&mut repl, "cd ..").unwrap();
+        run(&mut repl, "cd ..").unwrap();
+        run(&mut repl, "cd ..").unwrap();
+        assert_eq!(run(&mut repl, "pwd").unwrap(), ".\n");
+        // Paths that do not select one value leave the location unchanged.
+        run(&mut repl, "cd vec").unwrap();
+        assert!(run(&mut repl, "cd [2]").is_err());
+        assert!(run(&mut repl, "cd [*]").is_err());
+        assert_eq!(run(&mut repl, "pwd").unwrap(), ".vec\n");
+        run(&mut repl, "cd /").unwrap();
+        assert_eq!(run(&mut repl, "pwd").unwrap(), ".\n");
+    }
+
+    #[test]
+    fn xdr() {
+        let l = crate::Limits::none();
+        let mut repl = repl(vec![vec([1, 2])]);
+        let root = vec([1, 2]).to_xdr_base64(l.clone()).unwrap();
+        assert_eq!(run(&mut repl, "xdr").unwrap(), format!("{root}\n"));
+        // Below the root the type of the value is required.
+        run(&mut repl, "cd vec[1]").unwrap();
+        assert!(matches!(
+            run(&mut repl, "xdr"),
+            Err(Error::TypeRequired(p)) if p == ".vec[1]"
+        ));
+        let below = ScVal::U32(2).to_xdr_base64(l).unwrap();
+        assert_eq!(run(&mut repl, "xdr ScVal").unwrap(), format!("{below}\n"));
+        assert!(run(&mut repl, "xdr Uint32").is_err());
+    }
+
+    #[test]
+    fn at_mut_selects_one_value() {
+        let mut v = serde_json::json!({"a": [1, {"b": 2}]});
+        let path = query::parse_path("a[1].b").unwrap();
+        *at_mut(&mut v, &path).unwrap() = serde_json::json!(3);
+        assert_eq!(v, serde_json::json!({"a": [1, {"b": 3}]}));
+        let mut root = v.clone();
+        assert_eq!(at_mut(&mut v, &[]), Some(&mut root));
+        for path in ["a[2]", "a.b", "a[0].b", "*", "a[*]", "c"] {
+            let path = query::parse_path(path).unwrap();
+            assert_eq!(at_mut(&mut v, &path), None);
+        }
+    }
+
+    #[test]
+    fn path_strings() {
+        let mut repl = repl(vec![vec([1])]);
+        assert_eq!(run(&mut repl, "pwd").unwrap(), ".\n");
+        run(&mut repl, "cd vec[0]").unwrap();
+        assert_eq!(path_string(&repl.path), ".vec[0]");
+        assert_eq!(path_string(&repl.resolve("u32").unwrap()), ".vec[0].u32");
+        assert_eq!(path_string(&repl.resolve("/").unwrap()), ".");
+    }
+}
@@ -0,0 +1,446 @@
+use std::ffi::OsString;
+use std::io::{stdout, Write};
+use std::path::PathBuf;
+use std::{fmt::Debug, str::FromStr};
+
+use clap::{ArgGroup, Args, ValueEnum};
+use ed25519_dalek::{Signer, SigningKey};
+use sha2::{Digest, Sha256};
+
+use crate::cli::util;
+use crate::{
+    AccountId, DecoratedSignature, PublicKey, ScAddress, ScBytes, ScMap, ScSymbol, ScVal,
+    Signature, SignatureHint, SorobanAddressCredentials, SorobanAddressCredentialsWithDelegates,
+    SorobanAuthorizationEntry, SorobanCredentials, TransactionEnvelope, Uint256,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("unknown type {0}, choose one of {1:?}")]
+    UnknownType(String, &'static [&'static str]),
+    #[error("type {0} cannot be signed, choose one of {TYPES:?}")]
+    UnsupportedType(String),
+    #[error("error decoding XDR: {0}")]
+    ReadXdr(#[from] crate::Error),
+    #[error("error reading file: {0}")]
+    ReadFile(std::io::Error),
+    #[error("error writing output: {0}")]
+    WriteOutput(std::io::Error),
+    #[error("error generating JSON: {0}")]
+    GenerateJson(#[from] serde_json::Error),
+    #[error("error reading secret key: {0}")]
+    ReadSecretKey(String),
+    #[error("invalid secret key, expected an ed25519 secret key strkey")]
+    InvalidSecretKey,
+    #[error("auth entry credentials are not for the signer {0}")]
+    NotSigner(String),
+    #[error("no auth entries in the transaction have credentials for the signer {0}")]
+    NoAuthEntriesForSigner(String),
+    #[error("auth entry signature is not a list of account signatures")]
+    UnsupportedAuthSignature,
+    #[error("transaction has the maximum number of signatures")]
+    TooManySignatures,
+}
+
+/// Sign transaction envelopes or Soroban auth entries
+///
+/// Outputs the signed values in the input format.
+///
+/// Transaction envelopes are signed by adding a signature for the transaction
+/// to the envelope's signatures, or with `--auth` by signing the envelope's
+/// auth entries that have credentials for the signer's account. Auth entries
+/// are signed by adding the signer's signature to the account signatures of
+/// the entry's credentials. The signature expiration ledger of auth entries
+/// must already be set.
+///
+/// Signing the auth entries of a transaction changes the transaction, so sign
+/// the auth entries before signing the transaction.
+#[derive(Args, Debug, Clone)]
+#[command(group(
+    ArgGroup::new("secret_key")
+        .required(true)
+        .args(["secret_key_file", "secret_key_env"]),
+))]
+pub struct Cmd {
+    /// XDR or files containing XDR to sign, or stdin if empty
+    #[arg()]
+    pub input: Vec<OsString>,
+
+    /// XDR type to sign
+    #[arg(long, default_value = "TransactionEnvelope")]
+    pub r#type: String,
+
+    /// Network passphrase of the network to sign for
+    #[arg(long)]
+    pub network_passphrase: String,
+
+    /// File containing the secret key strkey (S...) to sign with
+    #[arg(long)]
+    pub secret_key_file: Option<PathBuf>,
+
+    /// Environment variable containing the secret key strkey (S...) to sign
+    /// with
+    #[arg(long)]
+    pub secret_key_env: Option<String>,
+
+    /// Sign the auth entries in the transaction envelope that have
+    /// credentials for the signer's account, instead of the transaction
+    #[arg(long)]
+    pub auth: bool,
+
+    // Input and output format
+    #[arg(long = "input", value_enum, default_value_t)]
+    pub input_format: InputFormat,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum InputFormat {
+    Single,
+    SingleBase64,
+    Json,
+}
+
+impl Default for InputFormat {
+    fn default() -> Self {
+        Self::SingleBase64
+    }
+}
+
+const TYPES: &[&str] = &["TransactionEnvelope", "SorobanAuthorizationEntry"];
+
+impl Cmd {
+    /// Run the CLIs sign command.
+    ///
+    /// ## Errors
+    ///
+    /// If the command is configured with state that is invalid.
+    pub fn run(&self) -> Result<(), Error> {
+        let result = self.run_inner();
+        match result {
+            Ok(()) => Ok(()),
+            Err(Error::WriteOutput(e)) if e.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn run_inner(&self) -> Result<(), Error> {
+        use crate::WriteXdr;
+        let r#type = crate::TypeVariant::from_str(&self.r#type).map_err(|_| {
+            Error::UnknownType(self.r#type.clone(), &crate::TypeVariant::VARIANTS_STR)
+        })?;
+        if !TYPES.contains(&r#type.name()) {
+            return Err(Error::UnsupportedType(self.r#type.clone()));
+        }
+        let key = self.signing_key()?;
+        let signer = signer_strkey(&key);
+        let network_id = network_id(&self.network_passphrase);
+        let mut input = util::parse_input(&self.input).map_err(Error::ReadFile)?;
+        for f in &mut input {
+            let mut t = read(r#type, self.input_format, f)?;
+            match &mut t {
+                crate::Type::TransactionEnvelope(e) if self.auth => {
+                    let mut any_signed = false;
+                    for entry in e.auths_mut() {
+                        any_signed |= sign_auth(entry, &key, network_id)?;
+                    }
+                    if !any_signed {
+                        return Err(Error::NoAuthEntriesForSigner(signer));
+                    }
+                }
+                crate::Type::TransactionEnvelope(e) => sign_tx(e, &key, network_id)?,
+                crate::Type::SorobanAuthorizationEntry(e) => {
+                    if !sign_auth(e, &key, network_id)? {
+                        return Err(Error::NotSigner(signer));
+                    }
+                }
+                _ => return Err(Error::UnsupportedType(self.r#type.clone())),
+            }
+            let l = crate::Limits::none();
+            let mut out = stdout();
+            match self.input_format {
+                InputFormat::Single => out.write_all(&t.to_xdr(l)?),
+                InputFormat::SingleBase64 => writeln!(out, "{}", t.to_xdr_base64(l)?),
+                InputFormat::Json => writeln!(out, "{}", serde_json::to_string(&t)?),
+            }
+            .map_err(Error::WriteOutput)?;
+        }
+        Ok(())
+    }
+
+    fn signing_key(&self) -> Result<SigningKey, Error> {
+        let secret = if let Some(path) = &self.secret_key_file {
+            std::fs::read_to_string(path)
+                .map_err(|e| Error::ReadSecretKey(format!("{}: {e}", path.display())))?
+        } else if let Some(name) = &self.secret_key_env {
+            std::env::var(name).map_err(|e| Error::ReadSecretKey(format!("{name}: {e}")))?
+        } else {
+            return Err(Error::ReadSecretKey("no secret key given".to_string()));
+        };
+        let secret = stellar_strkey::ed25519::PrivateKey::from_string(secret.trim())
+            .map_err(|_| Error::InvalidSecretKey)?;
+        Ok(SigningKey::from_bytes(&secret.0))
+    }
+}
+
+/// Returns the network id of the network with the passphrase.
+pub(crate) fn network_id(passphrase: &str) -> [u8; 32] {
+    Sha256::digest(passphrase).into()
+}
+
+pub(crate) fn read(
+    r#type: crate::TypeVariant,
+    input_format: InputFormat,
+    f: &mut impl std::io::Read,
+) -> Result<crate::Type, crate::Error> {
+    match input_format {
+        InputFormat::Single => {
+            let mut l = crate::Limited::new(f, crate::Limits::none());
+            crate::Type::read_xdr_to_end(r#type, &mut l)
+        }
+        InputFormat::SingleBase64 => {
+            let mut l = crate::Limited::new(f, crate::Limits::none());
+            crate::Type::read_xdr_base64_to_end(r#type, &mut l)
+        }
+        InputFormat::Json => crate::Type::from_json(r#type, f),
+    }
+}
+
+fn signer_strkey(key: &SigningKey) -> String {
+    stellar_strkey::ed25519::PublicKey(key.verifying_key().to_bytes()).to_string()
+}
+
+/// Adds the key's signature of the transaction to the envelope's signatures.
+pub(crate) fn sign_tx(
+    e: &mut TransactionEnvelope,
+    key: &SigningKey,
+    network_id: [u8; 32],
+) -> Result<(), Error> {
+    let hash = e.hash(network_id)?;
+    let public_key = key.verifying_key().to_bytes();
+    let signature = DecoratedSignature {
+        hint: signature_hint(&public_key),
+        signature: Signature(key.sign(&hash).to_bytes().try_into()?),
+    };
+    let signatures = match e {
+        TransactionEnvelope::TxV0(e) => &mut e.signatures,
+        TransactionEnvelope::Tx(e) => &mut e.signatures,
+        TransactionEnvelope::TxFeeBump(e) => &mut e.signatures,
+    };
+    // Signatures are deterministic, so signing again has no effect.
+    if !signatures.contains(&signature) {
+        let mut v = signatures.to_vec();
+        v.push(signature);
+        *signatures = v.try_into().map_err(|_| Error::TooManySignatures)?;
+    }
+    Ok(())
+}
+
+/// Returns the hint of signatures by the public key, that is the last four
+/// bytes of the key.
+pub(crate) fn signature_hint(public_key: &[u8; 32]) -> SignatureHint {
+    let mut hint = [0u8; 4];
+    hint.copy_from_slice(&public_key[28..]);
+    SignatureHint(hint)
+}
+
+/// Signs the auth entry if its credentials are for the signer's account.
+/// Returns whether the entry was signed.
+pub(crate) fn sign_auth(
+    entry: &mut SorobanAuthorizationEntry,
+    key: &SigningKey,
+    network_id: [u8; 32],
+) -> Result<bool, Error> {
+    let public_key = key.verifying_key().to_bytes();
+    let Some(payload) = entry.signature_payload(network_id)? else {
+        return Ok(false);
+    };
+    let Some(credentials) = address_credentials_mut(&mut entry.credentials) else {
+        return Ok(false);
+    };
+    if credentials.address != account_address(public_key) {
+        return Ok(false);
+    }
+    let mut signatures =
+        account_signatures(&credentials.signature).ok_or(Error::UnsupportedAuthSignature)?;
+    signatures.retain(|(k, _)| *k != public_key);
+    signatures.push((public_key, key.sign(&payload).to_bytes().to_vec()));
+    signatures.sort();
+    credentials.signature = account_signatures_val(&signatures)?;
+    Ok(true)
+}
+
+pub(crate) fn account_address(public_key: [u8; 32]) -> ScAddress {
+    ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(
+        public_key,
+    ))))
+}
+
+pub(crate) fn address_credentials(
+    credentials: &SorobanCredentials,
+) -> Option<&SorobanAddressCredentials> {
+    match credentials {
+        SorobanCredentials::SourceAccount => None,
+        SorobanCredentials::Address(c)
+        | SorobanCredentials::AddressV2(c)
+        | SorobanCredentials::AddressWithDelegates(SorobanAddressCredentialsWithDelegates {
+            address_credentials: c,
+            ..
+        }) => Some(c),
+    }
+}
+
+fn address_credentials_mut(
+    credentials: &mut SorobanCredentials,
+) -> Option<&mut SorobanAddressCredentials> {
+    match credentials {
+        SorobanCredentials::SourceAccount => None,
+        SorobanCredentials::Address(c)
+        | SorobanCredentials::AddressV2(c)
+        | SorobanCredentials::AddressWithDelegates(SorobanAddressCredentialsWithDelegates {
+            address_credentials: c,
+            ..
+        }) => Some(c),
+    }
+}
+
+/// Returns the public keys and signatures in the signature of an account's
+/// address credentials, that is a vec of maps with a `public_key` and
+/// `signature`. Returns an empty list for a void signature, and `None` if the
+/// signature is not in that form.
+pub(crate) fn account_signatures(signature: &ScVal) -> Option<Vec<([u8; 32], Vec<u8>)>> {
+    let items = match signature {
+        ScVal::Void => return Some(Vec::new()),
+        ScVal::Vec(Some(items)) => items,
+        _ => return None,
+    };
+    items
+        .iter()
+        .map(|item| {
+            let ScVal::Map(Some(map)) = item else {
+                return None;
+            };
+            let field = |name: &str| match map.get_by_key(&ScVal::Symbol(name.try_into().ok()?)) {
+                Some(ScVal::Bytes(ScBytes(b))) => Some(b.to_vec()),
+                _ => None,
+            };
+            Some((field("public_key")?.try_into().ok()?, field("signature")?))
+        })
+        .collect()
+}
+
+fn account_signatures_val(signatures: &[([u8; 32], Vec<u8>)]) -> Result<ScVal, crate::Error> {
+    let items = signatures
+        .iter()
+        .map(|(public_key, signature)| {
+            let map = ScMap::sorted_from([
+                (
+                    ScVal::Symbol(ScSymbol("public_key".try_into()?)),
+                    ScVal::Bytes(ScBytes(public_key.try_into()?)),
+                ),
+                (
+                    ScVal::Symbol(ScSymbol("signature".try_into()?)),
+                    ScVal::Bytes(ScBytes(signature.try_into()?)),
+                ),
+            ])?;
+            Ok(ScVal::Map(Some(map)))
+        })
+        .collect::<Result<Vec<_>, crate::Error>>()?;
+    Ok(ScVal::Vec(Some(items.try_into()?)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn auth_entry(
+        credentials: fn(SorobanAddressCredentials) -> SorobanCredentials,
+        key: &SigningKey,
+    ) -> SorobanAuthorizationEntry {
+        SorobanAuthorizationEntry {
+            credentials: credentials(SorobanAddressCredentials {
+                address: account_address(key.verifying_key().to_bytes()),
+                nonce: 1,
+                signature_expiration_ledger: 100,
+                signature: ScVal::Void,
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn tx_signatures(e: &TransactionEnvelope) -> &[DecoratedSignature] {
+        let TransactionEnvelope::Tx(e) = e else {
+            panic!("expected a v1 transaction envelope");
+        };
+        &e.signatures
+    }
+
+    #[test]
+    fn sign_tx_is_deduplicated() {
+        let network_id = network_id("Test");
+        let (k1, k2) = (
+            SigningKey::from_bytes(&[1; 32]),
+            SigningKey::from_bytes(&[2; 32]),
+        );
+        let mut e = TransactionEnvelope::default();
+        sign_tx(&mut e, &k1, network_id).unwrap();
+        let signed = e.clone();
+        sign_tx(&mut e, &k1, network_id).unwrap();
+        assert_eq!(e, signed);
+        sign_tx(&mut e, &k2, network_id).unwrap();
+        assert_eq!(tx_signatures(&e).len(), 2);
+    }
+
+    #[test]
+    fn sign_tx_hint() {
+        let key = SigningKey::from_bytes(&[1; 32]);
+        let public_key = key.verifying_key().to_bytes();
+        let mut e = TransactionEnvelope::default();
+        sign_tx(&mut e, &key, network_id("Test")).unwrap();
+        let [signature] = tx_signatures(&e) else {
+            panic!("expected one signature");
+        };
+        assert_eq!(signature.hint.0, public_key[28..]);
+        assert_eq!(signature.hint, signature_hint(&public_key));
+    }
+
+    #[test]
+    fn sign_auth_sorts_account_signatures() {
+        let network_id = network_id("Test");
+        let keys = [[3; 32], [1; 32], [2; 32]].map(|k| SigningKey::from_bytes(&k));
+        let mut entry = auth_entry(SorobanCredentials::Address, &keys[0]);
+        // Signatures of other keys, as signed by other signers of an account
+        // with multiple signers, are kept.
+        for key in &keys {
+            let credentials = address_credentials_mut(&mut entry.credentials).unwrap();
+            credentials.address = account_address(key.verifying_key().to_bytes());
+            assert!(sign_auth(&mut entry, key, network_id).unwrap());
+        }
+        let signed = entry.clone();
+        assert!(sign_auth(&mut entry, &keys[2], network_id).unwrap());
+        assert_eq!(entry, signed);
+        let signatures =
+            account_signatures(&address_credentials(&entry.credentials).unwrap().signature)
+                .unwrap();
+        let public_keys = signatures.iter().map(|(k, _)| *k).collect::<Vec<_>>();
+        let mut sorted = keys.map(|k| k.verifying_key().to_bytes()).to_vec();
+        sorted.sort_unstable();
+        assert_eq!(public_keys, sorted);
+    }
+
+    #[test]
+    fn sign_auth_of_other_signer() {
+        let network_id = network_id("Test");
+        let (k1, k2) = (
+            SigningKey::from_bytes(&[1; 32]),
+            SigningKey::from_bytes(&[2; 32]),
+        );
+        let mut entry = auth_entry(SorobanCredentials::Address, &k1);
+        assert!(!sign_auth(&mut entry, &k2, network_id).unwrap());
+        assert_eq!(entry, auth_entry(SorobanCredentials::Address, &k1));
+        let mut entry = SorobanAuthorizationEntry {
+            credentials: SorobanCredentials::SourceAccount,
+            ..Default::default()
+        };
+        assert!(!sign_auth(&mut entry, &k1, network_id).unwrap());
+    }
+}
@@ -1,6 +1,6 @@
 use std::ffi::OsString;
 use std::fs::File;
-use std::io::{stdin, Cursor, Error as IoError, Read};
+use std::io::{stdin, Cursor, Error as IoError, ErrorKind, Read, Write};
 use std::path::Path;
 
 pub fn parse_input(input: &[OsString]) -> Result<Vec<Box<dyn Read>>, IoError> {
@@ -32,3 +32,15 @@ pub fn serde_json_value_to_text(v: serde_json::Value) -> Option<String> {
     };
     Some(s)
 }
+
+/// Writes the XDR as a frame of a framed stream, preceded by the record mark
+/// that `read_xdr_framed_iter` reads, that is the length with the high bit
+/// set to mark the record as having a single fragment.
+pub fn write_framed(out: &mut impl Write, xdr: &[u8]) -> Result<(), IoError> {
+    let len = u32::try_from(xdr.len())
+        .ok()
+        .filter(|len| *len < 0x8000_0000)
+        .ok_or_else(|| IoError::new(ErrorKind::InvalidInput, "value is too large to frame"))?;
+    out.write_all(&(len | 0x8000_0000).to_be_bytes())?;
+    out.write_all(xdr)
+}
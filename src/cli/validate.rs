@@ -0,0 +1,533 @@
+use std::cmp::Ordering;
+use std::ffi::OsString;
+use std::fmt::Display;
+use std::io::{stdout, Read, Write};
+use std::{fmt::Debug, str::FromStr};
+
+use base64::Engine;
+use clap::{Args, ValueEnum};
+use serde_json::Value;
+
+use crate::cli::explain::{self, Node};
+use crate::cli::util;
+use crate::{
+    ClaimableBalanceId, ContractId, LedgerFootprint, LedgerKey, MuxedAccount, MuxedEd25519Account,
+    OperationBody, PoolId, Preconditions, PublicKey, ReadXdr, ScAddress, ScMap, ScVal, SignerKey,
+    TimeBounds, Transaction, TransactionExt, TransactionV0, Validate, WriteXdr,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("unknown type {0}, choose one of {1:?}")]
+    UnknownType(String, &'static [&'static str]),
+    #[error("type {0} has no XDR definition")]
+    UnknownDefinition(String),
+    #[error(transparent)]
+    Explain(#[from] explain::Error),
+    #[error("error decoding XDR: {0}")]
+    ReadXdr(#[from] crate::Error),
+    #[error("error decoding JSON: {0}")]
+    ReadJson(serde_json::Error),
+    #[error("error decoding base64: {0}")]
+    ReadBase64(base64::DecodeError),
+    #[error("error reading file: {0}")]
+    ReadFile(std::io::Error),
+    #[error("error generating JSON: {0}")]
+    GenerateJson(serde_json::Error),
+    #[error("error writing output: {0}")]
+    WriteOutput(std::io::Error),
+    #[error("{0} validation issues found")]
+    Invalid(usize),
+}
+
+/// Validate XDR values beyond decoding
+///
+/// Checks that the values are valid in ways that decoding does not check,
+/// and outputs a line for each issue found, with the path of the value the
+/// issue is with. Paths are of the fields as named in the XDR definitions,
+/// as output by explain.
+///
+/// Checks that:
+///
+/// - the input is canonically encoded, i.e. re-encoding the value results in
+///   the same XDR, or for JSON input the same JSON;
+///
+/// - `ScVal`s and `ScMap`s are valid, with maps sorted by key with no
+///   duplicate keys, and symbols containing only valid characters;
+///
+/// - transactions have operations, Soroban transactions have exactly one
+///   Soroban operation and Soroban transaction data, and time and ledger
+///   bounds are ordered;
+///
+/// - footprints have only keys that can be in a footprint, no duplicate keys,
+///   and no keys that are both read-only and read-write;
+///
+/// - fields that are represented as strkeys can be.
+///
+/// Fails if any issue is found.
+#[derive(Args, Debug, Clone)]
+#[command()]
+pub struct Cmd {
+    /// XDR, JSON, or files containing them to validate, or stdin if empty
+    #[arg()]
+    pub input: Vec<OsString>,
+
+    /// XDR type of the values
+    #[arg(long)]
+    pub r#type: String,
+
+    // Input format
+    #[arg(long = "input", value_enum, default_value_t)]
+    pub input_format: InputFormat,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum InputFormat {
+    Single,
+    SingleBase64,
+    Json,
+}
+
+impl Default for InputFormat {
+    fn default() -> Self {
+        Self::SingleBase64
+    }
+}
+
+/// An issue found with a value.
+struct Issue {
+    path: String,
+    message: String,
+}
+
+impl Cmd {
+    /// Run the CLIs validate command.
+    ///
+    /// ## Errors
+    ///
+    /// If the command is configured with state that is invalid, the input
+    /// fails to decode, or any issue is found.
+    pub fn run(&self) -> Result<(), Error> {
+        let result = self.run_inner();
+        match result {
+            Ok(()) => Ok(()),
+            Err(Error::WriteOutput(e)) if e.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn run_inner(&self) -> Result<(), Error> {
+        let r#type = crate::TypeVariant::from_str(&self.r#type).map_err(|_| {
+            Error::UnknownType(self.r#type.clone(), &crate::TypeVariant::VARIANTS_STR)
+        })?;
+        let defs = explain::Definitions::load()?;
+        let mut issues = 0;
+        let mut input = util::parse_input(&self.input).map_err(Error::ReadFile)?;
+        for f in &mut input {
+            let mut bytes = Vec::new();
+            f.read_to_end(&mut bytes).map_err(Error::ReadFile)?;
+            for issue in self.validate(&defs, r#type, bytes)? {
+                issues += 1;
+                writeln!(stdout(), "{}: {}", issue.path, issue.message)
+                    .map_err(Error::WriteOutput)?;
+            }
+        }
+        if issues > 0 {
+            return Err(Error::Invalid(issues));
+        }
+        Ok(())
+    }
+
+    fn validate(
+        &self,
+        defs: &explain::Definitions,
+        r#type: crate::TypeVariant,
+        bytes: Vec<u8>,
+    ) -> Result<Vec<Issue>, Error> {
+        let mut issues = Vec::new();
+        let (t, xdr) = match self.input_format {
+            InputFormat::Single | InputFormat::SingleBase64 => {
+                let input = if self.input_format == InputFormat::Single {
+                    bytes
+                } else {
+                    let text = bytes
+                        .into_iter()
+                        .filter(|b| !b.is_ascii_whitespace())
+                        .collect::<Vec<_>>();
+                    base64::engine::general_purpose::STANDARD
+                        .decode(text)
+                        .map_err(Error::ReadBase64)?
+                };
+                let t = crate::Type::from_xdr(r#type, &input, crate::Limits::none())?;
+                let xdr = t.to_xdr(crate::Limits::none())?;
+                let offset = xdr.iter().zip(&input).position(|(a, b)| a != b);
+                let offset = offset
+                    .or_else(|| (xdr.len() != input.len()).then(|| xdr.len().min(input.len())));
+                if let Some(offset) = offset {
+                    issues.push(Issue {
+                        path: ".".to_string(),
+                        message: format!(
+                            "not canonically encoded, re-encodes differently from offset {offset:#x}"
+                        ),
+                    });
+                }
+                (t, xdr)
+            }
+            InputFormat::Json => {
+                let input: Value = serde_json::from_slice(&bytes).map_err(Error::ReadJson)?;
+                let t = crate::Type::from_json(r#type, &bytes[..])?;
+                if serde_json::to_value(&t).map_err(Error::GenerateJson)? != input {
+                    issues.push(Issue {
+                        path: ".".to_string(),
+                        message: "not canonically encoded, re-encodes to different JSON"
+                            .to_string(),
+                    });
+                }
+                let xdr = t.to_xdr(crate::Limits::none())?;
+                (t, xdr)
+            }
+        };
+        let name = defs
+            .name_of(t.variant())
+            .ok_or_else(|| Error::UnknownDefinition(self.r#type.clone()))?;
+        let (node, result) = explain::explain(defs, name, &xdr);
+        if let Err(explain::DecodeError { offset, reason, .. }) = result {
+            return Err(explain::Error::Decode { offset, reason }.into());
+        }
+        let mut validator = Validator { xdr: &xdr, issues };
+        validator.node(&node, "")?;
+        Ok(validator.issues)
+    }
+}
+
+struct Validator<'a> {
+    xdr: &'a [u8],
+    issues: Vec<Issue>,
+}
+
+impl Validator<'_> {
+    fn issue(&mut self, path: String, message: impl Into<String>) {
+        let path = if path.is_empty() {
+            ".".to_string()
+        } else {
+            path
+        };
+        self.issues.push(Issue {
+            path,
+            message: message.into(),
+        });
+    }
+
+    /// Validates the value and the values it contains.
+    ///
+    /// `ScVal`s are validated after the values they contain, and only reported
+    /// as invalid if none of the values they contain are, so that issues are
+    /// reported at the most specific path. `ScMap`s are checked for the order
+    /// of their keys, the only issue that is with the map itself.
+    fn node(&mut self, node: &Node, path: &str) -> Result<(), crate::Error> {
+        let Some(len) = node.len else {
+            return Ok(());
+        };
+        let bytes = &self.xdr[node.offset..node.offset + len];
+        let limits = crate::Limits::none;
+        match node.r#type.as_str() {
+            "Transaction" => self.transaction(&Transaction::from_xdr(bytes, limits())?, path),
+            "TransactionV0" => {
+                self.transaction_v0(&TransactionV0::from_xdr(bytes, limits())?, path);
+            }
+            "LedgerFootprint" => {
+                self.footprint(&LedgerFootprint::from_xdr(bytes, limits())?, path);
+            }
+            "PublicKey" | "AccountID" | "NodeID" => self.strkey::<PublicKey>(bytes, path)?,
+            "MuxedAccount" => self.strkey::<MuxedAccount>(bytes, path)?,
+            "MuxedEd25519Account" => self.strkey::<MuxedEd25519Account>(bytes, path)?,
+            "SCAddress" => self.strkey::<ScAddress>(bytes, path)?,
+            "SignerKey" => self.strkey::<SignerKey>(bytes, path)?,
+            "ClaimableBalanceID" => self.strkey::<ClaimableBalanceId>(bytes, path)?,
+            "PoolID" => self.strkey::<PoolId>(bytes, path)?,
+            "ContractID" => self.strkey::<ContractId>(bytes, path)?,
+            _ => {}
+        }
+        let issues = self.issues.len();
+        for child in &node.children {
+            match child.label.as_deref() {
+                // Lengths are only valid or not as the length of the value
+                // they are the length of.
+                Some("len") => {}
+                Some("some") | None => self.node(child, path)?,
+                Some(label) if label.starts_with('[') => {
+                    self.node(child, &format!("{path}{label}"))?;
+                }
+                Some(label) => self.node(child, &format!("{path}.{label}"))?,
+            }
+        }
+        let contained = self.issues.len() > issues;
+        match node.r#type.as_str() {
+            "SCVal" if !contained => {
+                let v = ScVal::from_xdr(bytes, limits())?;
+                if v.validate().is_err() {
+                    self.issue(path.to_string(), scval_issue(&v));
+                }
+            }
+            "SCMap" => {
+                let m = ScMap::from_xdr(bytes, limits())?;
+                if !m
+                    .windows(2)
+                    .all(|w| w[0].key.host_cmp(&w[1].key) == Ordering::Less)
+                {
+                    self.issue(path.to_string(), "map keys are not sorted or not unique");
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn transaction(&mut self, tx: &Transaction, path: &str) {
+        let ops = &tx.operations;
+        if ops.is_empty() {
+            self.issue(
+                format!("{path}.operations"),
+                "transaction has no operations",
+            );
+        }
+        let soroban = ops.iter().any(|op| is_soroban(&op.body));
+        if soroban && ops.len() > 1 {
+            self.issue(
+                format!("{path}.operations"),
+                "transaction has a Soroban operation and other operations, Soroban transactions must have exactly one operation",
+            );
+        }
+        match &tx.ext {
+            TransactionExt::V0 if soroban => self.issue(
+                format!("{path}.ext"),
+                "transaction has a Soroban operation but no Soroban transaction data",
+            ),
+            TransactionExt::V1(_) if !soroban => self.issue(
+                format!("{path}.ext.sorobanData"),
+                "transaction has Soroban transaction data but no Soroban operation",
+            ),
+            TransactionExt::V1(data) => {
+                let path = format!("{path}.ext.sorobanData.resources.footprint");
+                let footprint = &data.resources.footprint;
+                for op in ops {
+                    match op.body {
+                        OperationBody::ExtendFootprintTtl(_) => self.footprint_of_ttl_op(
+                            &path,
+                            ("readOnly", &footprint.read_only),
+                            ("readWrite", &footprint.read_write),
+                            "extend footprint TTL",
+                        ),
+                        OperationBody::RestoreFootprint(_) => self.footprint_of_ttl_op(
+                            &path,
+                            ("readWrite", &footprint.read_write),
+                            ("readOnly", &footprint.read_only),
+                            "restore footprint",
+                        ),
+                        _ => {}
+                    }
+                }
+            }
+            TransactionExt::V0 => {}
+        }
+        match &tx.cond {
+            Preconditions::None => {}
+            Preconditions::Time(tb) => self.time_bounds(tb, &format!("{path}.cond.timeBounds")),
+            Preconditions::V2(p) => {
+                if let Some(tb) = &p.time_bounds {
+                    self.time_bounds(tb, &format!("{path}.cond.v2.timeBounds"));
+                }
+                if let Some(lb) = p
+                    .ledger_bounds
+                    .as_ref()
+                    .filter(|lb| lb.max_ledger != 0 && lb.min_ledger > lb.max_ledger)
+                {
+                    self.issue(
+                        format!("{path}.cond.v2.ledgerBounds"),
+                        format!(
+                            "min ledger {} is after max ledger {}",
+                            lb.min_ledger, lb.max_ledger
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    fn transaction_v0(&mut self, tx: &TransactionV0, path: &str) {
+        if tx.operations.is_empty() {
+            self.issue(
+                format!("{path}.operations"),
+                "transaction has no operations",
+            );
+        }
+        for (i, op) in tx.operations.iter().enumerate() {
+            if is_soroban(&op.body) {
+                self.issue(
+                    format!("{path}.operations[{i}]"),
+                    "Soroban operations require Soroban transaction data, which v0 transactions cannot have",
+                );
+            }
+        }
+        if let Some(tb) = &tx.time_bounds {
+            self.time_bounds(tb, &format!("{path}.timeBounds"));
+        }
+    }
+
+    fn time_bounds(&mut self, tb: &TimeBounds, path: &str) {
+        if tb.max_time.0 != 0 && tb.min_time.0 > tb.max_time.0 {
+            self.issue(
+                path.to_string(),
+                format!(
+                    "min time {} is after max time {}",
+                    tb.min_time.0, tb.max_time.0
+                ),
+            );
+        }
+    }
+
+    /// Validates the footprint of an operation that extends or restores the
+    /// TTL of the keys in one side of the footprint, and must have no keys in
+    /// the other side.
+    fn footprint_of_ttl_op(
+        &mut self,
+        path: &str,
+        (name, keys): (&str, &[LedgerKey]),
+        (empty_name, empty): (&str, &[LedgerKey]),
+        op: &str,
+    ) {
+        if !empty.is_empty() {
+            self.issue(
+                format!("{path}.{empty_name}"),
+                format!("footprint of a {op} operation must have no {empty_name} keys"),
+            );
+        }
+        for (i, key) in keys.iter().enumerate() {
+            if !matches!(key, LedgerKey::ContractData(_) | LedgerKey::ContractCode(_)) {
+                self.issue(
+                    format!("{path}.{name}[{i}]"),
+                    format!(
+                        "footprint of a {op} operation must have only contract data and contract code keys"
+                    ),
+                );
+            }
+        }
+    }
+
+    fn footprint(&mut self, footprint: &LedgerFootprint, path: &str) {
+        let sides = [
+            ("readOnly", &footprint.read_only),
+            ("readWrite", &footprint.read_write),
+        ];
+        for (name, keys) in sides {
+            for (i, key) in keys.iter().enumerate() {
+                let path = format!("{path}.{name}[{i}]");
+                if !matches!(
+                    key,
+                    LedgerKey::Account(_)
+                        | LedgerKey::Trustline(_)
+                        | LedgerKey::ContractData(_)
+                        | LedgerKey::ContractCode(_)
+                ) {
+                    self.issue(
+                        path.clone(),
+                        "footprint keys must be account, trust line, contract data, or contract code keys",
+                    );
+                }
+                if let Some(j) = keys[..i].iter().position(|k| k == key) {
+                    self.issue(path.clone(), format!("duplicate of {name}[{j}]"));
+                }
+                if name == "readWrite" {
+                    if let Some(j) = footprint.read_only.iter().position(|k| k == key) {
+                        self.issue(path, format!("also in readOnly[{j}]"));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Validates that the value can be represented as a strkey, by checking
+    /// that its strkey parses back to the same value.
+    fn strkey<T>(&mut self, bytes: &[u8], path: &str) -> Result<(), crate::Error>
+    where
+        T: ReadXdr + Display + FromStr + PartialEq,
+    {
+        let v = T::from_xdr(bytes, crate::Limits::none())?;
+        let s = v.to_string();
+        if !T::from_str(&s).is_ok_and(|parsed| parsed == v) {
+            self.issue(
+                path.to_string(),
+                format!("cannot be represented as a strkey, {s} parses to a different value"),
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Returns a description of why the `ScVal` is invalid, given that none of
+/// the values it contains are invalid.
+fn scval_issue(v: &ScVal) -> &'static str {
+    match v {
+        ScVal::Symbol(_) => "symbol contains characters other than a-z, A-Z, 0-9, and _",
+        ScVal::Vec(None) => "vec is none, vecs must be present",
+        ScVal::Map(None) => "map is none, maps must be present",
+        _ => "invalid value",
+    }
+}
+
+fn is_soroban(body: &OperationBody) -> bool {
+    matches!(
+        body,
+        OperationBody::InvokeHostFunction(_)
+            | OperationBody::ExtendFootprintTtl(_)
+            | OperationBody::RestoreFootprint(_)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ScSymbol, ScVec};
+
+    fn issues(r#type: crate::TypeVariant, input_format: InputFormat, input: &str) -> Vec<String> {
+        let cmd = Cmd {
+            input: Vec::new(),
+            r#type: r#type.name().to_string(),
+            input_format,
+        };
+        let defs = explain::Definitions::load().unwrap();
+        cmd.validate(&defs, r#type, input.as_bytes().to_vec())
+            .unwrap()
+            .into_iter()
+            .map(|i| format!("{}: {}", i.path, i.message))
+            .collect()
+    }
+
+    #[test]
+    fn validate_valid_value() {
+        let v = ScVal::Vec(Some(ScVec(
+            [ScVal::Symbol(ScSymbol("ok".try_into().unwrap()))]
+                .to_vec()
+                .try_into()
+                .unwrap(),
+        )));
+        let b64 = v.to_xdr_base64(crate::Limits::none()).unwrap();
+        assert_eq!(
+            issues(crate::TypeVariant::ScVal, InputFormat::SingleBase64, &b64),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn validate_reports_issues_with_paths() {
+        let json = r#"{"vec":[{"symbol":"a-b"},{"map":[{"key":{"u32":2},"val":"void"},{"key":{"u32":1},"val":"void"}]},{"u64":5}]}"#;
+        assert_eq!(
+            issues(crate::TypeVariant::ScVal, InputFormat::Json, json),
+            [
+                ".: not canonically encoded, re-encodes to different JSON",
+                ".vec[0]: symbol contains characters other than a-z, A-Z, 0-9, and _",
+                ".vec[1].map: map keys are not sorted or not unique",
+            ]
+        );
+    }
+}
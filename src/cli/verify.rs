@@ -0,0 +1,288 @@
+use std::ffi::OsString;
+use std::io::{stdout, Write};
+use std::{fmt::Debug, str::FromStr};
+
+use clap::Args;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+use crate::cli::sign::{self, InputFormat};
+use crate::cli::util;
+use crate::{SorobanAuthorizationEntry, TransactionEnvelope};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("unknown type {0}, choose one of {1:?}")]
+    UnknownType(String, &'static [&'static str]),
+    #[error("type {0} cannot be verified, choose one of {TYPES:?}")]
+    UnsupportedType(String),
+    #[error("error decoding XDR: {0}")]
+    ReadXdr(#[from] crate::Error),
+    #[error("error reading file: {0}")]
+    ReadFile(std::io::Error),
+    #[error("error writing output: {0}")]
+    WriteOutput(std::io::Error),
+    #[error("invalid public key {0}, expected an ed25519 public key strkey")]
+    InvalidPublicKey(String),
+    #[error("{0} signatures not verified by any of the public keys")]
+    Unverified(usize),
+}
+
+/// Verify the signatures of transaction envelopes or Soroban auth entries
+///
+/// Outputs a line for each signature, with the public key that the signature
+/// is verified by, or `unverified` if it is not verified by any of the public
+/// keys. For transaction envelopes, outputs the signatures of the transaction
+/// followed by the signatures of each auth entry with address credentials.
+///
+/// Fails if any signature is not verified.
+#[derive(Args, Debug, Clone)]
+#[command()]
+pub struct Cmd {
+    /// XDR or files containing XDR to verify, or stdin if empty
+    #[arg()]
+    pub input: Vec<OsString>,
+
+    /// XDR type to verify
+    #[arg(long, default_value = "TransactionEnvelope")]
+    pub r#type: String,
+
+    /// Network passphrase of the network the signatures are for
+    #[arg(long)]
+    pub network_passphrase: String,
+
+    /// Public key strkey (G...) to verify signatures with, may be given
+    /// multiple times
+    #[arg(long = "public-key", required = true)]
+    pub public_keys: Vec<String>,
+
+    // Input format
+    #[arg(long = "input", value_enum, default_value_t)]
+    pub input_format: InputFormat,
+}
+
+const TYPES: &[&str] = &["TransactionEnvelope", "SorobanAuthorizationEntry"];
+
+impl Cmd {
+    /// Run the CLIs verify command.
+    ///
+    /// ## Errors
+    ///
+    /// If the command is configured with state that is invalid, or any
+    /// signature is not verified.
+    pub fn run(&self) -> Result<(), Error> {
+        let result = self.run_inner();
+        match result {
+            Ok(()) => Ok(()),
+            Err(Error::WriteOutput(e)) if e.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn run_inner(&self) -> Result<(), Error> {
+        let r#type = crate::TypeVariant::from_str(&self.r#type).map_err(|_| {
+            Error::UnknownType(self.r#type.clone(), &crate::TypeVariant::VARIANTS_STR)
+        })?;
+        if !TYPES.contains(&r#type.name()) {
+            return Err(Error::UnsupportedType(self.r#type.clone()));
+        }
+        let keys = self
+            .public_keys
+            .iter()
+            .map(|k| {
+                let invalid = || Error::InvalidPublicKey(k.clone());
+                let k =
+                    stellar_strkey::ed25519::PublicKey::from_string(k).map_err(|_| invalid())?;
+                VerifyingKey::from_bytes(&k.0).map_err(|_| invalid())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let network_id = sign::network_id(&self.network_passphrase);
+        let mut results = Vec::new();
+        let mut input = util::parse_input(&self.input).map_err(Error::ReadFile)?;
+        for f in &mut input {
+            match sign::read(r#type, self.input_format, f)? {
+                crate::Type::TransactionEnvelope(e) => {
+                    results.extend(verify_tx(&e, &keys, network_id)?);
+                    for (i, entry) in e.auths().enumerate() {
+                        for (j, verified_by) in verify_auth(entry, &keys, network_id)? {
+                            results.push((format!("auth {i} signature {j}"), verified_by));
+                        }
+                    }
+                }
+                crate::Type::SorobanAuthorizationEntry(entry) => {
+                    for (j, verified_by) in verify_auth(&entry, &keys, network_id)? {
+                        results.push((format!("signature {j}"), verified_by));
+                    }
+                }
+                _ => return Err(Error::UnsupportedType(self.r#type.clone())),
+            }
+        }
+        let mut out = stdout();
+        let mut unverified = 0;
+        for (name, verified_by) in results {
+            let verified_by = verified_by.map_or_else(
+                || {
+                    unverified += 1;
+                    "unverified".to_string()
+                },
+                |k| stellar_strkey::ed25519::PublicKey(k.to_bytes()).to_string(),
+            );
+            writeln!(out, "{name}: {verified_by}").map_err(Error::WriteOutput)?;
+        }
+        if unverified > 0 {
+            return Err(Error::Unverified(unverified));
+        }
+        Ok(())
+    }
+}
+
+/// Returns for each signature of the transaction the key that verifies it.
+fn verify_tx(
+    e: &TransactionEnvelope,
+    keys: &[VerifyingKey],
+    network_id: [u8; 32],
+) -> Result<Vec<(String, Option<VerifyingKey>)>, Error> {
+    let hash = e.hash(network_id)?;
+    let signatures = match e {
+        TransactionEnvelope::TxV0(e) => &e.signatures,
+        TransactionEnvelope::Tx(e) => &e.signatures,
+        TransactionEnvelope::TxFeeBump(e) => &e.signatures,
+    };
+    Ok(signatures
+        .iter()
+        .enumerate()
+        .map(|(i, s)| {
+            let verified_by = Signature::from_slice(&s.signature.0).ok().and_then(|sig| {
+                keys.iter()
+                    .find(|k| {
+                        sign::signature_hint(k.as_bytes()) == s.hint
+                            && k.verify(&hash, &sig).is_ok()
+                    })
+                    .copied()
+            });
+            (format!("signature {i}"), verified_by)
+        })
+        .collect())
+}
+
+/// Returns for each account signature of the auth entry the key that
+/// verifies it. Entries with source account credentials have no signatures.
+/// Signatures that are not account signatures are a single unverified
+/// signature.
+fn verify_auth(
+    entry: &SorobanAuthorizationEntry,
+    keys: &[VerifyingKey],
+    network_id: [u8; 32],
+) -> Result<Vec<(usize, Option<VerifyingKey>)>, Error> {
+    let (Some(payload), Some(credentials)) = (
+        entry.signature_payload(network_id)?,
+        sign::address_credentials(&entry.credentials),
+    ) else {
+        return Ok(Vec::new());
+    };
+    let Some(signatures) = sign::account_signatures(&credentials.signature) else {
+        return Ok(vec![(0, None)]);
+    };
+    Ok(signatures
+        .iter()
+        .enumerate()
+        .map(|(i, (public_key, signature))| {
+            let verified_by = Signature::from_slice(signature).ok().and_then(|sig| {
+                keys.iter()
+                    .find(|k| k.as_bytes() == public_key && k.verify(&payload, &sig).is_ok())
+                    .copied()
+            });
+            (i, verified_by)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ScVal, SorobanAddressCredentials, SorobanAddressCredentialsWithDelegates,
+        SorobanCredentials,
+    };
+    use ed25519_dalek::SigningKey;
+
+    fn auth_entry(
+        credentials: impl Fn(SorobanAddressCredentials) -> SorobanCredentials,
+        key: &SigningKey,
+    ) -> SorobanAuthorizationEntry {
+        SorobanAuthorizationEntry {
+            credentials: credentials(SorobanAddressCredentials {
+                address: sign::account_address(key.verifying_key().to_bytes()),
+                nonce: 1,
+                signature_expiration_ledger: 100,
+                signature: ScVal::Void,
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn verify_signed_tx() {
+        let network_id = sign::network_id("Test");
+        let (k1, k2) = (
+            SigningKey::from_bytes(&[1; 32]),
+            SigningKey::from_bytes(&[2; 32]),
+        );
+        let keys = [k1.verifying_key(), k2.verifying_key()];
+        let mut e = TransactionEnvelope::default();
+        sign::sign_tx(&mut e, &k2, network_id).unwrap();
+        sign::sign_tx(&mut e, &k1, network_id).unwrap();
+        let verified_by = verify_tx(&e, &keys, network_id)
+            .unwrap()
+            .into_iter()
+            .map(|(_, k)| k)
+            .collect::<Vec<_>>();
+        assert_eq!(verified_by, [Some(keys[1]), Some(keys[0])]);
+        // Signatures for another network are not verified.
+        let other_network_id = sign::network_id("Other");
+        let verified_by = verify_tx(&e, &keys, other_network_id).unwrap();
+        assert!(verified_by.iter().all(|(_, k)| k.is_none()));
+    }
+
+    #[test]
+    fn verify_signed_auth() {
+        let network_id = sign::network_id("Test");
+        let key = SigningKey::from_bytes(&[1; 32]);
+        let keys = [key.verifying_key()];
+        let credentials: [fn(SorobanAddressCredentials) -> SorobanCredentials; 3] = [
+            SorobanCredentials::Address,
+            SorobanCredentials::AddressV2,
+            |c| {
+                SorobanCredentials::AddressWithDelegates(SorobanAddressCredentialsWithDelegates {
+                    address_credentials: c,
+                    delegates: [].try_into().unwrap(),
+                })
+            },
+        ];
+        for credentials in credentials {
+            let mut entry = auth_entry(credentials, &key);
+            assert_eq!(verify_auth(&entry, &keys, network_id).unwrap(), []);
+            assert!(sign::sign_auth(&mut entry, &key, network_id).unwrap());
+            assert_eq!(
+                verify_auth(&entry, &keys, network_id).unwrap(),
+                [(0, Some(keys[0]))]
+            );
+        }
+    }
+
+    #[test]
+    fn verify_with_wrong_key_is_unverified() {
+        let network_id = sign::network_id("Test");
+        let (k1, k2) = (
+            SigningKey::from_bytes(&[1; 32]),
+            SigningKey::from_bytes(&[2; 32]),
+        );
+        let keys = [k2.verifying_key()];
+        let mut e = TransactionEnvelope::default();
+        sign::sign_tx(&mut e, &k1, network_id).unwrap();
+        let verified_by = verify_tx(&e, &keys, network_id).unwrap();
+        assert_eq!(verified_by, [("signature 0".to_string(), None)]);
+        let mut entry = auth_entry(SorobanCredentials::Address, &k1);
+        assert!(sign::sign_auth(&mut entry, &k1, network_id).unwrap());
+        assert_eq!(verify_auth(&entry, &keys, network_id).unwrap(), [(0, None)]);
+    }
+}
@@ -0,0 +1,93 @@
+use super::{
+    ContractDataDurability, ContractDataEntry, LedgerKeyContractData, ScAddress,
+    ScContractInstance, ScMap, ScNonceKey, ScVal, ScVec,
+};
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+use alloc::vec;
+
+impl LedgerKeyContractData {
+    /// Returns the key of the contract's instance entry, that holds the
+    /// contract's executable and instance storage.
+    #[must_use]
+    pub fn contract_instance(contract: ScAddress) -> Self {
+        Self {
+            contract,
+            key: ScVal::LedgerKeyContractInstance,
+            durability: ContractDataDurability::Persistent,
+        }
+    }
+
+    /// Returns the key of the entry that records the nonce has been consumed
+    /// by an authorization of the address.
+    #[must_use]
+    pub fn nonce(address: ScAddress, nonce: i64) -> Self {
+        Self {
+            contract: address,
+            key: ScVal::LedgerKeyNonce(ScNonceKey { nonce }),
+            durability: ContractDataDurability::Temporary,
+        }
+    }
+
+    /// Returns the key of the entry that holds the balance of an address in
+    /// a SEP-41 token contract that stores balances in the layout of the
+    /// Stellar Asset Contract and the token examples, that is keyed by
+    /// `Vec[Symbol("Balance"), Address]` in persistent storage.
+    #[must_use]
+    // The symbol and vec are within their maximum lengths, so converting
+    // them does not panic.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn token_balance(contract: ScAddress, holder: ScAddress) -> Self {
+        let key = vec![
+            ScVal::Symbol("Balance".try_into().unwrap()),
+            ScVal::Address(holder),
+        ];
+        Self {
+            contract,
+            key: ScVal::Vec(Some(ScVec(key.try_into().unwrap()))),
+            durability: ContractDataDurability::Persistent,
+        }
+    }
+}
+
+impl ContractDataEntry {
+    /// Returns the contract instance, if the entry is the contract's
+    /// instance entry.
+    #[must_use]
+    pub fn contract_instance(&self) -> Option<&ScContractInstance> {
+        match (&self.key, &self.val) {
+            (ScVal::LedgerKeyContractInstance, ScVal::ContractInstance(instance)) => Some(instance),
+            _ => None,
+        }
+    }
+
+    /// Returns the instance storage of the contract, if the entry is the
+    /// contract's instance entry and the contract has instance storage.
+    #[must_use]
+    pub fn instance_storage(&self) -> Option<&ScMap> {
+        self.contract_instance()?.storage.as_ref()
+    }
+}
+
+impl ScContractInstance {
+    /// Returns the value stored in the instance storage for the key.
+    #[must_use]
+    pub fn storage_get(&self, key: &ScVal) -> Option<&ScVal> {
+        self.storage.as_ref()?.get_by_key(key)
+    }
+}
+
+impl ScMap {
+    /// Returns the value of the entry with the key.
+    ///
+    /// The map is searched assuming it is sorted in the order of
+    /// [`ScVal::host_cmp`], as maps in the ledger are. Entries of an
+    /// unsorted map may not be found.
+    #[must_use]
+    pub fn get_by_key(&self, key: &ScVal) -> Option<&ScVal> {
+        let i = self.binary_search_by(|e| e.key.host_cmp(key)).ok()?;
+        Some(&self[i].val)
+    }
+}
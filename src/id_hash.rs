@@ -0,0 +1,115 @@
+#![cfg(feature = "std")]
+use super::{
+    ContractId, ContractIdPreimage, Hash, HashIdPreimage, HashIdPreimageContractId,
+    HashIdPreimageSorobanAuthorization, HashIdPreimageSorobanAuthorizationWithAddress, LedgerKey,
+    LedgerKeyTtl, Limits, SorobanAddressCredentialsWithDelegates, SorobanAuthorizationEntry,
+    SorobanCredentials, WriteXdr,
+};
+
+use sha2::{Digest, Sha256};
+
+impl HashIdPreimage {
+    /// Computes the hash of the preimage, that is the id the preimage is
+    /// for, such as an operation id, contract id, or the payload signed in a
+    /// Soroban authorization.
+    ///
+    /// # Errors
+    ///
+    /// If there is any issue serializing to XDR.
+    pub fn hash(&self) -> Result<[u8; 32], super::Error> {
+        let payload = self.to_xdr(Limits::none())?;
+        let hash = Sha256::digest(payload);
+        Ok(hash.into())
+    }
+}
+
+impl ContractIdPreimage {
+    /// Computes the id of the contract created from the preimage.
+    ///
+    /// # Arguments
+    ///
+    /// * `network_id` - The network ID the contract is created on.
+    ///
+    /// # Errors
+    ///
+    /// If there is any issue serializing to XDR.
+    pub fn contract_id(&self, network_id: [u8; 32]) -> Result<ContractId, super::Error> {
+        let preimage = HashIdPreimage::ContractId(HashIdPreimageContractId {
+            network_id: Hash(network_id),
+            contract_id_preimage: self.clone(),
+        });
+        Ok(ContractId(Hash(preimage.hash()?)))
+    }
+}
+
+impl LedgerKey {
+    /// Computes the hash of the ledger key, that is the hash the TTL entry of
+    /// a contract data or contract code entry is keyed by.
+    ///
+    /// # Errors
+    ///
+    /// If there is any issue serializing to XDR.
+    pub fn ttl_key_hash(&self) -> Result<[u8; 32], super::Error> {
+        let payload = self.to_xdr(Limits::none())?;
+        let hash = Sha256::digest(payload);
+        Ok(hash.into())
+    }
+
+    /// Returns the key of the TTL entry of the ledger entry with this key.
+    ///
+    /// # Errors
+    ///
+    /// If there is any issue serializing to XDR.
+    pub fn ttl_key(&self) -> Result<LedgerKeyTtl, super::Error> {
+        Ok(LedgerKeyTtl {
+            key_hash: Hash(self.ttl_key_hash()?),
+        })
+    }
+}
+
+impl SorobanAuthorizationEntry {
+    /// Computes the payload signed by the address of the entry's credentials.
+    ///
+    /// # Arguments
+    ///
+    /// * `network_id` - The network ID the entry is authorized on.
+    ///
+    /// # Returns
+    ///
+    /// The payload, or `None` if the credentials are those of the source
+    /// account, which are authorized by the transaction's signatures.
+    ///
+    /// # Errors
+    ///
+    /// If there is any issue serializing to XDR.
+    pub fn signature_payload(
+        &self,
+        network_id: [u8; 32],
+    ) -> Result<Option<[u8; 32]>, super::Error> {
+        let preimage = match &self.credentials {
+            SorobanCredentials::SourceAccount => return Ok(None),
+            SorobanCredentials::Address(c) => {
+                HashIdPreimage::SorobanAuthorization(HashIdPreimageSorobanAuthorization {
+                    network_id: Hash(network_id),
+                    nonce: c.nonce,
+                    signature_expiration_ledger: c.signature_expiration_ledger,
+                    invocation: self.root_invocation.clone(),
+                })
+            }
+            SorobanCredentials::AddressV2(c)
+            | SorobanCredentials::AddressWithDelegates(SorobanAddressCredentialsWithDelegates {
+                address_credentials: c,
+                ..
+            }) => HashIdPreimage::SorobanAuthorizationWithAddress(
+                HashIdPreimageSorobanAuthorizationWithAddress {
+                    network_id: Hash(network_id),
+                    nonce: c.nonce,
+                    signature_expiration_ledger: c.signature_expiration_ledger,
+                    address: c.address.clone(),
+                    invocation: self.root_invocation.clone(),
+                },
+            ),
+        };
+        Ok(Some(preimage.hash()?))
+    }
+}
@@ -0,0 +1,319 @@
+use super::{Error, LedgerCloseMeta, Limited, Limits, ReadXdr, WriteXdr};
+
+use core::fmt;
+use std::io::{Read, Write};
+
+/// Error returned when reading or writing a `LedgerCloseMetaBatch` one ledger
+/// at a time.
+#[derive(Debug, PartialEq)]
+pub enum LedgerCloseMetaBatchError {
+    /// The batch could not be decoded or encoded.
+    Xdr(Error),
+    /// The number of ledgers in the batch does not match the number of
+    /// ledgers in the batch's sequence range.
+    LengthMismatch {
+        start_sequence: u32,
+        end_sequence: u32,
+        len: u32,
+    },
+    /// A ledger in the batch does not have the sequence expected at its
+    /// position in the batch.
+    SequenceMismatch { expected: u32, actual: u32 },
+    /// A ledger was written after every ledger in the batch's sequence range
+    /// was already written.
+    SequenceOutOfRange {
+        start_sequence: u32,
+        end_sequence: u32,
+        sequence: u32,
+    },
+    /// The batch was finished before every ledger in the batch's sequence
+    /// range was written.
+    Incomplete {
+        end_sequence: u32,
+        next_sequence: u32,
+    },
+}
+
+impl fmt::Display for LedgerCloseMetaBatchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Xdr(e) => write!(f, "{e}"),
+            Self::LengthMismatch {
+                start_sequence,
+                end_sequence,
+                len,
+            } => write!(
+                f,
+                "batch of ledgers {start_sequence}..={end_sequence} contains {len} ledgers"
+            ),
+            Self::SequenceMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "expected ledger {expected} in batch, got ledger {actual}"
+                )
+            }
+            Self::SequenceOutOfRange {
+                start_sequence,
+                end_sequence,
+                sequence,
+            } => write!(
+                f,
+                "ledger {sequence} is outside of batch of ledgers {start_sequence}..={end_sequence}"
+            ),
+            Self::Incomplete {
+                end_sequence,
+                next_sequence,
+            } => write!(
+                f,
+                "batch finished at ledger {next_sequence} before reaching ledger {end_sequence}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LedgerCloseMetaBatchError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Xdr(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for LedgerCloseMetaBatchError {
+    fn from(e: Error) -> Self {
+        Self::Xdr(e)
+    }
+}
+
+impl From<std::io::Error> for LedgerCloseMetaBatchError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Xdr(Error::Io(e))
+    }
+}
+
+impl LedgerCloseMeta {
+    /// Returns the sequence of the ledger the meta is for.
+    #[must_use]
+    pub fn ledger_seq(&self) -> u32 {
+        match self {
+            LedgerCloseMeta::V0(m) => m.ledger_header.header.ledger_seq,
+            LedgerCloseMeta::V1(m) => m.ledger_header.header.ledger_seq,
+            LedgerCloseMeta::V2(m) => m.ledger_header.header.ledger_seq,
+        }
+    }
+}
+
+/// Returns the number of ledgers in the inclusive sequence range, or `None` if
+/// the range is empty or its length is not representable.
+fn range_len(start_sequence: u32, end_sequence: u32) -> Option<u32> {
+    end_sequence
+        .checked_sub(start_sequence)
+        .and_then(|d| d.checked_add(1))
+}
+
+/// Reads the `LedgerCloseMeta` contained in an XDR encoded
+/// `LedgerCloseMetaBatch` one at a time, without decoding the entire batch
+/// into memory.
+///
+/// The batch's `start_sequence` and `end_sequence` are read when the reader
+/// is constructed, and every `LedgerCloseMeta` is checked to have the
+/// sequence expected at its position in the batch.
+pub struct LedgerCloseMetaBatchReader<R: Read> {
+    reader: Limited<R>,
+    start_sequence: u32,
+    end_sequence: u32,
+    len: u32,
+    read: u32,
+    failed: bool,
+}
+
+impl<R: Read> LedgerCloseMetaBatchReader<R> {
+    /// Constructs a new `LedgerCloseMetaBatchReader`, reading the batch's
+    /// sequence range and length.
+    ///
+    /// # Errors
+    ///
+    /// If the batch's sequence range and length cannot be read, or if the
+    /// length does not match the number of ledgers in the range.
+    pub fn new(r: R, limits: Limits) -> Result<Self, LedgerCloseMetaBatchError> {
+        let mut reader = Limited::new(r, limits);
+        let start_sequence = u32::read_xdr(&mut reader)?;
+        let end_sequence = u32::read_xdr(&mut reader)?;
+        let len = u32::read_xdr(&mut reader)?;
+        if range_len(start_sequence, end_sequence) != Some(len) {
+            return Err(LedgerCloseMetaBatchError::LengthMismatch {
+                start_sequence,
+                end_sequence,
+                len,
+            });
+        }
+        Ok(Self {
+            reader,
+            start_sequence,
+            end_sequence,
+            len,
+            read: 0,
+            failed: false,
+        })
+    }
+
+    #[must_use]
+    pub fn start_sequence(&self) -> u32 {
+        self.start_sequence
+    }
+
+    #[must_use]
+    pub fn end_sequence(&self) -> u32 {
+        self.end_sequence
+    }
+
+    /// Returns the number of `LedgerCloseMeta` in the batch that are yet to be
+    /// read.
+    #[must_use]
+    pub fn remaining(&self) -> u32 {
+        self.len - self.read
+    }
+
+    /// Consumes the reader, returning the underlying reader positioned after
+    /// the last `LedgerCloseMeta` that was read.
+    pub fn into_inner(self) -> R {
+        self.reader.inner
+    }
+
+    fn read_next(&mut self) -> Result<LedgerCloseMeta, LedgerCloseMetaBatchError> {
+        let meta = self
+            .reader
+            .with_limited_depth(|r| LedgerCloseMeta::read_xdr(r))?;
+        let expected = self.start_sequence + self.read;
+        let actual = meta.ledger_seq();
+        if actual != expected {
+            return Err(LedgerCloseMetaBatchError::SequenceMismatch { expected, actual });
+        }
+        self.read += 1;
+        Ok(meta)
+    }
+}
+
+impl<R: Read> Iterator for LedgerCloseMetaBatchReader<R> {
+    type Item = Result<LedgerCloseMeta, LedgerCloseMetaBatchError>;
+
+    // Iteration stops once every `LedgerCloseMeta` in the batch has been read,
+    // or after the first error is returned, since the position of the
+    // underlying reader is undefined after an error.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.read >= self.len {
+            return None;
+        }
+        let r = self.read_next();
+        self.failed = r.is_err();
+        Some(r)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining() as usize;
+        (0, Some(remaining))
+    }
+}
+
+/// Writes an XDR encoded `LedgerCloseMetaBatch` one `LedgerCloseMeta` at a
+/// time, without holding the entire batch in memory.
+///
+/// The batch's sequence range is written when the writer is constructed, and
+/// every `LedgerCloseMeta` written must have the next sequence in the range.
+/// The batch is complete once every ledger in the range has been written,
+/// which is checked by [`LedgerCloseMetaBatchWriter::finish`].
+pub struct LedgerCloseMetaBatchWriter<W: Write> {
+    writer: Limited<W>,
+    start_sequence: u32,
+    end_sequence: u32,
+    next_sequence: Option<u32>,
+}
+
+impl<W: Write> LedgerCloseMetaBatchWriter<W> {
+    /// Constructs a new `LedgerCloseMetaBatchWriter`, writing the batch's
+    /// sequence range and length.
+    ///
+    /// # Errors
+    ///
+    /// If the sequence range is empty, or if the sequence range and length
+    /// cannot be written.
+    pub fn new(
+        w: W,
+        limits: Limits,
+        start_sequence: u32,
+        end_sequence: u32,
+    ) -> Result<Self, LedgerCloseMetaBatchError> {
+        let len = range_len(start_sequence, end_sequence).ok_or(
+            LedgerCloseMetaBatchError::LengthMismatch {
+                start_sequence,
+                end_sequence,
+                len: 0,
+            },
+        )?;
+        let mut writer = Limited::new(w, limits);
+        start_sequence.write_xdr(&mut writer)?;
+        end_sequence.write_xdr(&mut writer)?;
+        len.write_xdr(&mut writer)?;
+        Ok(Self {
+            writer,
+            start_sequence,
+            end_sequence,
+            next_sequence: Some(start_sequence),
+        })
+    }
+
+    /// Returns the sequence of the next `LedgerCloseMeta` to be written, or
+    /// `None` if the batch is complete.
+    #[must_use]
+    pub fn next_sequence(&self) -> Option<u32> {
+        self.next_sequence
+    }
+
+    /// Writes the next `LedgerCloseMeta` in the batch.
+    ///
+    /// # Errors
+    ///
+    /// If the meta is not for the next ledger in the batch, if the batch is
+    /// already complete, or if the meta cannot be written.
+    pub fn push(&mut self, meta: &LedgerCloseMeta) -> Result<(), LedgerCloseMetaBatchError> {
+        let sequence = meta.ledger_seq();
+        let Some(expected) = self.next_sequence else {
+            return Err(LedgerCloseMetaBatchError::SequenceOutOfRange {
+                start_sequence: self.start_sequence,
+                end_sequence: self.end_sequence,
+                sequence,
+            });
+        };
+        if sequence != expected {
+            return Err(LedgerCloseMetaBatchError::SequenceMismatch {
+                expected,
+                actual: sequence,
+            });
+        }
+        self.writer.with_limited_depth(|w| meta.write_xdr(w))?;
+        self.next_sequence = if sequence == self.end_sequence {
+            None
+        } else {
+            Some(sequence + 1)
+        };
+        Ok(())
+    }
+
+    /// Completes the batch, flushing and returning the underlying writer.
+    ///
+    /// # Errors
+    ///
+    /// If not every ledger in the batch's sequence range has been written, or
+    /// if the underlying writer cannot be flushed.
+    pub fn finish(mut self) -> Result<W, LedgerCloseMetaBatchError> {
+        if let Some(next_sequence) = self.next_sequence {
+            return Err(LedgerCloseMetaBatchError::Incomplete {
+                end_sequence: self.end_sequence,
+                next_sequence,
+            });
+        }
+        self.writer.flush()?;
+        Ok(self.writer.inner)
+    }
+}
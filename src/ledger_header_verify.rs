@@ -0,0 +1,321 @@
+use super::{
+    Error, GeneralizedTransactionSet, Hash, LedgerHeader, LedgerHeaderHistoryEntry, Limits,
+    TransactionResultSet, TransactionSet, WriteXdr,
+};
+
+use core::{borrow::Borrow, fmt};
+use sha2::{Digest, Sha256};
+
+/// Error returned when verifying ledger headers, and the values committed to
+/// in them, fails.
+#[derive(Debug, PartialEq)]
+pub enum LedgerHeaderVerifyError {
+    /// A value could not be serialized to XDR for hashing.
+    Xdr(Error),
+    /// The hash stored alongside a header is not the hash of the header.
+    HeaderHashMismatch {
+        ledger_seq: u32,
+        expected: Hash,
+        actual: Hash,
+    },
+    /// A header does not have the ledger sequence that immediately follows
+    /// the previous header.
+    LedgerSeqNotSequential {
+        ledger_seq: u32,
+        previous_ledger_seq: u32,
+    },
+    /// A header's `previous_ledger_hash` is not the hash of the previous
+    /// header.
+    PreviousLedgerHashMismatch {
+        ledger_seq: u32,
+        expected: Hash,
+        actual: Hash,
+    },
+    /// A transaction set does not hash to the header's
+    /// `scp_value.tx_set_hash`.
+    TxSetHashMismatch {
+        ledger_seq: u32,
+        expected: Hash,
+        actual: Hash,
+    },
+    /// A transaction result set does not hash to the header's
+    /// `tx_set_result_hash`.
+    TxSetResultHashMismatch {
+        ledger_seq: u32,
+        expected: Hash,
+        actual: Hash,
+    },
+}
+
+impl fmt::Display for LedgerHeaderVerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Xdr(e) => write!(f, "{e}"),
+            Self::HeaderHashMismatch {
+                ledger_seq,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "ledger {ledger_seq} header hash mismatch: expected {expected}, got {actual}"
+            ),
+            Self::LedgerSeqNotSequential {
+                ledger_seq,
+                previous_ledger_seq,
+            } => write!(
+                f,
+                "ledger {ledger_seq} does not follow previous ledger {previous_ledger_seq}"
+            ),
+            Self::PreviousLedgerHashMismatch {
+                ledger_seq,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "ledger {ledger_seq} previous ledger hash mismatch: expected {expected}, got {actual}"
+            ),
+            Self::TxSetHashMismatch {
+                ledger_seq,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "ledger {ledger_seq} tx set hash mismatch: expected {expected}, got {actual}"
+            ),
+            Self::TxSetResultHashMismatch {
+                ledger_seq,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "ledger {ledger_seq} tx set result hash mismatch: expected {expected}, got {actual}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LedgerHeaderVerifyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Xdr(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for LedgerHeaderVerifyError {
+    fn from(e: Error) -> Self {
+        Self::Xdr(e)
+    }
+}
+
+impl LedgerHeader {
+    /// Computes the hash of the ledger header.
+    ///
+    /// # Returns
+    ///
+    /// The ledger header hash, as stored in `LedgerHeaderHistoryEntry.hash`
+    /// and in the `previous_ledger_hash` of the following ledger.
+    ///
+    /// # Errors
+    ///
+    /// If there is any issue serializing to XDR.
+    pub fn hash(&self) -> Result<[u8; 32], Error> {
+        let payload = self.to_xdr(Limits::none())?;
+        let hash = Sha256::digest(payload);
+        Ok(hash.into())
+    }
+
+    /// Verifies that the transaction set hashes to the `scp_value.tx_set_hash`
+    /// committed to in the header.
+    ///
+    /// # Errors
+    ///
+    /// If the hashes do not match, or if there is any issue serializing to
+    /// XDR.
+    pub fn verify_tx_set(
+        &self,
+        tx_set: &GeneralizedTransactionSet,
+    ) -> Result<(), LedgerHeaderVerifyError> {
+        let actual = Hash(tx_set.hash()?);
+        if actual == self.scp_value.tx_set_hash {
+            Ok(())
+        } else {
+            Err(LedgerHeaderVerifyError::TxSetHashMismatch {
+                ledger_seq: self.ledger_seq,
+                expected: self.scp_value.tx_set_hash.clone(),
+                actual,
+            })
+        }
+    }
+
+    /// Verifies that the legacy transaction set, used prior to protocol 20,
+    /// hashes to the `scp_value.tx_set_hash` committed to in the header.
+    ///
+    /// # Errors
+    ///
+    /// If the hashes do not match, or if there is any issue serializing to
+    /// XDR.
+    pub fn verify_legacy_tx_set(
+        &self,
+        tx_set: &TransactionSet,
+    ) -> Result<(), LedgerHeaderVerifyError> {
+        let actual = Hash(tx_set.hash()?);
+        if actual == self.scp_value.tx_set_hash {
+            Ok(())
+        } else {
+            Err(LedgerHeaderVerifyError::TxSetHashMismatch {
+                ledger_seq: self.ledger_seq,
+                expected: self.scp_value.tx_set_hash.clone(),
+                actual,
+            })
+        }
+    }
+
+    /// Verifies that the transaction result set hashes to the
+    /// `tx_set_result_hash` committed to in the header.
+    ///
+    /// # Errors
+    ///
+    /// If the hashes do not match, or if there is any issue serializing to
+    /// XDR.
+    pub fn verify_tx_set_result(
+        &self,
+        result_set: &TransactionResultSet,
+    ) -> Result<(), LedgerHeaderVerifyError> {
+        let actual = Hash(result_set.hash()?);
+        if actual == self.tx_set_result_hash {
+            Ok(())
+        } else {
+            Err(LedgerHeaderVerifyError::TxSetResultHashMismatch {
+                ledger_seq: self.ledger_seq,
+                expected: self.tx_set_result_hash.clone(),
+                actual,
+            })
+        }
+    }
+}
+
+impl LedgerHeaderHistoryEntry {
+    /// Verifies that the entry's `hash` is the hash of its header.
+    ///
+    /// # Errors
+    ///
+    /// If the hashes do not match, or if there is any issue serializing to
+    /// XDR.
+    pub fn verify_hash(&self) -> Result<(), LedgerHeaderVerifyError> {
+        let actual = Hash(self.header.hash()?);
+        if actual == self.hash {
+            Ok(())
+        } else {
+            Err(LedgerHeaderVerifyError::HeaderHashMismatch {
+                ledger_seq: self.header.ledger_seq,
+                expected: self.hash.clone(),
+                actual,
+            })
+        }
+    }
+
+    /// Verifies that the entry immediately follows the previous entry, by
+    /// having the next ledger sequence and by committing to the previous
+    /// entry's hash.
+    ///
+    /// The hashes of the entries themselves are not checked, use
+    /// [`LedgerHeaderHistoryEntry::verify_hash`] for that.
+    ///
+    /// # Errors
+    ///
+    /// If the entry does not follow the previous entry.
+    pub fn verify_follows(
+        &self,
+        previous: &LedgerHeaderHistoryEntry,
+    ) -> Result<(), LedgerHeaderVerifyError> {
+        verify_follows(&self.header, previous.header.ledger_seq, &previous.hash)
+    }
+}
+
+fn verify_follows(
+    header: &LedgerHeader,
+    previous_ledger_seq: u32,
+    previous_hash: &Hash,
+) -> Result<(), LedgerHeaderVerifyError> {
+    if previous_ledger_seq.checked_add(1) != Some(header.ledger_seq) {
+        return Err(LedgerHeaderVerifyError::LedgerSeqNotSequential {
+            ledger_seq: header.ledger_seq,
+            previous_ledger_seq,
+        });
+    }
+    if &header.previous_ledger_hash != previous_hash {
+        return Err(LedgerHeaderVerifyError::PreviousLedgerHashMismatch {
+            ledger_seq: header.ledger_seq,
+            expected: previous_hash.clone(),
+            actual: header.previous_ledger_hash.clone(),
+        });
+    }
+    Ok(())
+}
+
+/// Verifies a sequence of ledger header history entries one entry at a time,
+/// such as when streaming the entries out of a history archive file.
+///
+/// Only the sequence and hash of the last accepted entry is retained.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LedgerHeaderChainVerifier {
+    last: Option<(u32, Hash)>,
+}
+
+impl LedgerHeaderChainVerifier {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Verifies the next entry in the chain, checking that its hash is the
+    /// hash of its header, and that it follows the previously pushed entry if
+    /// any.
+    ///
+    /// The first entry pushed is only checked for its own hash, and anchors
+    /// the chain.
+    ///
+    /// # Errors
+    ///
+    /// If the entry is not valid or does not follow the previous entry. The
+    /// verifier is left unchanged when an error is returned.
+    pub fn push(
+        &mut self,
+        entry: &LedgerHeaderHistoryEntry,
+    ) -> Result<(), LedgerHeaderVerifyError> {
+        entry.verify_hash()?;
+        if let Some((previous_ledger_seq, previous_hash)) = &self.last {
+            verify_follows(&entry.header, *previous_ledger_seq, previous_hash)?;
+        }
+        self.last = Some((entry.header.ledger_seq, entry.hash.clone()));
+        Ok(())
+    }
+
+    /// Returns the ledger sequence and hash of the last verified entry.
+    #[must_use]
+    pub fn last(&self) -> Option<(u32, &Hash)> {
+        self.last.as_ref().map(|(seq, hash)| (*seq, hash))
+    }
+}
+
+/// Verifies that the entries form a valid chain of ledger headers, where
+/// every entry's hash is the hash of its header, and every entry follows the
+/// entry before it.
+///
+/// # Errors
+///
+/// On the first entry that is not valid or does not follow the previous
+/// entry.
+pub fn verify_ledger_header_chain<I, E>(entries: I) -> Result<(), LedgerHeaderVerifyError>
+where
+    I: IntoIterator<Item = E>,
+    E: Borrow<LedgerHeaderHistoryEntry>,
+{
+    let mut verifier = LedgerHeaderChainVerifier::new();
+    for entry in entries {
+        verifier.push(entry.borrow())?;
+    }
+    Ok(())
+}
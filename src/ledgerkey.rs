@@ -6,6 +6,8 @@ use super::{
     LedgerKeyTrustLine, LedgerKeyTtl, LiquidityPoolEntry, OfferEntry, TrustLineEntry, TtlEntry,
 };
 
+use core::cmp::Ordering;
+
 impl LedgerEntry {
     #[must_use]
     pub fn to_key(&self) -> LedgerKey {
@@ -13,6 +15,54 @@ impl LedgerEntry {
     }
 }
 
+impl LedgerKey {
+    /// Compares ledger keys in the order that stellar-core sorts ledger
+    /// entries, such as the order of entries within a bucket.
+    ///
+    /// Keys are ordered first by their `LedgerEntryType`, and then by the
+    /// fields that identify the entry in the order the fields are declared,
+    /// with each field compared in XDR order: unions by discriminant then
+    /// value, and opaque and string values lexicographically.
+    #[must_use]
+    pub fn entry_id_cmp(&self, other: &Self) -> Ordering {
+        let l = self.discriminant() as i32;
+        let r = other.discriminant() as i32;
+        l.cmp(&r).then_with(|| match (self, other) {
+            (LedgerKey::Account(l), LedgerKey::Account(r)) => l.account_id.cmp(&r.account_id),
+            (LedgerKey::Trustline(l), LedgerKey::Trustline(r)) => l
+                .account_id
+                .cmp(&r.account_id)
+                .then_with(|| l.asset.cmp(&r.asset)),
+            (LedgerKey::Offer(l), LedgerKey::Offer(r)) => l
+                .seller_id
+                .cmp(&r.seller_id)
+                .then_with(|| l.offer_id.cmp(&r.offer_id)),
+            (LedgerKey::Data(l), LedgerKey::Data(r)) => l
+                .account_id
+                .cmp(&r.account_id)
+                .then_with(|| l.data_name.cmp(&r.data_name)),
+            (LedgerKey::ClaimableBalance(l), LedgerKey::ClaimableBalance(r)) => {
+                l.balance_id.cmp(&r.balance_id)
+            }
+            (LedgerKey::LiquidityPool(l), LedgerKey::LiquidityPool(r)) => {
+                l.liquidity_pool_id.cmp(&r.liquidity_pool_id)
+            }
+            (LedgerKey::ContractData(l), LedgerKey::ContractData(r)) => l
+                .contract
+                .cmp(&r.contract)
+                .then_with(|| l.key.cmp(&r.key))
+                .then_with(|| (l.durability as i32).cmp(&(r.durability as i32))),
+            (LedgerKey::ContractCode(l), LedgerKey::ContractCode(r)) => l.hash.cmp(&r.hash),
+            (LedgerKey::ConfigSetting(l), LedgerKey::ConfigSetting(r)) => {
+                (l.config_setting_id as i32).cmp(&(r.config_setting_id as i32))
+            }
+            (LedgerKey::Ttl(l), LedgerKey::Ttl(r)) => l.key_hash.cmp(&r.key_hash),
+            // Keys of different types were ordered by type above.
+            _ => Ordering::Equal,
+        })
+    }
+}
+
 impl LedgerEntryData {
     #[must_use]
     pub fn to_key(&self) -> LedgerKey {
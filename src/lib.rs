@@ -97,6 +97,11 @@
 //! -
 //! ```
 //!
+//! Parse the `ScSpecEntry` stream embedded in a contract's Wasm binary:
+//! ```console
+//! stellar-xdr decode --type ScSpecEntry --input wasm --output json-formatted contract.wasm
+//! ```
+//!
 //! Parse a `BucketEntry` framed stream from a bucket file:
 //! ```console
 //! stellar-xdr decode --type BucketEntry --input stream-framed --output json-formatted bucket.xdr
@@ -147,11 +152,68 @@ mod transaction_conversions;
 mod scval_validations;
 pub use scval_validations::*;
 
+#[cfg(feature = "alloc")]
+mod contract_data;
 #[cfg(feature = "alloc")]
 mod scmap;
 
 mod tx_auths;
 mod tx_hash;
+#[cfg(feature = "std")]
+mod tx_set_hash;
+
+mod id_hash;
+
+#[cfg(feature = "std")]
+mod tx_set_apply_order;
+#[cfg(feature = "std")]
+pub use tx_set_apply_order::*;
+
+#[cfg(feature = "std")]
+mod ledger_header_verify;
+#[cfg(feature = "std")]
+pub use ledger_header_verify::*;
+
+#[cfg(feature = "std")]
+mod ledger_close_meta_batch;
+#[cfg(feature = "std")]
+pub use ledger_close_meta_batch::*;
+
+#[cfg(feature = "std")]
+mod bucket;
+#[cfg(feature = "std")]
+pub use bucket::*;
+#[cfg(feature = "std")]
+mod bucket_merge;
+#[cfg(feature = "std")]
+pub use bucket_merge::*;
+
+#[cfg(feature = "std")]
+mod wasm;
+#[cfg(feature = "std")]
+pub use wasm::*;
+
+mod scspec;
+mod scspec_compat;
+#[cfg(feature = "alloc")]
+pub use scspec_compat::*;
+mod scspec_event;
+#[cfg(feature = "alloc")]
+pub use scspec_event::*;
+mod scspec_invoke;
+#[cfg(feature = "alloc")]
+pub use scspec_invoke::*;
+mod scval_spec_json;
+mod scval_spec_validations;
+#[cfg(feature = "serde_json")]
+pub use scval_spec_json::*;
+#[cfg(feature = "alloc")]
+pub use scval_spec_validations::*;
+mod scval_json;
+mod scval_ordering;
+mod scval_text;
+#[cfg(feature = "alloc")]
+pub use scval_text::*;
 
 #[cfg(feature = "cli")]
 pub mod cli;
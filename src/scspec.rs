@@ -0,0 +1,90 @@
+#![cfg(feature = "alloc")]
+use super::{
+    ScSpecEntry, ScSpecTypeDef, ScSpecUdtEnumV0, ScSpecUdtErrorEnumV0, ScSpecUdtStructV0,
+    ScSpecUdtUnionV0,
+};
+
+use core::fmt;
+
+/// Formats the type as it is written in Rust contracts, e.g. `u32`,
+/// `Option<Address>`, `Map<Symbol, Vec<i128>>`, `BytesN<32>`, or the name of
+/// a user defined type.
+impl fmt::Display for ScSpecTypeDef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScSpecTypeDef::Val => f.write_str("Val"),
+            ScSpecTypeDef::Bool => f.write_str("bool"),
+            ScSpecTypeDef::Void => f.write_str("()"),
+            ScSpecTypeDef::Error => f.write_str("Error"),
+            ScSpecTypeDef::U32 => f.write_str("u32"),
+            ScSpecTypeDef::I32 => f.write_str("i32"),
+            ScSpecTypeDef::U64 => f.write_str("u64"),
+            ScSpecTypeDef::I64 => f.write_str("i64"),
+            ScSpecTypeDef::Timepoint => f.write_str("Timepoint"),
+            ScSpecTypeDef::Duration => f.write_str("Duration"),
+            ScSpecTypeDef::U128 => f.write_str("u128"),
+            ScSpecTypeDef::I128 => f.write_str("i128"),
+            ScSpecTypeDef::U256 => f.write_str("U256"),
+            ScSpecTypeDef::I256 => f.write_str("I256"),
+            ScSpecTypeDef::Bytes => f.write_str("Bytes"),
+            ScSpecTypeDef::String => f.write_str("String"),
+            ScSpecTypeDef::Symbol => f.write_str("Symbol"),
+            ScSpecTypeDef::Address => f.write_str("Address"),
+            ScSpecTypeDef::MuxedAddress => f.write_str("MuxedAddress"),
+            ScSpecTypeDef::Option(o) => write!(f, "Option<{}>", o.value_type),
+            ScSpecTypeDef::Result(r) => write!(f, "Result<{}, {}>", r.ok_type, r.error_type),
+            ScSpecTypeDef::Vec(v) => write!(f, "Vec<{}>", v.element_type),
+            ScSpecTypeDef::Map(m) => write!(f, "Map<{}, {}>", m.key_type, m.value_type),
+            ScSpecTypeDef::Tuple(t) => {
+                f.write_str("(")?;
+                for (i, t) in t.value_types.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{t}")?;
+                }
+                if t.value_types.len() == 1 {
+                    f.write_str(",")?;
+                }
+                f.write_str(")")
+            }
+            ScSpecTypeDef::BytesN(b) => write!(f, "BytesN<{}>", b.n),
+            ScSpecTypeDef::Udt(u) => write!(f, "{}", u.name),
+        }
+    }
+}
+
+/// A user defined type in a contract spec.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ScSpecUdt<'a> {
+    Struct(&'a ScSpecUdtStructV0),
+    Union(&'a ScSpecUdtUnionV0),
+    Enum(&'a ScSpecUdtEnumV0),
+    ErrorEnum(&'a ScSpecUdtErrorEnumV0),
+}
+
+/// Returns the user defined type with the name in the contract spec.
+pub(crate) fn find_udt<'a>(entries: &'a [ScSpecEntry], name: &[u8]) -> Option<ScSpecUdt<'a>> {
+    entries.iter().find_map(|e| match e {
+        ScSpecEntry::UdtStructV0(s) if s.name.as_slice() == name => Some(ScSpecUdt::Struct(s)),
+        ScSpecEntry::UdtUnionV0(u) if u.name.as_slice() == name => Some(ScSpecUdt::Union(u)),
+        ScSpecEntry::UdtEnumV0(e) if e.name.as_slice() == name => Some(ScSpecUdt::Enum(e)),
+        ScSpecEntry::UdtErrorEnumV0(e) if e.name.as_slice() == name => {
+            Some(ScSpecUdt::ErrorEnum(e))
+        }
+        _ => None,
+    })
+}
+
+impl ScSpecUdtStructV0 {
+    /// Returns true if the struct is a tuple struct, a struct with fields
+    /// named by their position, which is stored as a vec rather than a map.
+    #[must_use]
+    pub fn is_tuple(&self) -> bool {
+        !self.fields.is_empty()
+            && self
+                .fields
+                .iter()
+                .all(|f| !f.name.is_empty() && f.name.iter().all(u8::is_ascii_digit))
+    }
+}
@@ -0,0 +1,740 @@
+#![cfg(feature = "alloc")]
+use super::{
+    scspec::{find_udt, ScSpecUdt},
+    ScSpecEntry, ScSpecEventV0, ScSpecFunctionV0, ScSpecTypeDef, ScSpecUdtEnumV0,
+    ScSpecUdtErrorEnumV0, ScSpecUdtStructV0, ScSpecUdtUnionCaseV0, ScSpecUdtUnionV0,
+};
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+use core::fmt;
+
+/// Whether a change to a contract spec breaks existing callers of the
+/// contract, or consumers of its events.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ScSpecChangeImpact {
+    /// The change only adds to the contract's interface.
+    Additive,
+    /// The change may break existing callers or consumers.
+    Breaking,
+}
+
+/// A change between two versions of a contract spec, as reported by
+/// [`compare_contract_specs`].
+///
+/// Documentation and `lib` fields are not compared.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScSpecChange {
+    FunctionAdded {
+        function: String,
+    },
+    FunctionRemoved {
+        function: String,
+    },
+    /// A function was removed and a function with a different name, but the
+    /// same inputs and outputs, was added. Functions that have no inputs and
+    /// no outputs are never reported as renamed, as any two such functions
+    /// have the same inputs and outputs.
+    FunctionRenamed {
+        old_function: String,
+        new_function: String,
+    },
+    FunctionInputAdded {
+        function: String,
+        input: String,
+    },
+    FunctionInputRemoved {
+        function: String,
+        input: String,
+    },
+    /// An input was renamed, which breaks callers that pass arguments by
+    /// name.
+    FunctionInputRenamed {
+        function: String,
+        old_input: String,
+        new_input: String,
+    },
+    FunctionInputTypeChanged {
+        function: String,
+        input: String,
+        old_type: ScSpecTypeDef,
+        new_type: ScSpecTypeDef,
+    },
+    FunctionOutputChanged {
+        function: String,
+        old_type: Option<ScSpecTypeDef>,
+        new_type: Option<ScSpecTypeDef>,
+    },
+    TypeAdded {
+        type_name: String,
+    },
+    TypeRemoved {
+        type_name: String,
+    },
+    /// A user defined type changed between being a struct, union, enum, or
+    /// error enum.
+    TypeKindChanged {
+        type_name: String,
+    },
+    StructFieldAdded {
+        type_name: String,
+        field: String,
+    },
+    StructFieldRemoved {
+        type_name: String,
+        field: String,
+    },
+    StructFieldTypeChanged {
+        type_name: String,
+        field: String,
+        old_type: ScSpecTypeDef,
+        new_type: ScSpecTypeDef,
+    },
+    /// A case was added to a union. If the union is returned by a function
+    /// or published in an event, directly or within another type, clients
+    /// that decode the union may not be able to decode the case.
+    UnionCaseAdded {
+        type_name: String,
+        case: String,
+        output: bool,
+    },
+    UnionCaseRemoved {
+        type_name: String,
+        case: String,
+    },
+    /// The values carried by a union case changed.
+    UnionCaseTypesChanged {
+        type_name: String,
+        case: String,
+    },
+    /// A case was added to an enum. If the enum is returned by a function or
+    /// published in an event, directly or within another type, clients that
+    /// decode the enum may not be able to decode the case.
+    EnumCaseAdded {
+        type_name: String,
+        case: String,
+        output: bool,
+    },
+    EnumCaseRemoved {
+        type_name: String,
+        case: String,
+    },
+    EnumCaseValueChanged {
+        type_name: String,
+        case: String,
+        old_value: u32,
+        new_value: u32,
+    },
+    ErrorCaseAdded {
+        type_name: String,
+        case: String,
+    },
+    ErrorCaseRemoved {
+        type_name: String,
+        case: String,
+    },
+    ErrorCodeChanged {
+        type_name: String,
+        case: String,
+        old_code: u32,
+        new_code: u32,
+    },
+    EventAdded {
+        event: String,
+    },
+    EventRemoved {
+        event: String,
+    },
+    EventPrefixTopicsChanged {
+        event: String,
+    },
+    EventDataFormatChanged {
+        event: String,
+    },
+    /// A param was added to an event, which breaks consumers that decode the
+    /// event's topics or data by position.
+    EventParamAdded {
+        event: String,
+        param: String,
+    },
+    EventParamRemoved {
+        event: String,
+        param: String,
+    },
+    /// The type of an event param, or whether it is published in the topics
+    /// or the data, changed.
+    EventParamChanged {
+        event: String,
+        param: String,
+    },
+}
+
+impl ScSpecChange {
+    /// Returns whether the change is additive or breaking.
+    #[must_use]
+    pub fn impact(&self) -> ScSpecChangeImpact {
+        match self {
+            Self::FunctionAdded { .. }
+            | Self::TypeAdded { .. }
+            | Self::UnionCaseAdded { output: false, .. }
+            | Self::EnumCaseAdded { output: false, .. }
+            | Self::ErrorCaseAdded { .. }
+            | Self::EventAdded { .. } => ScSpecChangeImpact::Additive,
+            _ => ScSpecChangeImpact::Breaking,
+        }
+    }
+
+    /// Returns true if the change may break existing callers or consumers.
+    #[must_use]
+    pub fn is_breaking(&self) -> bool {
+        self.impact() == ScSpecChangeImpact::Breaking
+    }
+}
+
+impl fmt::Display for ScSpecChange {
+    #[allow(clippy::too_many_lines)]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::FunctionAdded { function } => write!(f, "function {function} added"),
+            Self::FunctionRemoved { function } => write!(f, "function {function} removed"),
+            Self::FunctionRenamed {
+                old_function,
+                new_function,
+            } => write!(f, "function {old_function} renamed to {new_function}"),
+            Self::FunctionInputAdded { function, input } => {
+                write!(f, "function {function} input {input} added")
+            }
+            Self::FunctionInputRemoved { function, input } => {
+                write!(f, "function {function} input {input} removed")
+            }
+            Self::FunctionInputRenamed {
+                function,
+                old_input,
+                new_input,
+            } => write!(
+                f,
+                "function {function} input {old_input} renamed to {new_input}"
+            ),
+            Self::FunctionInputTypeChanged {
+                function,
+                input,
+                old_type,
+                new_type,
+            } => write!(
+                f,
+                "function {function} input {input} type changed from {old_type} to {new_type}"
+            ),
+            Self::FunctionOutputChanged {
+                function,
+                old_type,
+                new_type,
+            } => write!(
+                f,
+                "function {function} output changed from {} to {}",
+                OutputType(old_type.as_ref()),
+                OutputType(new_type.as_ref()),
+            ),
+            Self::TypeAdded { type_name } => write!(f, "type {type_name} added"),
+            Self::TypeRemoved { type_name } => write!(f, "type {type_name} removed"),
+            Self::TypeKindChanged { type_name } => write!(f, "type {type_name} kind changed"),
+            Self::StructFieldAdded { type_name, field } => {
+                write!(f, "struct {type_name} field {field} added")
+            }
+            Self::StructFieldRemoved { type_name, field } => {
+                write!(f, "struct {type_name} field {field} removed")
+            }
+            Self::StructFieldTypeChanged {
+                type_name,
+                field,
+                old_type,
+                new_type,
+            } => write!(
+                f,
+                "struct {type_name} field {field} type changed from {old_type} to {new_type}"
+            ),
+            Self::UnionCaseAdded {
+                type_name, case, ..
+            } => {
+                write!(f, "union {type_name} case {case} added")
+            }
+            Self::UnionCaseRemoved { type_name, case } => {
+                write!(f, "union {type_name} case {case} removed")
+            }
+            Self::UnionCaseTypesChanged { type_name, case } => {
+                write!(f, "union {type_name} case {case} types changed")
+            }
+            Self::EnumCaseAdded {
+                type_name, case, ..
+            } => {
+                write!(f, "enum {type_name} case {case} added")
+            }
+            Self::EnumCaseRemoved { type_name, case } => {
+                write!(f, "enum {type_name} case {case} removed")
+            }
+            Self::EnumCaseValueChanged {
+                type_name,
+                case,
+                old_value,
+                new_value,
+            } => write!(
+                f,
+                "enum {type_name} case {case} value changed from {old_value} to {new_value}"
+            ),
+            Self::ErrorCaseAdded { type_name, case } => {
+                write!(f, "error enum {type_name} case {case} added")
+            }
+            Self::ErrorCaseRemoved { type_name, case } => {
+                write!(f, "error enum {type_name} case {case} removed")
+            }
+            Self::ErrorCodeChanged {
+                type_name,
+                case,
+                old_code,
+                new_code,
+            } => write!(
+                f,
+                "error enum {type_name} case {case} code changed from {old_code} to {new_code}"
+            ),
+            Self::EventAdded { event } => write!(f, "event {event} added"),
+            Self::EventRemoved { event } => write!(f, "event {event} removed"),
+            Self::EventPrefixTopicsChanged { event } => {
+                write!(f, "event {event} prefix topics changed")
+            }
+            Self::EventDataFormatChanged { event } => {
+                write!(f, "event {event} data format changed")
+            }
+            Self::EventParamAdded { event, param } => {
+                write!(f, "event {event} param {param} added")
+            }
+            Self::EventParamRemoved { event, param } => {
+                write!(f, "event {event} param {param} removed")
+            }
+            Self::EventParamChanged { event, param } => {
+                write!(f, "event {event} param {param} changed")
+            }
+        }
+    }
+}
+
+/// Formats a function output type, which is `()` if the function has no
+/// output.
+struct OutputType<'a>(Option<&'a ScSpecTypeDef>);
+
+impl fmt::Display for OutputType<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Some(t) => write!(f, "{t}"),
+            None => f.write_str("()"),
+        }
+    }
+}
+
+/// Compares two versions of a contract spec and returns the changes from the
+/// old spec to the new spec, each of which is either additive or breaking.
+///
+/// Functions, user defined types, and events are matched by name, struct
+/// fields and union, enum, and error enum cases are matched by name, and
+/// function inputs and event params are matched by position. A removed
+/// function is reported as renamed if exactly one function with a different
+/// name but identical inputs and outputs was added, and the function has
+/// inputs or outputs.
+///
+/// Cases added to unions and enums are breaking if the type is returned by a
+/// function or published in an event of the new spec, directly or within
+/// another type, and otherwise are additive.
+///
+/// Changes are reported for functions first, then user defined types, then
+/// events, in the order they appear in the old spec followed by additions in
+/// the order they appear in the new spec.
+#[must_use]
+pub fn compare_contract_specs(old: &[ScSpecEntry], new: &[ScSpecEntry]) -> Vec<ScSpecChange> {
+    let mut changes = Vec::new();
+    compare_functions(&mut changes, old, new);
+    compare_udts(&mut changes, old, new);
+    compare_events(&mut changes, old, new);
+    changes
+}
+
+fn name(s: &[u8]) -> String {
+    String::from_utf8_lossy(s).into_owned()
+}
+
+fn functions(entries: &[ScSpecEntry]) -> impl Iterator<Item = &ScSpecFunctionV0> {
+    entries.iter().filter_map(|e| match e {
+        ScSpecEntry::FunctionV0(f) => Some(f),
+        _ => None,
+    })
+}
+
+fn events(entries: &[ScSpecEntry]) -> impl Iterator<Item = &ScSpecEventV0> {
+    entries.iter().filter_map(|e| match e {
+        ScSpecEntry::EventV0(e) => Some(e),
+        _ => None,
+    })
+}
+
+fn udt_name(e: &ScSpecEntry) -> Option<&[u8]> {
+    match e {
+        ScSpecEntry::UdtStructV0(s) => Some(&s.name),
+        ScSpecEntry::UdtUnionV0(u) => Some(&u.name),
+        ScSpecEntry::UdtEnumV0(e) => Some(&e.name),
+        ScSpecEntry::UdtErrorEnumV0(e) => Some(&e.name),
+        ScSpecEntry::FunctionV0(_) | ScSpecEntry::EventV0(_) => None,
+    }
+}
+
+/// Returns true if the functions take the same inputs, by name and type, and
+/// return the same outputs.
+fn same_signature(a: &ScSpecFunctionV0, b: &ScSpecFunctionV0) -> bool {
+    a.inputs.len() == b.inputs.len()
+        && a.inputs
+            .iter()
+            .zip(b.inputs.iter())
+            .all(|(a, b)| a.name == b.name && a.type_ == b.type_)
+        && a.outputs == b.outputs
+}
+
+/// Returns the names of the user defined types that are returned by the
+/// functions or published in the events of the spec, directly or within
+/// another type.
+fn output_udts(entries: &[ScSpecEntry]) -> Vec<&[u8]> {
+    let mut pending = functions(entries)
+        .flat_map(|f| f.outputs.iter())
+        .chain(events(entries).flat_map(|e| e.params.iter().map(|p| &p.type_)))
+        .collect::<Vec<_>>();
+    let mut udts = Vec::<&[u8]>::new();
+    while let Some(t) = pending.pop() {
+        match t {
+            ScSpecTypeDef::Option(o) => pending.push(&o.value_type),
+            ScSpecTypeDef::Result(r) => pending.extend([&*r.ok_type, &*r.error_type]),
+            ScSpecTypeDef::Vec(v) => pending.push(&v.element_type),
+            ScSpecTypeDef::Map(m) => pending.extend([&*m.key_type, &*m.value_type]),
+            ScSpecTypeDef::Tuple(t) => pending.extend(t.value_types.iter()),
+            ScSpecTypeDef::Udt(u) if !udts.contains(&u.name.as_slice()) => {
+                udts.push(&u.name);
+                match find_udt(entries, &u.name) {
+                    Some(ScSpecUdt::Struct(s)) => pending.extend(s.fields.iter().map(|f| &f.type_)),
+                    Some(ScSpecUdt::Union(u)) => {
+                        pending.extend(u.cases.iter().flat_map(|c| union_case(c).1));
+                    }
+                    Some(ScSpecUdt::Enum(_) | ScSpecUdt::ErrorEnum(_)) | None => {}
+                }
+            }
+            _ => {}
+        }
+    }
+    udts
+}
+
+fn compare_functions(changes: &mut Vec<ScSpecChange>, old: &[ScSpecEntry], new: &[ScSpecEntry]) {
+    let removed = functions(old)
+        .filter(|o| !functions(new).any(|n| n.name == o.name))
+        .collect::<Vec<_>>();
+    let mut added = functions(new)
+        .filter(|n| !functions(old).any(|o| o.name == n.name))
+        .collect::<Vec<_>>();
+
+    for o in functions(old) {
+        if removed.iter().any(|r| r.name == o.name) {
+            let trivial = o.inputs.is_empty() && o.outputs.is_empty();
+            let mut candidates = added
+                .iter()
+                .enumerate()
+                .filter(|(_, a)| same_signature(o, a))
+                .map(|(i, _)| i);
+            match (candidates.next(), candidates.next()) {
+                (Some(i), None) if !trivial => {
+                    let a = added.remove(i);
+                    changes.push(ScSpecChange::FunctionRenamed {
+                        old_function: name(&o.name),
+                        new_function: name(&a.name),
+                    });
+                }
+                _ => changes.push(ScSpecChange::FunctionRemoved {
+                    function: name(&o.name),
+                }),
+            }
+        } else if let Some(n) = functions(new).find(|n| n.name == o.name) {
+            compare_function(changes, o, n);
+        }
+    }
+    changes.extend(added.into_iter().map(|a| ScSpecChange::FunctionAdded {
+        function: name(&a.name),
+    }));
+}
+
+fn compare_function(
+    changes: &mut Vec<ScSpecChange>,
+    old: &ScSpecFunctionV0,
+    new: &ScSpecFunctionV0,
+) {
+    let function = name(&old.name);
+    for i in 0..old.inputs.len().max(new.inputs.len()) {
+        match (old.inputs.get(i), new.inputs.get(i)) {
+            (Some(o), Some(n)) => {
+                if o.name != n.name {
+                    changes.push(ScSpecChange::FunctionInputRenamed {
+                        function: function.clone(),
+                        old_input: name(&o.name),
+                        new_input: name(&n.name),
+                    });
+                }
+                if o.type_ != n.type_ {
+                    changes.push(ScSpecChange::FunctionInputTypeChanged {
+                        function: function.clone(),
+                        input: name(&n.name),
+                        old_type: o.type_.clone(),
+                        new_type: n.type_.clone(),
+                    });
+                }
+            }
+            (Some(o), None) => changes.push(ScSpecChange::FunctionInputRemoved {
+                function: function.clone(),
+                input: name(&o.name),
+            }),
+            (None, Some(n)) => changes.push(ScSpecChange::FunctionInputAdded {
+                function: function.clone(),
+                input: name(&n.name),
+            }),
+            (None, None) => {}
+        }
+    }
+    if old.outputs != new.outputs {
+        changes.push(ScSpecChange::FunctionOutputChanged {
+            function,
+            old_type: old.outputs.first().cloned(),
+            new_type: new.outputs.first().cloned(),
+        });
+    }
+}
+
+fn compare_udts(changes: &mut Vec<ScSpecChange>, old: &[ScSpecEntry], new: &[ScSpecEntry]) {
+    let outputs = output_udts(new);
+    for o in old {
+        let Some(type_name) = udt_name(o) else {
+            continue;
+        };
+        let Some(n) = new.iter().find(|n| udt_name(n) == Some(type_name)) else {
+            changes.push(ScSpecChange::TypeRemoved {
+                type_name: name(type_name),
+            });
+            continue;
+        };
+        match (o, n) {
+            (ScSpecEntry::UdtStructV0(o), ScSpecEntry::UdtStructV0(n)) => {
+                compare_struct(changes, o, n);
+            }
+            (ScSpecEntry::UdtUnionV0(o), ScSpecEntry::UdtUnionV0(n)) => {
+                compare_union(changes, o, n, outputs.contains(&type_name));
+            }
+            (ScSpecEntry::UdtEnumV0(o), ScSpecEntry::UdtEnumV0(n)) => {
+                compare_enum(changes, o, n, outputs.contains(&type_name));
+            }
+            (ScSpecEntry::UdtErrorEnumV0(o), ScSpecEntry::UdtErrorEnumV0(n)) => {
+                compare_error_enum(changes, o, n);
+            }
+            _ => changes.push(ScSpecChange::TypeKindChanged {
+                type_name: name(type_name),
+            }),
+        }
+    }
+    for n in new {
+        if let Some(type_name) = udt_name(n) {
+            if !old.iter().any(|o| udt_name(o) == Some(type_name)) {
+                changes.push(ScSpecChange::TypeAdded {
+                    type_name: name(type_name),
+                });
+            }
+        }
+    }
+}
+
+fn compare_struct(
+    changes: &mut Vec<ScSpecChange>,
+    old: &ScSpecUdtStructV0,
+    new: &ScSpecUdtStructV0,
+) {
+    let type_name = name(&old.name);
+    for o in &*old.fields {
+        match new.fields.iter().find(|n| n.name == o.name) {
+            Some(n) if n.type_ != o.type_ => changes.push(ScSpecChange::StructFieldTypeChanged {
+                type_name: type_name.clone(),
+                field: name(&o.name),
+                old_type: o.type_.clone(),
+                new_type: n.type_.clone(),
+            }),
+            Some(_) => {}
+            None => changes.push(ScSpecChange::StructFieldRemoved {
+                type_name: type_name.clone(),
+                field: name(&o.name),
+            }),
+        }
+    }
+    for n in &*new.fields {
+        if !old.fields.iter().any(|o| o.name == n.name) {
+            changes.push(ScSpecChange::StructFieldAdded {
+                type_name: type_name.clone(),
+                field: name(&n.name),
+            });
+        }
+    }
+}
+
+fn union_case(c: &ScSpecUdtUnionCaseV0) -> (&[u8], &[ScSpecTypeDef]) {
+    match c {
+        ScSpecUdtUnionCaseV0::VoidV0(c) => (&c.name, &[]),
+        ScSpecUdtUnionCaseV0::TupleV0(c) => (&c.name, &c.type_),
+    }
+}
+
+fn compare_union(
+    changes: &mut Vec<ScSpecChange>,
+    old: &ScSpecUdtUnionV0,
+    new: &ScSpecUdtUnionV0,
+    output: bool,
+) {
+    let type_name = name(&old.name);
+    for o in &*old.cases {
+        let (case, types) = union_case(o);
+        match new.cases.iter().find(|n| union_case(n).0 == case) {
+            // A void case differs from a tuple case with no values, so the
+            // kinds of the cases are compared as well as their types.
+            Some(n) if n.discriminant() != o.discriminant() || union_case(n).1 != types => {
+                changes.push(ScSpecChange::UnionCaseTypesChanged {
+                    type_name: type_name.clone(),
+                    case: name(case),
+                });
+            }
+            Some(_) => {}
+            None => changes.push(ScSpecChange::UnionCaseRemoved {
+                type_name: type_name.clone(),
+                case: name(case),
+            }),
+        }
+    }
+    for n in &*new.cases {
+        let (case, _) = union_case(n);
+        if !old.cases.iter().any(|o| union_case(o).0 == case) {
+            changes.push(ScSpecChange::UnionCaseAdded {
+                type_name: type_name.clone(),
+                case: name(case),
+                output,
+            });
+        }
+    }
+}
+
+fn compare_enum(
+    changes: &mut Vec<ScSpecChange>,
+    old: &ScSpecUdtEnumV0,
+    new: &ScSpecUdtEnumV0,
+    output: bool,
+) {
+    let type_name = name(&old.name);
+    for o in &*old.cases {
+        match new.cases.iter().find(|n| n.name == o.name) {
+            Some(n) if n.value != o.value => changes.push(ScSpecChange::EnumCaseValueChanged {
+                type_name: type_name.clone(),
+                case: name(&o.name),
+                old_value: o.value,
+                new_value: n.value,
+            }),
+            Some(_) => {}
+            None => changes.push(ScSpecChange::EnumCaseRemoved {
+                type_name: type_name.clone(),
+                case: name(&o.name),
+            }),
+        }
+    }
+    for n in &*new.cases {
+        if !old.cases.iter().any(|o| o.name == n.name) {
+            changes.push(ScSpecChange::EnumCaseAdded {
+                type_name: type_name.clone(),
+                case: name(&n.name),
+                output,
+            });
+        }
+    }
+}
+
+fn compare_error_enum(
+    changes: &mut Vec<ScSpecChange>,
+    old: &ScSpecUdtErrorEnumV0,
+    new: &ScSpecUdtErrorEnumV0,
+) {
+    let type_name = name(&old.name);
+    for o in &*old.cases {
+        match new.cases.iter().find(|n| n.name == o.name) {
+            Some(n) if n.value != o.value => changes.push(ScSpecChange::ErrorCodeChanged {
+                type_name: type_name.clone(),
+                case: name(&o.name),
+                old_code: o.value,
+                new_code: n.value,
+            }),
+            Some(_) => {}
+            None => changes.push(ScSpecChange::ErrorCaseRemoved {
+                type_name: type_name.clone(),
+                case: name(&o.name),
+            }),
+        }
+    }
+    for n in &*new.cases {
+        if !old.cases.iter().any(|o| o.name == n.name) {
+            changes.push(ScSpecChange::ErrorCaseAdded {
+                type_name: type_name.clone(),
+                case: name(&n.name),
+            });
+        }
+    }
+}
+
+fn compare_events(changes: &mut Vec<ScSpecChange>, old: &[ScSpecEntry], new: &[ScSpecEntry]) {
+    for o in events(old) {
+        let event = name(&o.name);
+        let Some(n) = events(new).find(|n| n.name == o.name) else {
+            changes.push(ScSpecChange::EventRemoved { event });
+            continue;
+        };
+        if o.prefix_topics != n.prefix_topics {
+            changes.push(ScSpecChange::EventPrefixTopicsChanged {
+                event: event.clone(),
+            });
+        }
+        if o.data_format != n.data_format {
+            changes.push(ScSpecChange::EventDataFormatChanged {
+                event: event.clone(),
+            });
+        }
+        for i in 0..o.params.len().max(n.params.len()) {
+            match (o.params.get(i), n.params.get(i)) {
+                (Some(op), Some(np)) => {
+                    if op.name != np.name || op.type_ != np.type_ || op.location != np.location {
+                        changes.push(ScSpecChange::EventParamChanged {
+                            event: event.clone(),
+                            param: name(&np.name),
+                        });
+                    }
+                }
+                (Some(op), None) => changes.push(ScSpecChange::EventParamRemoved {
+                    event: event.clone(),
+                    param: name(&op.name),
+                }),
+                (None, Some(np)) => changes.push(ScSpecChange::EventParamAdded {
+                    event: event.clone(),
+                    param: name(&np.name),
+                }),
+                (None, None) => {}
+            }
+        }
+    }
+    for n in events(new) {
+        if !events(old).any(|o| o.name == n.name) {
+            changes.push(ScSpecChange::EventAdded {
+                event: name(&n.name),
+            });
+        }
+    }
+}
@@ -0,0 +1,223 @@
+#![cfg(feature = "alloc")]
+use super::{
+    ContractEvent, ContractEventBody, ScMap, ScSpecEntry, ScSpecEventDataFormat,
+    ScSpecEventParamLocationV0, ScSpecEventParamV0, ScSpecEventV0, ScSpecValidationError, ScVal,
+};
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+use core::fmt;
+
+/// A contract event decoded using the event definitions in a contract spec.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodedContractEvent {
+    /// The name of the event in the contract spec.
+    pub name: String,
+    /// The event's params, by name, in the order they are defined in the
+    /// contract spec.
+    pub params: Vec<(String, ScVal)>,
+}
+
+/// Error returned when decoding a contract event using a contract spec
+/// fails.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScSpecEventError {
+    /// No event in the contract spec has prefix topics matching the event's
+    /// topics.
+    UnknownEvent,
+    /// The event has a different number of topics than the matching event
+    /// definition.
+    TopicCountMismatch {
+        event: String,
+        expected: usize,
+        actual: usize,
+    },
+    /// The event's data is not in the data format of the matching event
+    /// definition.
+    DataFormatMismatch {
+        event: String,
+        expected: ScSpecEventDataFormat,
+    },
+    /// The data of the event has no value for a data param.
+    MissingParam { event: String, param: String },
+    /// A param's value does not conform to the param's type.
+    InvalidParam {
+        event: String,
+        param: String,
+        error: ScSpecValidationError,
+    },
+}
+
+impl fmt::Display for ScSpecEventError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownEvent => write!(f, "event does not match any event in the spec"),
+            Self::TopicCountMismatch {
+                event,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "event {event} expects {expected} topics, but has {actual}"
+            ),
+            Self::DataFormatMismatch { event, expected } => write!(
+                f,
+                "event {event} expects data in the {} format",
+                expected.name()
+            ),
+            Self::MissingParam { event, param } => {
+                write!(f, "event {event} data is missing param {param}")
+            }
+            Self::InvalidParam {
+                event,
+                param,
+                error,
+            } => write!(f, "event {event} param {param} is invalid: {error}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ScSpecEventError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidParam { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl ContractEvent {
+    /// Decodes the event using the event definitions in the contract spec
+    /// entries.
+    ///
+    /// An event matches a definition if its topics begin with the
+    /// definition's prefix topics. The remaining topics are the params
+    /// located in the topic list, and the data holds the params located in
+    /// the data, as a single value, a vec in order, or a map keyed by param
+    /// name. Each param's value must conform to the param's type.
+    ///
+    /// If more than one definition matches the prefix topics, the first that
+    /// the event decodes with is returned.
+    ///
+    /// # Errors
+    ///
+    /// If no definition has matching prefix topics, or if the event does not
+    /// decode with any definition that does, in which case the error for the
+    /// last such definition is returned.
+    pub fn decode_with_spec(
+        &self,
+        entries: &[ScSpecEntry],
+    ) -> Result<DecodedContractEvent, ScSpecEventError> {
+        let ContractEventBody::V0(body) = &self.body;
+        let mut result = Err(ScSpecEventError::UnknownEvent);
+        for e in entries {
+            let ScSpecEntry::EventV0(event) = e else {
+                continue;
+            };
+            let prefix_matches = event.prefix_topics.len() <= body.topics.len()
+                && event
+                    .prefix_topics
+                    .iter()
+                    .zip(body.topics.iter())
+                    .all(|(p, t)| matches!(t, ScVal::Symbol(s) if s == p));
+            if !prefix_matches {
+                continue;
+            }
+            result = decode(
+                event,
+                &body.topics[event.prefix_topics.len()..],
+                &body.data,
+                entries,
+            )
+            .map(|params| DecodedContractEvent {
+                name: event.name.to_utf8_string_lossy(),
+                params,
+            });
+            if result.is_ok() {
+                break;
+            }
+        }
+        result
+    }
+}
+
+/// Returns the value in the map with the symbol key.
+fn map_value<'a>(map: &'a ScMap, key: &[u8]) -> Option<&'a ScVal> {
+    map.iter()
+        .find(|e| matches!(&e.key, ScVal::Symbol(k) if k.as_slice() == key))
+        .map(|e| &e.val)
+}
+
+/// Decodes the params of the event from the topics following the prefix
+/// topics, and the data.
+fn decode(
+    event: &ScSpecEventV0,
+    topics: &[ScVal],
+    data: &ScVal,
+    entries: &[ScSpecEntry],
+) -> Result<Vec<(String, ScVal)>, ScSpecEventError> {
+    let name = event.name.to_utf8_string_lossy();
+    let (topic_params, data_params): (Vec<&ScSpecEventParamV0>, Vec<&ScSpecEventParamV0>) = event
+        .params
+        .iter()
+        .partition(|p| p.location == ScSpecEventParamLocationV0::TopicList);
+    if topics.len() != topic_params.len() {
+        return Err(ScSpecEventError::TopicCountMismatch {
+            event: name,
+            expected: event.prefix_topics.len() + topic_params.len(),
+            actual: event.prefix_topics.len() + topics.len(),
+        });
+    }
+
+    let format_mismatch = || ScSpecEventError::DataFormatMismatch {
+        event: name.clone(),
+        expected: event.data_format,
+    };
+    let data_values: Vec<&ScVal> = match (event.data_format, data) {
+        (ScSpecEventDataFormat::SingleValue, ScVal::Void) if data_params.is_empty() => Vec::new(),
+        (ScSpecEventDataFormat::SingleValue, _) if data_params.len() == 1 => [data].into(),
+        (ScSpecEventDataFormat::Vec, ScVal::Vec(Some(vec))) if vec.len() == data_params.len() => {
+            vec.iter().collect()
+        }
+        (ScSpecEventDataFormat::Map, ScVal::Map(Some(map))) if map.len() == data_params.len() => {
+            data_params
+                .iter()
+                .map(|p| {
+                    map_value(map, &p.name).ok_or_else(|| ScSpecEventError::MissingParam {
+                        event: name.clone(),
+                        param: p.name.to_utf8_string_lossy(),
+                    })
+                })
+                .collect::<Result<_, _>>()?
+        }
+        _ => return Err(format_mismatch()),
+    };
+
+    // Return the params in the order they are defined.
+    let mut topic_values = topics.iter();
+    let mut data_values = data_values.into_iter();
+    event
+        .params
+        .iter()
+        .map(|p| {
+            let value = match p.location {
+                ScSpecEventParamLocationV0::TopicList => topic_values.next(),
+                ScSpecEventParamLocationV0::Data => data_values.next(),
+            }
+            .ok_or_else(format_mismatch)?;
+            let param = p.name.to_utf8_string_lossy();
+            value.validate_against(&p.type_, entries).map_err(|error| {
+                ScSpecEventError::InvalidParam {
+                    event: name.clone(),
+                    param: param.clone(),
+                    error,
+                }
+            })?;
+            Ok((param, value.clone()))
+        })
+        .collect()
+}
@@ -0,0 +1,329 @@
+#![cfg(feature = "alloc")]
+#[cfg(feature = "serde_json")]
+use super::ScSpecJsonError;
+use super::{
+    HostFunction, InvokeContractArgs, InvokeHostFunctionOp, ScAddress, ScSpecEntry,
+    ScSpecFunctionV0, ScSpecValidationError, ScVal, VecM,
+};
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+use core::fmt;
+
+/// A contract invocation decoded using the function definitions in a
+/// contract spec.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodedContractInvocation {
+    pub contract_address: ScAddress,
+    /// The name of the function in the contract spec.
+    pub function: String,
+    /// The function's arguments, by name, in the order they are defined in
+    /// the contract spec.
+    pub args: Vec<(String, ScVal)>,
+}
+
+/// Error returned when building or decoding a contract invocation using a
+/// contract spec fails.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScSpecInvokeError {
+    /// The host function is not an invocation of a contract function.
+    NotInvokeContract,
+    /// No function in the contract spec has the name of the function invoked.
+    UnknownFunction(String),
+    /// The invocation has a different number of arguments than the function
+    /// has inputs.
+    ArgCountMismatch {
+        function: String,
+        expected: usize,
+        actual: usize,
+    },
+    /// No argument was given for an input of the function.
+    MissingArg { function: String, arg: String },
+    /// An argument was given that is not an input of the function.
+    UnknownArg { function: String, arg: String },
+    /// More than one argument was given for an input of the function.
+    DuplicateArg { function: String, arg: String },
+    /// An argument's value does not conform to the input's type.
+    InvalidArg {
+        function: String,
+        arg: String,
+        error: ScSpecValidationError,
+    },
+    /// An argument's JSON could not be converted to the input's type.
+    #[cfg(feature = "serde_json")]
+    InvalidJsonArg {
+        function: String,
+        arg: String,
+        error: ScSpecJsonError,
+    },
+}
+
+impl fmt::Display for ScSpecInvokeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NotInvokeContract => write!(f, "host function is not a contract invocation"),
+            Self::UnknownFunction(function) => {
+                write!(f, "function {function} does not exist in the spec")
+            }
+            Self::ArgCountMismatch {
+                function,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "function {function} expects {expected} args, but has {actual}"
+            ),
+            Self::MissingArg { function, arg } => {
+                write!(f, "function {function} is missing arg {arg}")
+            }
+            Self::UnknownArg { function, arg } => {
+                write!(f, "function {function} has no arg {arg}")
+            }
+            Self::DuplicateArg { function, arg } => {
+                write!(f, "function {function} arg {arg} is given more than once")
+            }
+            Self::InvalidArg {
+                function,
+                arg,
+                error,
+            } => write!(f, "function {function} arg {arg} is invalid: {error}"),
+            #[cfg(feature = "serde_json")]
+            Self::InvalidJsonArg {
+                function,
+                arg,
+                error,
+            } => write!(f, "function {function} arg {arg} is invalid: {error}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ScSpecInvokeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidArg { error, .. } => Some(error),
+            #[cfg(feature = "serde_json")]
+            Self::InvalidJsonArg { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl InvokeContractArgs {
+    /// Builds the arguments for invoking the function on the contract, from
+    /// arguments given by input name.
+    ///
+    /// The arguments are placed in the order of the function's inputs, and
+    /// each argument's value must conform to the input's type, using the
+    /// contract spec entries to resolve user defined types.
+    ///
+    /// # Errors
+    ///
+    /// If an input has no argument, an argument has no input, or an
+    /// argument's value does not conform to the input's type.
+    pub fn from_spec_args<S: AsRef<str>>(
+        contract_address: ScAddress,
+        function: &ScSpecFunctionV0,
+        args: &[(S, ScVal)],
+        entries: &[ScSpecEntry],
+    ) -> Result<Self, ScSpecInvokeError> {
+        let values = order_args(function, args)?;
+        let function_name = function.name.to_utf8_string_lossy();
+        for (input, value) in function.inputs.iter().zip(&values) {
+            value
+                .validate_against(&input.type_, entries)
+                .map_err(|error| ScSpecInvokeError::InvalidArg {
+                    function: function_name.clone(),
+                    arg: input.name.to_utf8_string_lossy(),
+                    error,
+                })?;
+        }
+        build(
+            contract_address,
+            function,
+            values.into_iter().cloned().collect(),
+        )
+    }
+
+    /// Builds the arguments for invoking the function on the contract, from
+    /// a JSON object of arguments keyed by input name.
+    ///
+    /// Each argument is converted from JSON to the input's type as described
+    /// by [`ScVal::from_json_with_spec`], and placed in the order of the
+    /// function's inputs.
+    ///
+    /// # Errors
+    ///
+    /// If an input has no argument, an argument has no input, or an
+    /// argument's JSON cannot be converted to the input's type.
+    #[cfg(feature = "serde_json")]
+    pub fn from_spec_json_args(
+        contract_address: ScAddress,
+        function: &ScSpecFunctionV0,
+        args: &serde_json::Map<String, serde_json::Value>,
+        entries: &[ScSpecEntry],
+    ) -> Result<Self, ScSpecInvokeError> {
+        let args = args.iter().collect::<Vec<_>>();
+        let values = order_args(function, &args)?;
+        let function_name = function.name.to_utf8_string_lossy();
+        let values = function
+            .inputs
+            .iter()
+            .zip(values)
+            .map(|(input, json)| {
+                ScVal::from_json_with_spec(json, &input.type_, entries).map_err(|error| {
+                    ScSpecInvokeError::InvalidJsonArg {
+                        function: function_name.clone(),
+                        arg: input.name.to_utf8_string_lossy(),
+                        error,
+                    }
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        build(contract_address, function, values)
+    }
+
+    /// Decodes the arguments using the definition of the invoked function in
+    /// the contract spec entries.
+    ///
+    /// # Errors
+    ///
+    /// If the function does not exist in the contract spec, the number of
+    /// arguments differs from the number of inputs, or an argument's value
+    /// does not conform to the input's type.
+    pub fn decode_with_spec(
+        &self,
+        entries: &[ScSpecEntry],
+    ) -> Result<DecodedContractInvocation, ScSpecInvokeError> {
+        let function_name = self.function_name.to_utf8_string_lossy();
+        let function = entries
+            .iter()
+            .find_map(|e| match e {
+                ScSpecEntry::FunctionV0(f) if f.name == self.function_name => Some(f),
+                _ => None,
+            })
+            .ok_or_else(|| ScSpecInvokeError::UnknownFunction(function_name.clone()))?;
+        if self.args.len() != function.inputs.len() {
+            return Err(ScSpecInvokeError::ArgCountMismatch {
+                function: function_name,
+                expected: function.inputs.len(),
+                actual: self.args.len(),
+            });
+        }
+        let args = function
+            .inputs
+            .iter()
+            .zip(self.args.iter())
+            .map(|(input, value)| {
+                let arg = input.name.to_utf8_string_lossy();
+                value
+                    .validate_against(&input.type_, entries)
+                    .map_err(|error| ScSpecInvokeError::InvalidArg {
+                        function: function_name.clone(),
+                        arg: arg.clone(),
+                        error,
+                    })?;
+                Ok((arg, value.clone()))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(DecodedContractInvocation {
+            contract_address: self.contract_address.clone(),
+            function: function_name,
+            args,
+        })
+    }
+}
+
+impl InvokeHostFunctionOp {
+    /// Decodes the contract invocation using the definition of the invoked
+    /// function in the contract spec entries.
+    ///
+    /// # Errors
+    ///
+    /// If the host function is not a contract invocation, or if the
+    /// invocation does not decode as described by
+    /// [`InvokeContractArgs::decode_with_spec`].
+    pub fn decode_with_spec(
+        &self,
+        entries: &[ScSpecEntry],
+    ) -> Result<DecodedContractInvocation, ScSpecInvokeError> {
+        match &self.host_function {
+            HostFunction::InvokeContract(args) => args.decode_with_spec(entries),
+            _ => Err(ScSpecInvokeError::NotInvokeContract),
+        }
+    }
+}
+
+impl From<InvokeContractArgs> for InvokeHostFunctionOp {
+    /// Creates an operation that invokes the contract, without any
+    /// authorizations.
+    fn from(args: InvokeContractArgs) -> Self {
+        InvokeHostFunctionOp {
+            host_function: HostFunction::InvokeContract(args),
+            auth: VecM::default(),
+        }
+    }
+}
+
+/// Returns the values of the named arguments in the order of the function's
+/// inputs.
+fn order_args<'a, S: AsRef<str>, V>(
+    function: &ScSpecFunctionV0,
+    args: &'a [(S, V)],
+) -> Result<Vec<&'a V>, ScSpecInvokeError> {
+    let function_name = function.name.to_utf8_string_lossy();
+    for (i, (name, _)) in args.iter().enumerate() {
+        let name = name.as_ref();
+        if !function
+            .inputs
+            .iter()
+            .any(|input| input.name.as_slice() == name.as_bytes())
+        {
+            return Err(ScSpecInvokeError::UnknownArg {
+                function: function_name,
+                arg: name.into(),
+            });
+        }
+        if args[..i].iter().any(|(n, _)| n.as_ref() == name) {
+            return Err(ScSpecInvokeError::DuplicateArg {
+                function: function_name,
+                arg: name.into(),
+            });
+        }
+    }
+    function
+        .inputs
+        .iter()
+        .map(|input| {
+            args.iter()
+                .find(|(name, _)| name.as_ref().as_bytes() == input.name.as_slice())
+                .map(|(_, value)| value)
+                .ok_or_else(|| ScSpecInvokeError::MissingArg {
+                    function: function_name.clone(),
+                    arg: input.name.to_utf8_string_lossy(),
+                })
+        })
+        .collect()
+}
+
+fn build(
+    contract_address: ScAddress,
+    function: &ScSpecFunctionV0,
+    args: Vec<ScVal>,
+) -> Result<InvokeContractArgs, ScSpecInvokeError> {
+    let actual = args.len();
+    Ok(InvokeContractArgs {
+        contract_address,
+        function_name: function.name.clone(),
+        args: args
+            .try_into()
+            .map_err(|_| ScSpecInvokeError::ArgCountMismatch {
+                function: function.name.to_utf8_string_lossy(),
+                expected: function.inputs.len(),
+                actual,
+            })?,
+    })
+}
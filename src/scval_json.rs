@@ -0,0 +1,218 @@
+#![cfg(feature = "serde_json")]
+use super::{
+    Duration, Error, Int128Parts, Int256Parts, ScAddress, ScBytes, ScMap, ScString, ScSymbol,
+    ScVal, ScValType, ScVec, TimePoint, UInt128Parts, UInt256Parts, Validate,
+};
+
+use core::str::FromStr;
+use serde_json::Value;
+
+impl ScVal {
+    /// Converts the `ScVal` into natural JSON, without type information.
+    ///
+    /// The conversion is lossy, because different types convert to the same
+    /// JSON. The JSON for each type is:
+    ///
+    /// - `Bool`: a boolean. `Void`: null.
+    /// - `U32`, `I32`, `U64`, `I64`, `Timepoint`, and `Duration`: a number.
+    /// - `U128`, `I128`, `U256`, and `I256`: a string containing the decimal
+    ///   number.
+    /// - `Bytes`: a hex string.
+    /// - `String` and `Symbol`: a string, with invalid UTF-8 replaced.
+    /// - `Vec`: an array. `Map`: an object if every key is a `Symbol` or
+    ///   `String`, otherwise an array of `[key, value]` arrays. An absent
+    ///   `Vec` or `Map` is null.
+    /// - `Address`: the strkey.
+    /// - Any other type: a string containing the value in the `ScVal` text
+    ///   notation, e.g. `"error:Contract:1"`.
+    #[must_use]
+    pub fn to_json_value(&self) -> Value {
+        match self {
+            ScVal::Bool(v) => Value::Bool(*v),
+            ScVal::Void | ScVal::Vec(None) | ScVal::Map(None) => Value::Null,
+            ScVal::U32(v) => (*v).into(),
+            ScVal::I32(v) => (*v).into(),
+            ScVal::U64(v) | ScVal::Timepoint(TimePoint(v)) | ScVal::Duration(Duration(v)) => {
+                (*v).into()
+            }
+            ScVal::I64(v) => (*v).into(),
+            ScVal::U128(v) => v.to_string().into(),
+            ScVal::I128(v) => v.to_string().into(),
+            ScVal::U256(v) => v.to_string().into(),
+            ScVal::I256(v) => v.to_string().into(),
+            ScVal::Bytes(v) => hex::encode(v).into(),
+            ScVal::String(ScString(s)) => s.to_utf8_string_lossy().into(),
+            ScVal::Symbol(ScSymbol(s)) => s.to_utf8_string_lossy().into(),
+            ScVal::Vec(Some(vec)) => vec.iter().map(ScVal::to_json_value).collect(),
+            ScVal::Map(Some(map)) => {
+                let key_string = |key: &ScVal| match key {
+                    ScVal::String(ScString(s)) => Some(s.to_utf8_string_lossy()),
+                    ScVal::Symbol(ScSymbol(s)) => Some(s.to_utf8_string_lossy()),
+                    _ => None,
+                };
+                if map.iter().all(|e| key_string(&e.key).is_some()) {
+                    map.iter()
+                        .filter_map(|e| Some((key_string(&e.key)?, e.val.to_json_value())))
+                        .collect::<serde_json::Map<_, _>>()
+                        .into()
+                } else {
+                    map.iter()
+                        .map(|e| {
+                            Value::Array([e.key.to_json_value(), e.val.to_json_value()].into())
+                        })
+                        .collect()
+                }
+            }
+            ScVal::Address(a) => a.to_string().into(),
+            _ => self.to_string().into(),
+        }
+    }
+
+    /// Converts natural JSON into a `ScVal`, of the type if a type hint is
+    /// given, otherwise of a type inferred from the JSON.
+    ///
+    /// The JSON accepted for each type hint is:
+    ///
+    /// - `Bool`: a boolean. `Void`: null.
+    /// - Integers, `Timepoint`, and `Duration`: a number, or a string
+    ///   containing a decimal number.
+    /// - `Bytes`: a hex string.
+    /// - `String` and `Symbol`: a string.
+    /// - `Vec`: an array. `Map`: an object, or an array of `[key, value]`
+    ///   arrays.
+    /// - `Address`: a strkey.
+    /// - Any other type: a string containing the value in the `ScVal` text
+    ///   notation.
+    ///
+    /// Without a type hint, and for the elements of a `Vec` and the keys and
+    /// values of a `Map`, the type is inferred:
+    ///
+    /// - null is `Void`, and booleans are `Bool`.
+    /// - Numbers are `U32` or `I32` if they fit, otherwise `U64` or `I64`.
+    /// - Strings are `Address` if they are a strkey of an address, otherwise
+    ///   `String`.
+    /// - Arrays are `Vec`.
+    /// - Objects are `Map`, with `Symbol` keys.
+    ///
+    /// Maps are sorted by key.
+    ///
+    /// # Errors
+    ///
+    /// If the JSON is not accepted for the type, a number is not an integer
+    /// or out of range, a map has duplicate keys, or a symbol contains
+    /// characters that are not permitted.
+    pub fn from_json_value(json: &Value, type_hint: Option<ScValType>) -> Result<ScVal, Error> {
+        let v = match type_hint {
+            Some(type_) => typed(json, type_)?,
+            None => inferred(json)?,
+        };
+        v.validate()?;
+        Ok(v)
+    }
+}
+
+fn inferred(json: &Value) -> Result<ScVal, Error> {
+    let v = match json {
+        Value::Null => ScVal::Void,
+        Value::Bool(b) => ScVal::Bool(*b),
+        Value::Number(n) => {
+            if let Some(u) = n.as_u64() {
+                u32::try_from(u).map_or(ScVal::U64(u), ScVal::U32)
+            } else if let Some(i) = n.as_i64() {
+                i32::try_from(i).map_or(ScVal::I64(i), ScVal::I32)
+            } else {
+                return Err(Error::Invalid);
+            }
+        }
+        Value::String(s) => ScAddress::from_str(s).map_or_else(
+            |_| s.as_str().try_into().map(|s| ScVal::String(ScString(s))),
+            |a| Ok(ScVal::Address(a)),
+        )?,
+        Value::Array(items) => ScVal::Vec(Some(ScVec(
+            items
+                .iter()
+                .map(inferred)
+                .collect::<Result<Vec<_>, _>>()?
+                .try_into()?,
+        ))),
+        Value::Object(entries) => ScVal::Map(Some(ScMap::sorted_from(
+            entries
+                .iter()
+                .map(|(k, v)| {
+                    Ok((
+                        ScVal::Symbol(ScSymbol(k.as_str().try_into()?)),
+                        inferred(v)?,
+                    ))
+                })
+                .collect::<Result<Vec<_>, Error>>()?,
+        )?)),
+    };
+    Ok(v)
+}
+
+fn typed(json: &Value, type_: ScValType) -> Result<ScVal, Error> {
+    let v = match (type_, json) {
+        (ScValType::Bool, Value::Bool(b)) => ScVal::Bool(*b),
+        (ScValType::Void, Value::Null) => ScVal::Void,
+        (ScValType::U32, _) => ScVal::U32(integer(json)?),
+        (ScValType::I32, _) => ScVal::I32(integer(json)?),
+        (ScValType::U64, _) => ScVal::U64(integer(json)?),
+        (ScValType::I64, _) => ScVal::I64(integer(json)?),
+        (ScValType::Timepoint, _) => ScVal::Timepoint(TimePoint(integer(json)?)),
+        (ScValType::Duration, _) => ScVal::Duration(Duration(integer(json)?)),
+        (ScValType::U128, _) => ScVal::U128(integer::<UInt128Parts>(json)?),
+        (ScValType::I128, _) => ScVal::I128(integer::<Int128Parts>(json)?),
+        (ScValType::U256, _) => ScVal::U256(integer::<UInt256Parts>(json)?),
+        (ScValType::I256, _) => ScVal::I256(integer::<Int256Parts>(json)?),
+        (ScValType::Bytes, Value::String(s)) => ScVal::Bytes(ScBytes(
+            hex::decode(s).map_err(|_| Error::InvalidHex)?.try_into()?,
+        )),
+        (ScValType::String, Value::String(s)) => ScVal::String(ScString(s.as_str().try_into()?)),
+        (ScValType::Symbol, Value::String(s)) => ScVal::Symbol(ScSymbol(s.as_str().try_into()?)),
+        (ScValType::Vec, Value::Array(_)) | (ScValType::Map, Value::Object(_)) => inferred(json)?,
+        (ScValType::Map, Value::Array(pairs)) => ScVal::Map(Some(ScMap::sorted_from(
+            pairs
+                .iter()
+                .map(|pair| match pair.as_array().map(Vec::as_slice) {
+                    Some([k, v]) => Ok((inferred(k)?, inferred(v)?)),
+                    _ => Err(Error::Invalid),
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        )?)),
+        (ScValType::Address, Value::String(s)) => ScVal::Address(s.parse()?),
+        (_, Value::String(s)) if has_text_notation_only(type_) => {
+            let v = ScVal::from_str(s).map_err(|_| Error::Invalid)?;
+            if v.discriminant() != type_ {
+                return Err(Error::Invalid);
+            }
+            v
+        }
+        _ => return Err(Error::Invalid),
+    };
+    Ok(v)
+}
+
+/// Returns true if values of the type have no natural JSON, and are given in
+/// the `ScVal` text notation.
+fn has_text_notation_only(type_: ScValType) -> bool {
+    match type_ {
+        ScValType::Error
+        | ScValType::ContractInstance
+        | ScValType::LedgerKeyContractInstance
+        | ScValType::LedgerKeyNonce => true,
+        #[cfg(feature = "cap_0085_executable_ref")]
+        ScValType::ExecutableTag => true,
+        _ => false,
+    }
+}
+
+/// Converts a JSON number, or string containing a decimal number, to an
+/// integer.
+fn integer<T: FromStr>(json: &Value) -> Result<T, Error> {
+    match json {
+        Value::Number(n) if n.is_u64() || n.is_i64() => n.to_string().parse(),
+        Value::String(s) => s.parse(),
+        _ => return Err(Error::Invalid),
+    }
+    .map_err(|_| Error::Invalid)
+}
@@ -0,0 +1,148 @@
+#[cfg(feature = "cap_0085_executable_ref")]
+use super::ContractExecutableExternalRef;
+use super::{
+    AccountId, ClaimableBalanceId, ContractExecutable, ContractId, Hash, PoolId, PublicKey,
+    ScAddress, ScContractInstance, ScError, ScMapEntry, ScVal,
+};
+
+use core::cmp::Ordering;
+
+impl ScVal {
+    /// Compares the values using the same ordering as the Soroban host, that
+    /// is the ordering that the entries of a `ScMap` must be sorted in.
+    ///
+    /// Values of different types are ordered by the value of their
+    /// `ScValType`. Values of the same type are ordered:
+    ///
+    /// - Integers, including 128 and 256-bit integers, `Timepoint`, and
+    ///   `Duration`: numerically.
+    /// - `Error`: by the error type, then the code.
+    /// - `Bytes`, `String`, and `Symbol`: lexicographically by byte.
+    /// - `Vec`: lexicographically by element. `Map`: lexicographically by
+    ///   entry, comparing the key then the value.
+    /// - `Address`: by the address type, then the address. Muxed accounts
+    ///   are ordered by the id, then the ed25519 key.
+    /// - `ContractInstance`: by the executable, then the storage.
+    ///
+    /// An absent `Vec` or `Map`, which the host does not permit, is ordered
+    /// before any present `Vec` or `Map`.
+    #[must_use]
+    pub fn host_cmp(&self, other: &ScVal) -> Ordering {
+        match (self, other) {
+            (ScVal::Bool(a), ScVal::Bool(b)) => a.cmp(b),
+            (ScVal::Void, ScVal::Void)
+            | (ScVal::LedgerKeyContractInstance, ScVal::LedgerKeyContractInstance) => {
+                Ordering::Equal
+            }
+            (ScVal::Error(a), ScVal::Error(b)) => cmp_error(a, b),
+            (ScVal::U32(a), ScVal::U32(b)) => a.cmp(b),
+            (ScVal::I32(a), ScVal::I32(b)) => a.cmp(b),
+            (ScVal::U64(a), ScVal::U64(b)) => a.cmp(b),
+            (ScVal::I64(a), ScVal::I64(b)) => a.cmp(b),
+            (ScVal::Timepoint(a), ScVal::Timepoint(b)) => a.0.cmp(&b.0),
+            (ScVal::Duration(a), ScVal::Duration(b)) => a.0.cmp(&b.0),
+            // The high parts of the signed integers are signed, and the low
+            // parts unsigned, so comparing the parts from high to low
+            // compares the integers numerically.
+            (ScVal::U128(a), ScVal::U128(b)) => (a.hi, a.lo).cmp(&(b.hi, b.lo)),
+            (ScVal::I128(a), ScVal::I128(b)) => (a.hi, a.lo).cmp(&(b.hi, b.lo)),
+            (ScVal::U256(a), ScVal::U256(b)) => {
+                (a.hi_hi, a.hi_lo, a.lo_hi, a.lo_lo).cmp(&(b.hi_hi, b.hi_lo, b.lo_hi, b.lo_lo))
+            }
+            (ScVal::I256(a), ScVal::I256(b)) => {
+                (a.hi_hi, a.hi_lo, a.lo_hi, a.lo_lo).cmp(&(b.hi_hi, b.hi_lo, b.lo_hi, b.lo_lo))
+            }
+            (ScVal::Bytes(a), ScVal::Bytes(b)) => a.0[..].cmp(&b.0[..]),
+            (ScVal::String(a), ScVal::String(b)) => a.0[..].cmp(&b.0[..]),
+            (ScVal::Symbol(a), ScVal::Symbol(b)) => a.0[..].cmp(&b.0[..]),
+            (ScVal::Vec(a), ScVal::Vec(b)) => match (a, b) {
+                (Some(a), Some(b)) => cmp_slices(a, b, ScVal::host_cmp),
+                _ => a.is_some().cmp(&b.is_some()),
+            },
+            (ScVal::Map(a), ScVal::Map(b)) => match (a, b) {
+                (Some(a), Some(b)) => cmp_slices(a, b, cmp_map_entry),
+                _ => a.is_some().cmp(&b.is_some()),
+            },
+            (ScVal::Address(a), ScVal::Address(b)) => cmp_address(a, b),
+            (ScVal::ContractInstance(a), ScVal::ContractInstance(b)) => cmp_contract_instance(a, b),
+            (ScVal::LedgerKeyNonce(a), ScVal::LedgerKeyNonce(b)) => a.nonce.cmp(&b.nonce),
+            #[cfg(feature = "cap_0085_executable_ref")]
+            (ScVal::ExecutableTag(a), ScVal::ExecutableTag(b)) => a.0[..].cmp(&b.0[..]),
+            _ => (self.discriminant() as i32).cmp(&(other.discriminant() as i32)),
+        }
+    }
+}
+
+/// Compares the slices lexicographically, using the function to compare
+/// elements.
+fn cmp_slices<T>(a: &[T], b: &[T], cmp: impl Fn(&T, &T) -> Ordering) -> Ordering {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| cmp(a, b))
+        .find(|o| o.is_ne())
+        .unwrap_or_else(|| a.len().cmp(&b.len()))
+}
+
+fn cmp_map_entry(a: &ScMapEntry, b: &ScMapEntry) -> Ordering {
+    a.key.host_cmp(&b.key).then_with(|| a.val.host_cmp(&b.val))
+}
+
+fn cmp_error(a: &ScError, b: &ScError) -> Ordering {
+    let code = |e: &ScError| match e {
+        ScError::Contract(code) => *code,
+        ScError::WasmVm(code)
+        | ScError::Context(code)
+        | ScError::Storage(code)
+        | ScError::Object(code)
+        | ScError::Crypto(code)
+        | ScError::Events(code)
+        | ScError::Budget(code)
+        | ScError::Value(code)
+        | ScError::Auth(code) => *code as u32,
+    };
+    (a.discriminant() as i32, code(a)).cmp(&(b.discriminant() as i32, code(b)))
+}
+
+fn cmp_address(a: &ScAddress, b: &ScAddress) -> Ordering {
+    match (a, b) {
+        (
+            ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(a))),
+            ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(b))),
+        ) => a.0.cmp(&b.0),
+        (ScAddress::MuxedAccount(a), ScAddress::MuxedAccount(b)) => {
+            (a.id, a.ed25519.0).cmp(&(b.id, b.ed25519.0))
+        }
+        (ScAddress::Contract(ContractId(Hash(a))), ScAddress::Contract(ContractId(Hash(b))))
+        | (
+            ScAddress::ClaimableBalance(ClaimableBalanceId::ClaimableBalanceIdTypeV0(Hash(a))),
+            ScAddress::ClaimableBalance(ClaimableBalanceId::ClaimableBalanceIdTypeV0(Hash(b))),
+        )
+        | (ScAddress::LiquidityPool(PoolId(Hash(a))), ScAddress::LiquidityPool(PoolId(Hash(b)))) => {
+            a.cmp(b)
+        }
+        _ => (a.discriminant() as i32).cmp(&(b.discriminant() as i32)),
+    }
+}
+
+fn cmp_contract_instance(a: &ScContractInstance, b: &ScContractInstance) -> Ordering {
+    let executable = match (&a.executable, &b.executable) {
+        (ContractExecutable::Wasm(Hash(a)), ContractExecutable::Wasm(Hash(b))) => a.cmp(b),
+        (ContractExecutable::StellarAsset, ContractExecutable::StellarAsset) => Ordering::Equal,
+        #[cfg(feature = "cap_0085_executable_ref")]
+        (
+            ContractExecutable::ExternalRef(ContractExecutableExternalRef {
+                executable_owner: a_owner,
+                tag: a_tag,
+            }),
+            ContractExecutable::ExternalRef(ContractExecutableExternalRef {
+                executable_owner: b_owner,
+                tag: b_tag,
+            }),
+        ) => cmp_address(a_owner, b_owner).then_with(|| a_tag.0[..].cmp(&b_tag.0[..])),
+        (a, b) => (a.discriminant() as i32).cmp(&(b.discriminant() as i32)),
+    };
+    executable.then_with(|| match (&a.storage, &b.storage) {
+        (Some(a), Some(b)) => cmp_slices(a, b, cmp_map_entry),
+        (a, b) => a.is_some().cmp(&b.is_some()),
+    })
+}
@@ -0,0 +1,753 @@
+#![cfg(feature = "serde_json")]
+use super::{
+    scspec::{find_udt, ScSpecUdt},
+    Duration, Int128Parts, Int256Parts, ScAddress, ScBytes, ScError, ScMap, ScSpecEntry,
+    ScSpecTypeDef, ScSpecTypeMap, ScSpecTypeUdt, ScSpecUdtStructV0, ScSpecUdtUnionCaseV0,
+    ScSpecUdtUnionV0, ScString, ScSymbol, ScVal, ScVec, TimePoint, UInt128Parts, UInt256Parts,
+};
+
+use core::{fmt, str::FromStr};
+use serde_json::{Map, Number, Value};
+
+/// Error returned when converting between JSON and `ScVal` using a contract
+/// spec fails.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScSpecJsonError {
+    /// The location of the value that could not be converted, as a path from
+    /// the root value `$`, e.g. `$.to[0]`.
+    pub path: String,
+    pub kind: ScSpecJsonErrorKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScSpecJsonErrorKind {
+    /// The value is not of the expected type.
+    TypeMismatch { expected: String },
+    /// The value is an integer that is out of range of the expected type.
+    OutOfRange { expected: String },
+    /// The value is of the expected kind, but is not a valid value of the
+    /// expected type, such as malformed hex or an invalid address.
+    InvalidValue { expected: String },
+    /// The value has a different number of elements than the expected type.
+    LengthMismatch { expected: usize, actual: usize },
+    /// The contract spec has no user defined type with the name.
+    UnknownType(String),
+    /// The user defined type has no case with the name or value.
+    UnknownCase(String),
+    /// The struct has no field with the name.
+    UnknownField(String),
+    /// The struct field with the name is missing from the value.
+    MissingField(String),
+    /// The map contains the same key more than once.
+    DuplicateKey,
+}
+
+impl fmt::Display for ScSpecJsonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.kind)
+    }
+}
+
+impl fmt::Display for ScSpecJsonErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::TypeMismatch { expected } => write!(f, "expected {expected}"),
+            Self::OutOfRange { expected } => write!(f, "out of range for {expected}"),
+            Self::InvalidValue { expected } => write!(f, "invalid {expected}"),
+            Self::LengthMismatch { expected, actual } => {
+                write!(f, "expected length {expected}, got {actual}")
+            }
+            Self::UnknownType(name) => write!(f, "unknown type {name}"),
+            Self::UnknownCase(name) => write!(f, "unknown case {name}"),
+            Self::UnknownField(name) => write!(f, "unknown field {name}"),
+            Self::MissingField(name) => write!(f, "missing field {name}"),
+            Self::DuplicateKey => write!(f, "duplicate map key"),
+        }
+    }
+}
+
+impl std::error::Error for ScSpecJsonError {}
+
+impl ScVal {
+    /// Converts JSON into a `ScVal` of the type, using the contract spec
+    /// entries to resolve user defined types.
+    ///
+    /// The JSON for each type is:
+    ///
+    /// - `bool`: a boolean. `()`: null.
+    /// - Integers, `Timepoint`, and `Duration`: a number, or a string
+    ///   containing a decimal number, which is required for values that do not
+    ///   fit in a JSON number.
+    /// - `Bytes` and `BytesN<N>`: a hex string.
+    /// - `String` and `Symbol`: a string.
+    /// - `Address` and `MuxedAddress`: a strkey string.
+    /// - `Option<T>`: null for none, otherwise the JSON for `T`.
+    /// - `Result<T, E>`: an object with a single `ok` or `error` field.
+    /// - `Vec<T>` and tuples: an array.
+    /// - `Map<K, V>`: an object keyed by the JSON string for each key, or an
+    ///   array of `[key, value]` pairs.
+    /// - Structs: an object keyed by field name, or an array for tuple
+    ///   structs.
+    /// - Unions: a string naming a case without values, or an object with a
+    ///   single field naming the case containing an array of the case's
+    ///   values.
+    /// - Enums and error enums: the number of a case, or a string naming a
+    ///   case.
+    /// - `Val` and `Error`: the JSON of the `ScVal` or `ScError`.
+    ///
+    /// # Errors
+    ///
+    /// If the JSON is not a valid value of the type.
+    pub fn from_json_with_spec(
+        json: &Value,
+        type_def: &ScSpecTypeDef,
+        entries: &[ScSpecEntry],
+    ) -> Result<ScVal, ScSpecJsonError> {
+        Converter { entries }.to_scval(json, type_def, "$")
+    }
+
+    /// Converts the `ScVal` into JSON for the type, using the contract spec
+    /// entries to resolve user defined types.
+    ///
+    /// The JSON produced is accepted by [`ScVal::from_json_with_spec`].
+    /// Integers wider than 64 bits are produced as strings, maps are produced
+    /// as objects when every key is a string in JSON, and enums and error
+    /// enums are produced as numbers.
+    ///
+    /// # Errors
+    ///
+    /// If the `ScVal` is not a valid value of the type.
+    pub fn to_json_with_spec(
+        &self,
+        type_def: &ScSpecTypeDef,
+        entries: &[ScSpecEntry],
+    ) -> Result<Value, ScSpecJsonError> {
+        Converter { entries }.to_json(self, type_def, "$")
+    }
+}
+
+fn err(path: &str, kind: ScSpecJsonErrorKind) -> ScSpecJsonError {
+    ScSpecJsonError {
+        path: path.to_string(),
+        kind,
+    }
+}
+
+fn mismatch(path: &str, expected: impl fmt::Display) -> ScSpecJsonError {
+    err(
+        path,
+        ScSpecJsonErrorKind::TypeMismatch {
+            expected: expected.to_string(),
+        },
+    )
+}
+
+fn invalid(path: &str, expected: impl fmt::Display) -> ScSpecJsonError {
+    err(
+        path,
+        ScSpecJsonErrorKind::InvalidValue {
+            expected: expected.to_string(),
+        },
+    )
+}
+
+fn length_mismatch(path: &str, expected: usize, actual: usize) -> ScSpecJsonError {
+    err(
+        path,
+        ScSpecJsonErrorKind::LengthMismatch { expected, actual },
+    )
+}
+
+fn field_path(path: &str, name: &str) -> String {
+    format!("{path}.{name}")
+}
+
+fn index_path(path: &str, i: usize) -> String {
+    format!("{path}[{i}]")
+}
+
+fn symbol(name: &[u8], path: &str) -> Result<ScVal, ScSpecJsonError> {
+    let s = name.try_into().map_err(|_| invalid(path, "Symbol"))?;
+    Ok(ScVal::Symbol(ScSymbol(s)))
+}
+
+fn vec(items: Vec<ScVal>, path: &str, ty: &ScSpecTypeDef) -> Result<ScVal, ScSpecJsonError> {
+    let v = items.try_into().map_err(|_| invalid(path, ty))?;
+    Ok(ScVal::Vec(Some(ScVec(v))))
+}
+
+fn is_symbol(s: &[u8]) -> bool {
+    s.iter().all(|c| c.is_ascii_alphanumeric() || *c == b'_')
+}
+
+fn is_address_of_type(a: &ScAddress, ty: &ScSpecTypeDef) -> bool {
+    match ty {
+        ScSpecTypeDef::MuxedAddress => {
+            matches!(a, ScAddress::Account(_) | ScAddress::MuxedAccount(_))
+        }
+        _ => !matches!(a, ScAddress::MuxedAccount(_)),
+    }
+}
+
+struct Converter<'a> {
+    entries: &'a [ScSpecEntry],
+}
+
+impl Converter<'_> {
+    fn udt(&self, u: &ScSpecTypeUdt, path: &str) -> Result<ScSpecUdt<'_>, ScSpecJsonError> {
+        find_udt(self.entries, &u.name).ok_or_else(|| {
+            err(
+                path,
+                ScSpecJsonErrorKind::UnknownType(u.name.to_utf8_string_lossy()),
+            )
+        })
+    }
+
+    fn to_scval(
+        &self,
+        v: &Value,
+        ty: &ScSpecTypeDef,
+        path: &str,
+    ) -> Result<ScVal, ScSpecJsonError> {
+        let str = || v.as_str().ok_or_else(|| mismatch(path, ty));
+        let val = match ty {
+            ScSpecTypeDef::Val => {
+                serde_json::from_value(v.clone()).map_err(|_| mismatch(path, ty))?
+            }
+            ScSpecTypeDef::Bool => ScVal::Bool(v.as_bool().ok_or_else(|| mismatch(path, ty))?),
+            ScSpecTypeDef::Void => {
+                if !v.is_null() {
+                    return Err(mismatch(path, ty));
+                }
+                ScVal::Void
+            }
+            ScSpecTypeDef::Error => ScVal::Error(
+                serde_json::from_value::<ScError>(v.clone()).map_err(|_| mismatch(path, ty))?,
+            ),
+            ScSpecTypeDef::U32 => ScVal::U32(int_from_json(v, ty, path)?),
+            ScSpecTypeDef::I32 => ScVal::I32(int_from_json(v, ty, path)?),
+            ScSpecTypeDef::U64 => ScVal::U64(int_from_json(v, ty, path)?),
+            ScSpecTypeDef::I64 => ScVal::I64(int_from_json(v, ty, path)?),
+            ScSpecTypeDef::Timepoint => ScVal::Timepoint(TimePoint(int_from_json(v, ty, path)?)),
+            ScSpecTypeDef::Duration => ScVal::Duration(Duration(int_from_json(v, ty, path)?)),
+            ScSpecTypeDef::U128 => ScVal::U128(int_from_json::<UInt128Parts>(v, ty, path)?),
+            ScSpecTypeDef::I128 => ScVal::I128(int_from_json::<Int128Parts>(v, ty, path)?),
+            ScSpecTypeDef::U256 => ScVal::U256(int_from_json::<UInt256Parts>(v, ty, path)?),
+            ScSpecTypeDef::I256 => ScVal::I256(int_from_json::<Int256Parts>(v, ty, path)?),
+            ScSpecTypeDef::Bytes | ScSpecTypeDef::BytesN(_) => {
+                let bytes = hex::decode(str()?).map_err(|_| invalid(path, ty))?;
+                if let ScSpecTypeDef::BytesN(b) = ty {
+                    if bytes.len() != b.n as usize {
+                        return Err(length_mismatch(path, b.n as usize, bytes.len()));
+                    }
+                }
+                ScVal::Bytes(ScBytes(bytes.try_into().map_err(|_| invalid(path, ty))?))
+            }
+            ScSpecTypeDef::String => {
+                ScVal::String(ScString(str()?.parse().map_err(|_| invalid(path, ty))?))
+            }
+            ScSpecTypeDef::Symbol => {
+                let s = str()?;
+                if !is_symbol(s.as_bytes()) {
+                    return Err(invalid(path, ty));
+                }
+                ScVal::Symbol(ScSymbol(s.parse().map_err(|_| invalid(path, ty))?))
+            }
+            ScSpecTypeDef::Address | ScSpecTypeDef::MuxedAddress => {
+                let a = ScAddress::from_str(str()?).map_err(|_| invalid(path, ty))?;
+                if !is_address_of_type(&a, ty) {
+                    return Err(invalid(path, ty));
+                }
+                ScVal::Address(a)
+            }
+            ScSpecTypeDef::Option(o) => {
+                if v.is_null() {
+                    ScVal::Void
+                } else {
+                    self.to_scval(v, &o.value_type, path)?
+                }
+            }
+            ScSpecTypeDef::Result(r) => {
+                let o = v
+                    .as_object()
+                    .filter(|o| o.len() == 1)
+                    .ok_or_else(|| mismatch(path, ty))?;
+                match o.iter().next() {
+                    Some((k, v)) if k == "ok" => {
+                        self.to_scval(v, &r.ok_type, &field_path(path, k))?
+                    }
+                    Some((k, v)) if k == "error" => {
+                        let val = self.to_scval(v, &r.error_type, &field_path(path, k))?;
+                        if !matches!(val, ScVal::Error(_)) {
+                            return Err(mismatch(&field_path(path, k), "Error"));
+                        }
+                        val
+                    }
+                    _ => return Err(mismatch(path, ty)),
+                }
+            }
+            ScSpecTypeDef::Vec(vt) => {
+                let a = v.as_array().ok_or_else(|| mismatch(path, ty))?;
+                let items = a
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| self.to_scval(v, &vt.element_type, &index_path(path, i)))
+                    .collect::<Result<Vec<_>, _>>()?;
+                vec(items, path, ty)?
+            }
+            ScSpecTypeDef::Map(m) => self.map_to_scval(v, m, path, ty)?,
+            ScSpecTypeDef::Tuple(tt) => {
+                let a = v.as_array().ok_or_else(|| mismatch(path, ty))?;
+                self.vec_to_scval(a, &tt.value_types, path, ty)?
+            }
+            ScSpecTypeDef::Udt(u) => self.udt_to_scval(v, u, path, ty)?,
+        };
+        Ok(val)
+    }
+
+    fn map_to_scval(
+        &self,
+        v: &Value,
+        m: &ScSpecTypeMap,
+        path: &str,
+        ty: &ScSpecTypeDef,
+    ) -> Result<ScVal, ScSpecJsonError> {
+        let pairs = match v {
+            Value::Object(o) => o
+                .iter()
+                .map(|(k, v)| {
+                    let path = field_path(path, k);
+                    let key = self.to_scval(&Value::String(k.clone()), &m.key_type, &path)?;
+                    let val = self.to_scval(v, &m.value_type, &path)?;
+                    Ok((key, val))
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            Value::Array(a) => a
+                .iter()
+                .enumerate()
+                .map(|(i, pair)| {
+                    let path = index_path(path, i);
+                    match pair.as_array().map(Vec::as_slice) {
+                        Some([k, v]) => {
+                            let key = self.to_scval(k, &m.key_type, &index_path(&path, 0))?;
+                            let val = self.to_scval(v, &m.value_type, &index_path(&path, 1))?;
+                            Ok((key, val))
+                        }
+                        _ => Err(mismatch(
+                            &path,
+                            format_args!("[{}, {}]", m.key_type, m.value_type),
+                        )),
+                    }
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            _ => return Err(mismatch(path, ty)),
+        };
+        let map =
+            ScMap::sorted_from(pairs).map_err(|_| err(path, ScSpecJsonErrorKind::DuplicateKey))?;
+        Ok(ScVal::Map(Some(map)))
+    }
+
+    fn udt_to_scval(
+        &self,
+        v: &Value,
+        u: &ScSpecTypeUdt,
+        path: &str,
+        ty: &ScSpecTypeDef,
+    ) -> Result<ScVal, ScSpecJsonError> {
+        let val = match self.udt(u, path)? {
+            ScSpecUdt::Struct(s) => self.struct_to_scval(v, s, path, ty)?,
+            ScSpecUdt::Union(u) => self.union_to_scval(v, u, path, ty)?,
+            ScSpecUdt::Enum(e) => {
+                let value = case_from_json(
+                    v,
+                    e.cases.iter().map(|c| (c.name.as_slice(), c.value)),
+                    path,
+                    ty,
+                )?;
+                ScVal::U32(value)
+            }
+            ScSpecUdt::ErrorEnum(e) => {
+                let value = case_from_json(
+                    v,
+                    e.cases.iter().map(|c| (c.name.as_slice(), c.value)),
+                    path,
+                    ty,
+                )?;
+                ScVal::Error(ScError::Contract(value))
+            }
+        };
+        Ok(val)
+    }
+
+    /// Converts the array into a vec of values of each of the types.
+    fn vec_to_scval(
+        &self,
+        a: &[Value],
+        types: &[ScSpecTypeDef],
+        path: &str,
+        ty: &ScSpecTypeDef,
+    ) -> Result<ScVal, ScSpecJsonError> {
+        if a.len() != types.len() {
+            return Err(length_mismatch(path, types.len(), a.len()));
+        }
+        let items = a
+            .iter()
+            .zip(types)
+            .enumerate()
+            .map(|(i, (v, ty))| self.to_scval(v, ty, &index_path(path, i)))
+            .collect::<Result<Vec<_>, _>>()?;
+        vec(items, path, ty)
+    }
+
+    fn struct_to_scval(
+        &self,
+        v: &Value,
+        s: &ScSpecUdtStructV0,
+        path: &str,
+        ty: &ScSpecTypeDef,
+    ) -> Result<ScVal, ScSpecJsonError> {
+        if s.is_tuple() {
+            let a = v.as_array().ok_or_else(|| mismatch(path, ty))?;
+            let types = s.fields.iter().map(|f| f.type_.clone()).collect::<Vec<_>>();
+            return self.vec_to_scval(a, &types, path, ty);
+        }
+        let o = v.as_object().ok_or_else(|| mismatch(path, ty))?;
+        if let Some(k) = o
+            .keys()
+            .find(|k| !s.fields.iter().any(|f| f.name.as_slice() == k.as_bytes()))
+        {
+            return Err(err(path, ScSpecJsonErrorKind::UnknownField(k.clone())));
+        }
+        let entries = s
+            .fields
+            .iter()
+            .map(|f| {
+                let name = f.name.to_utf8_string_lossy();
+                let v = o
+                    .get(&name)
+                    .ok_or_else(|| err(path, ScSpecJsonErrorKind::MissingField(name.clone())))?;
+                let path = field_path(path, &name);
+                Ok((symbol(&f.name, &path)?, self.to_scval(v, &f.type_, &path)?))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let map = ScMap::sorted_from(entries)
+            .map_err(|_| err(path, ScSpecJsonErrorKind::DuplicateKey))?;
+        Ok(ScVal::Map(Some(map)))
+    }
+
+    fn union_to_scval(
+        &self,
+        v: &Value,
+        u: &ScSpecUdtUnionV0,
+        path: &str,
+        ty: &ScSpecTypeDef,
+    ) -> Result<ScVal, ScSpecJsonError> {
+        let (name, values) = match v {
+            Value::String(name) => (name, None),
+            Value::Object(o) if o.len() == 1 => {
+                let (name, values) = o.iter().next().ok_or_else(|| mismatch(path, ty))?;
+                (name, Some(values))
+            }
+            _ => return Err(mismatch(path, ty)),
+        };
+        let case = u
+            .cases
+            .iter()
+            .find(|c| union_case_name(c) == name.as_bytes())
+            .ok_or_else(|| err(path, ScSpecJsonErrorKind::UnknownCase(name.clone())))?;
+        let mut items = vec![symbol(name.as_bytes(), path)?];
+        match (case, values) {
+            (ScSpecUdtUnionCaseV0::VoidV0(_), None) => {}
+            (ScSpecUdtUnionCaseV0::TupleV0(c), Some(values)) => {
+                let path = field_path(path, name);
+                let a = values.as_array().ok_or_else(|| mismatch(&path, "array"))?;
+                if a.len() != c.type_.len() {
+                    return Err(length_mismatch(&path, c.type_.len(), a.len()));
+                }
+                for (i, (v, ty)) in a.iter().zip(c.type_.iter()).enumerate() {
+                    items.push(self.to_scval(v, ty, &index_path(&path, i))?);
+                }
+            }
+            _ => return Err(mismatch(path, ty)),
+        }
+        vec(items, path, ty)
+    }
+
+    fn to_json(
+        &self,
+        val: &ScVal,
+        ty: &ScSpecTypeDef,
+        path: &str,
+    ) -> Result<Value, ScSpecJsonError> {
+        let json = match (ty, val) {
+            (ScSpecTypeDef::Val, _) => serde_json::to_value(val).map_err(|_| mismatch(path, ty))?,
+            (ScSpecTypeDef::Bool, ScVal::Bool(b)) => Value::Bool(*b),
+            (ScSpecTypeDef::Void | ScSpecTypeDef::Option(_), ScVal::Void) => Value::Null,
+            (ScSpecTypeDef::Error, ScVal::Error(e)) => {
+                serde_json::to_value(e).map_err(|_| mismatch(path, ty))?
+            }
+            (ScSpecTypeDef::U32, ScVal::U32(n)) => Value::from(*n),
+            (ScSpecTypeDef::I32, ScVal::I32(n)) => Value::from(*n),
+            (ScSpecTypeDef::U64, ScVal::U64(n))
+            | (ScSpecTypeDef::Timepoint, ScVal::Timepoint(TimePoint(n)))
+            | (ScSpecTypeDef::Duration, ScVal::Duration(Duration(n))) => Value::from(*n),
+            (ScSpecTypeDef::I64, ScVal::I64(n)) => Value::from(*n),
+            (ScSpecTypeDef::U128, ScVal::U128(n)) => Value::String(n.to_string()),
+            (ScSpecTypeDef::I128, ScVal::I128(n)) => Value::String(n.to_string()),
+            (ScSpecTypeDef::U256, ScVal::U256(n)) => Value::String(n.to_string()),
+            (ScSpecTypeDef::I256, ScVal::I256(n)) => Value::String(n.to_string()),
+            (ScSpecTypeDef::Bytes, ScVal::Bytes(b)) => Value::String(hex::encode(b)),
+            (ScSpecTypeDef::BytesN(n), ScVal::Bytes(b)) => {
+                if b.len() != n.n as usize {
+                    return Err(length_mismatch(path, n.n as usize, b.len()));
+                }
+                Value::String(hex::encode(b))
+            }
+            (ScSpecTypeDef::String, ScVal::String(s)) => Value::String(s.to_string()),
+            (ScSpecTypeDef::Symbol, ScVal::Symbol(s)) => {
+                if !is_symbol(s) {
+                    return Err(invalid(path, ty));
+                }
+                Value::String(s.to_string())
+            }
+            (ScSpecTypeDef::Address | ScSpecTypeDef::MuxedAddress, ScVal::Address(a)) => {
+                if !is_address_of_type(a, ty) {
+                    return Err(invalid(path, ty));
+                }
+                Value::String(a.to_string())
+            }
+            (ScSpecTypeDef::Option(o), _) => self.to_json(val, &o.value_type, path)?,
+            (ScSpecTypeDef::Result(r), ScVal::Error(_)) => {
+                let json = self.to_json(val, &r.error_type, &field_path(path, "error"))?;
+                Value::Object(Map::from_iter([("error".to_string(), json)]))
+            }
+            (ScSpecTypeDef::Result(r), _) => {
+                let json = self.to_json(val, &r.ok_type, &field_path(path, "ok"))?;
+                Value::Object(Map::from_iter([("ok".to_string(), json)]))
+            }
+            (ScSpecTypeDef::Vec(vt), ScVal::Vec(Some(v))) => Value::Array(
+                v.iter()
+                    .enumerate()
+                    .map(|(i, v)| self.to_json(v, &vt.element_type, &index_path(path, i)))
+                    .collect::<Result<_, _>>()?,
+            ),
+            (ScSpecTypeDef::Map(m), ScVal::Map(Some(map))) => self.map_to_json(map, m, path)?,
+            (ScSpecTypeDef::Tuple(tt), ScVal::Vec(Some(v))) => {
+                self.vec_to_json(v, &tt.value_types, path)?
+            }
+            (ScSpecTypeDef::Udt(u), _) => self.udt_to_json(val, u, path, ty)?,
+            _ => return Err(mismatch(path, ty)),
+        };
+        Ok(json)
+    }
+
+    /// Converts the map into an object if every key converts to a string,
+    /// otherwise into an array of key value pairs.
+    fn map_to_json(
+        &self,
+        map: &ScMap,
+        m: &ScSpecTypeMap,
+        path: &str,
+    ) -> Result<Value, ScSpecJsonError> {
+        let pairs = map
+            .iter()
+            .enumerate()
+            .map(|(i, e)| {
+                let path = index_path(path, i);
+                let k = self.to_json(&e.key, &m.key_type, &index_path(&path, 0))?;
+                let v = self.to_json(&e.val, &m.value_type, &index_path(&path, 1))?;
+                Ok((k, v))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if pairs.iter().all(|(k, _)| k.is_string()) {
+            Ok(Value::Object(
+                pairs
+                    .into_iter()
+                    .filter_map(|(k, v)| match k {
+                        Value::String(k) => Some((k, v)),
+                        _ => None,
+                    })
+                    .collect(),
+            ))
+        } else {
+            Ok(Value::Array(
+                pairs
+                    .into_iter()
+                    .map(|(k, v)| Value::Array(vec![k, v]))
+                    .collect(),
+            ))
+        }
+    }
+
+    fn udt_to_json(
+        &self,
+        val: &ScVal,
+        u: &ScSpecTypeUdt,
+        path: &str,
+        ty: &ScSpecTypeDef,
+    ) -> Result<Value, ScSpecJsonError> {
+        let (cases, n): (Vec<u32>, u32) = match (self.udt(u, path)?, val) {
+            (ScSpecUdt::Struct(s), _) => return self.struct_to_json(val, s, path, ty),
+            (ScSpecUdt::Union(u), _) => return self.union_to_json(val, u, path, ty),
+            (ScSpecUdt::Enum(e), ScVal::U32(n)) => (e.cases.iter().map(|c| c.value).collect(), *n),
+            (ScSpecUdt::ErrorEnum(e), ScVal::Error(ScError::Contract(n))) => {
+                (e.cases.iter().map(|c| c.value).collect(), *n)
+            }
+            _ => return Err(mismatch(path, ty)),
+        };
+        if !cases.contains(&n) {
+            return Err(err(path, ScSpecJsonErrorKind::UnknownCase(n.to_string())));
+        }
+        Ok(Value::Number(Number::from(n)))
+    }
+
+    /// Converts the values of a vec, which must each be of the corresponding
+    /// type, into an array.
+    fn vec_to_json(
+        &self,
+        v: &[ScVal],
+        types: &[ScSpecTypeDef],
+        path: &str,
+    ) -> Result<Value, ScSpecJsonError> {
+        if v.len() != types.len() {
+            return Err(length_mismatch(path, types.len(), v.len()));
+        }
+        Ok(Value::Array(
+            v.iter()
+                .zip(types)
+                .enumerate()
+                .map(|(i, (v, ty))| self.to_json(v, ty, &index_path(path, i)))
+                .collect::<Result<_, _>>()?,
+        ))
+    }
+
+    fn struct_to_json(
+        &self,
+        val: &ScVal,
+        s: &ScSpecUdtStructV0,
+        path: &str,
+        ty: &ScSpecTypeDef,
+    ) -> Result<Value, ScSpecJsonError> {
+        if s.is_tuple() {
+            let ScVal::Vec(Some(v)) = val else {
+                return Err(mismatch(path, ty));
+            };
+            let types = s.fields.iter().map(|f| f.type_.clone()).collect::<Vec<_>>();
+            return self.vec_to_json(v, &types, path);
+        }
+        let ScVal::Map(Some(map)) = val else {
+            return Err(mismatch(path, ty));
+        };
+        for e in map.iter() {
+            match &e.key {
+                ScVal::Symbol(k) if s.fields.iter().any(|f| f.name.as_slice() == k.as_slice()) => {}
+                ScVal::Symbol(k) => {
+                    return Err(err(
+                        path,
+                        ScSpecJsonErrorKind::UnknownField(k.to_utf8_string_lossy()),
+                    ))
+                }
+                _ => return Err(mismatch(path, ty)),
+            }
+        }
+        let mut o = Map::new();
+        for f in &*s.fields {
+            let name = f.name.to_utf8_string_lossy();
+            let v = map
+                .iter()
+                .find(|e| matches!(&e.key, ScVal::Symbol(k) if k.as_slice() == f.name.as_slice()))
+                .ok_or_else(|| err(path, ScSpecJsonErrorKind::MissingField(name.clone())))?;
+            let json = self.to_json(&v.val, &f.type_, &field_path(path, &name))?;
+            o.insert(name, json);
+        }
+        Ok(Value::Object(o))
+    }
+
+    fn union_to_json(
+        &self,
+        val: &ScVal,
+        u: &ScSpecUdtUnionV0,
+        path: &str,
+        ty: &ScSpecTypeDef,
+    ) -> Result<Value, ScSpecJsonError> {
+        let Some((ScVal::Symbol(name), values)) = (match val {
+            ScVal::Vec(Some(v)) => v.split_first(),
+            _ => None,
+        }) else {
+            return Err(mismatch(path, ty));
+        };
+        let name_str = name.to_utf8_string_lossy();
+        let case = u
+            .cases
+            .iter()
+            .find(|c| union_case_name(c) == name.as_slice())
+            .ok_or_else(|| err(path, ScSpecJsonErrorKind::UnknownCase(name_str.clone())))?;
+        match case {
+            ScSpecUdtUnionCaseV0::VoidV0(_) => {
+                if !values.is_empty() {
+                    return Err(length_mismatch(path, 1, values.len() + 1));
+                }
+                Ok(Value::String(name_str))
+            }
+            ScSpecUdtUnionCaseV0::TupleV0(c) => {
+                let json = self.vec_to_json(values, &c.type_, &field_path(path, &name_str))?;
+                Ok(Value::Object(Map::from_iter([(name_str, json)])))
+            }
+        }
+    }
+}
+
+fn union_case_name(c: &ScSpecUdtUnionCaseV0) -> &[u8] {
+    match c {
+        ScSpecUdtUnionCaseV0::VoidV0(c) => &c.name,
+        ScSpecUdtUnionCaseV0::TupleV0(c) => &c.name,
+    }
+}
+
+/// Converts a JSON number, or a string containing a decimal number, into an
+/// integer.
+fn int_from_json<T: FromStr>(
+    v: &Value,
+    ty: &ScSpecTypeDef,
+    path: &str,
+) -> Result<T, ScSpecJsonError> {
+    let s = match v {
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s.clone(),
+        _ => return Err(mismatch(path, ty)),
+    };
+    s.parse().map_err(|_| {
+        let digits = s.strip_prefix('-').unwrap_or(&s);
+        if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) {
+            err(
+                path,
+                ScSpecJsonErrorKind::OutOfRange {
+                    expected: ty.to_string(),
+                },
+            )
+        } else {
+            mismatch(path, ty)
+        }
+    })
+}
+
+/// Converts a JSON number, or a string naming a case, into the value of a case
+/// of an enum.
+fn case_from_json<'a>(
+    v: &Value,
+    mut cases: impl Iterator<Item = (&'a [u8], u32)>,
+    path: &str,
+    ty: &ScSpecTypeDef,
+) -> Result<u32, ScSpecJsonError> {
+    let found = match v {
+        Value::Number(_) => {
+            let n = int_from_json::<u32>(v, ty, path)?;
+            cases.find(|(_, value)| *value == n)
+        }
+        Value::String(s) => cases.find(|(name, _)| *name == s.as_bytes()),
+        _ => return Err(mismatch(path, ty)),
+    };
+    found
+        .map(|(_, value)| value)
+        .ok_or_else(|| err(path, ScSpecJsonErrorKind::UnknownCase(v.to_string())))
+}
@@ -0,0 +1,382 @@
+#![cfg(feature = "alloc")]
+use super::{
+    scspec::{find_udt, ScSpecUdt},
+    ScAddress, ScError, ScMap, ScSpecEntry, ScSpecTypeDef, ScSpecUdtStructV0, ScSpecUdtUnionCaseV0,
+    ScSpecUdtUnionV0, ScVal, Validate,
+};
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use core::cmp::Ordering;
+use core::fmt;
+
+/// Error returned when a `ScVal` does not conform to a contract spec type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScSpecValidationError {
+    /// The location of the first value that does not conform, as a path from
+    /// the root value `$`. Struct fields are selected with `.name`, and
+    /// elements of vecs and entries of maps with `[index]`, followed by
+    /// `.key` or `.val` for maps.
+    pub path: String,
+    pub kind: ScSpecValidationErrorKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScSpecValidationErrorKind {
+    /// The value is not of the expected type.
+    TypeMismatch { expected: String },
+    /// The value is of the expected kind, but is not a valid value of the
+    /// expected type, such as a symbol with invalid characters or a muxed
+    /// account where an address is expected.
+    InvalidValue { expected: String },
+    /// The value has a different number of elements than the expected type.
+    LengthMismatch { expected: usize, actual: usize },
+    /// The keys of the map are not sorted, or are not unique.
+    UnsortedMap,
+    /// The contract spec has no user defined type with the name.
+    UnknownType(String),
+    /// The user defined type has no case with the name or value.
+    UnknownCase(String),
+    /// The struct has no field with the name.
+    UnknownField(String),
+    /// The struct field with the name is missing from the value.
+    MissingField(String),
+}
+
+impl fmt::Display for ScSpecValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.kind)
+    }
+}
+
+impl fmt::Display for ScSpecValidationErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::TypeMismatch { expected } => write!(f, "expected {expected}"),
+            Self::InvalidValue { expected } => write!(f, "invalid {expected}"),
+            Self::LengthMismatch { expected, actual } => {
+                write!(f, "expected length {expected}, got {actual}")
+            }
+            Self::UnsortedMap => write!(f, "map keys are not sorted and unique"),
+            Self::UnknownType(name) => write!(f, "unknown type {name}"),
+            Self::UnknownCase(name) => write!(f, "unknown case {name}"),
+            Self::UnknownField(name) => write!(f, "unknown field {name}"),
+            Self::MissingField(name) => write!(f, "missing field {name}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ScSpecValidationError {}
+
+impl ScVal {
+    /// Validates that the value conforms to the type, using the contract spec
+    /// entries to resolve user defined types.
+    ///
+    /// In addition to the intrinsic validity checked by [`Validate`], the
+    /// value must have the representation the type has on chain:
+    ///
+    /// - `Option<T>` is `Void` or a `T`, and `Result<T, E>` is a `T` or an
+    ///   `E` that is an `Error`.
+    /// - `BytesN<N>` is `Bytes` of length `N`.
+    /// - `Address` is an account or contract address, and `MuxedAddress` is
+    ///   an account or muxed account address.
+    /// - Structs are maps with a `Symbol` key for each field, or vecs for
+    ///   tuple structs.
+    /// - Unions are vecs of a `Symbol` naming a case followed by the case's
+    ///   values.
+    /// - Enums are `U32` and error enums are contract `Error`s, with the
+    ///   value of one of the cases.
+    ///
+    /// # Errors
+    ///
+    /// If the value does not conform to the type, with the path to the first
+    /// value found that does not conform.
+    pub fn validate_against(
+        &self,
+        type_def: &ScSpecTypeDef,
+        entries: &[ScSpecEntry],
+    ) -> Result<(), ScSpecValidationError> {
+        Validator { entries }.validate(self, type_def, "$")
+    }
+}
+
+fn err(path: &str, kind: ScSpecValidationErrorKind) -> ScSpecValidationError {
+    ScSpecValidationError {
+        path: path.to_string(),
+        kind,
+    }
+}
+
+fn mismatch(path: &str, expected: impl fmt::Display) -> ScSpecValidationError {
+    err(
+        path,
+        ScSpecValidationErrorKind::TypeMismatch {
+            expected: expected.to_string(),
+        },
+    )
+}
+
+fn invalid(path: &str, expected: impl fmt::Display) -> ScSpecValidationError {
+    err(
+        path,
+        ScSpecValidationErrorKind::InvalidValue {
+            expected: expected.to_string(),
+        },
+    )
+}
+
+fn length_mismatch(path: &str, expected: usize, actual: usize) -> ScSpecValidationError {
+    err(
+        path,
+        ScSpecValidationErrorKind::LengthMismatch { expected, actual },
+    )
+}
+
+fn index_path(path: &str, i: usize) -> String {
+    format!("{path}[{i}]")
+}
+
+/// Returns true if the map is sorted by key, in the order of the host, and
+/// there are no keys that are duplicates.
+fn is_sorted(map: &ScMap) -> bool {
+    map.windows(2)
+        .all(|w| w[0].key.host_cmp(&w[1].key) == Ordering::Less)
+}
+
+/// Validates the intrinsic validity of a value of any type, reporting the
+/// path to the first invalid value.
+fn validate_val(val: &ScVal, path: &str) -> Result<(), ScSpecValidationError> {
+    match val {
+        ScVal::Vec(Some(vec)) => {
+            for (i, e) in vec.iter().enumerate() {
+                validate_val(e, &index_path(path, i))?;
+            }
+            Ok(())
+        }
+        ScVal::Map(Some(map)) => {
+            if !is_sorted(map) {
+                return Err(err(path, ScSpecValidationErrorKind::UnsortedMap));
+            }
+            for (i, e) in map.iter().enumerate() {
+                let path = index_path(path, i);
+                validate_val(&e.key, &format!("{path}.key"))?;
+                validate_val(&e.val, &format!("{path}.val"))?;
+            }
+            Ok(())
+        }
+        _ => val
+            .validate()
+            .map_err(|_| invalid(path, ScSpecTypeDef::Val)),
+    }
+}
+
+struct Validator<'a> {
+    entries: &'a [ScSpecEntry],
+}
+
+impl Validator<'_> {
+    fn validate(
+        &self,
+        val: &ScVal,
+        ty: &ScSpecTypeDef,
+        path: &str,
+    ) -> Result<(), ScSpecValidationError> {
+        match (ty, val) {
+            (ScSpecTypeDef::Val, _) => validate_val(val, path),
+            (ScSpecTypeDef::Bool, ScVal::Bool(_))
+            | (ScSpecTypeDef::Void | ScSpecTypeDef::Option(_), ScVal::Void)
+            | (ScSpecTypeDef::Error, ScVal::Error(_))
+            | (ScSpecTypeDef::U32, ScVal::U32(_))
+            | (ScSpecTypeDef::I32, ScVal::I32(_))
+            | (ScSpecTypeDef::U64, ScVal::U64(_))
+            | (ScSpecTypeDef::I64, ScVal::I64(_))
+            | (ScSpecTypeDef::Timepoint, ScVal::Timepoint(_))
+            | (ScSpecTypeDef::Duration, ScVal::Duration(_))
+            | (ScSpecTypeDef::U128, ScVal::U128(_))
+            | (ScSpecTypeDef::I128, ScVal::I128(_))
+            | (ScSpecTypeDef::U256, ScVal::U256(_))
+            | (ScSpecTypeDef::I256, ScVal::I256(_))
+            | (ScSpecTypeDef::Bytes, ScVal::Bytes(_))
+            | (ScSpecTypeDef::String, ScVal::String(_)) => Ok(()),
+            (ScSpecTypeDef::Symbol, ScVal::Symbol(_)) => {
+                val.validate().map_err(|_| invalid(path, ty))
+            }
+            (ScSpecTypeDef::Address, ScVal::Address(a)) => match a {
+                ScAddress::MuxedAccount(_) => Err(invalid(path, ty)),
+                _ => Ok(()),
+            },
+            (ScSpecTypeDef::MuxedAddress, ScVal::Address(a)) => match a {
+                ScAddress::Account(_) | ScAddress::MuxedAccount(_) => Ok(()),
+                _ => Err(invalid(path, ty)),
+            },
+            (ScSpecTypeDef::BytesN(b), ScVal::Bytes(bytes)) => {
+                if bytes.len() == b.n as usize {
+                    Ok(())
+                } else {
+                    Err(length_mismatch(path, b.n as usize, bytes.len()))
+                }
+            }
+            (ScSpecTypeDef::Option(o), _) => self.validate(val, &o.value_type, path),
+            (ScSpecTypeDef::Result(r), ScVal::Error(_)) => self.validate(val, &r.error_type, path),
+            (ScSpecTypeDef::Result(r), _) => self.validate(val, &r.ok_type, path),
+            (ScSpecTypeDef::Vec(v), ScVal::Vec(Some(vec))) => {
+                for (i, e) in vec.iter().enumerate() {
+                    self.validate(e, &v.element_type, &index_path(path, i))?;
+                }
+                Ok(())
+            }
+            (ScSpecTypeDef::Map(m), ScVal::Map(Some(map))) => {
+                if !is_sorted(map) {
+                    return Err(err(path, ScSpecValidationErrorKind::UnsortedMap));
+                }
+                for (i, e) in map.iter().enumerate() {
+                    let path = index_path(path, i);
+                    self.validate(&e.key, &m.key_type, &format!("{path}.key"))?;
+                    self.validate(&e.val, &m.value_type, &format!("{path}.val"))?;
+                }
+                Ok(())
+            }
+            (ScSpecTypeDef::Tuple(t), ScVal::Vec(Some(vec))) => {
+                self.validate_elements(vec, &t.value_types, path)
+            }
+            (ScSpecTypeDef::Udt(u), _) => match find_udt(self.entries, &u.name) {
+                Some(ScSpecUdt::Struct(s)) => self.validate_struct(val, s, path, ty),
+                Some(ScSpecUdt::Union(u)) => self.validate_union(val, u, path, ty),
+                Some(ScSpecUdt::Enum(e)) => match val {
+                    ScVal::U32(n) if e.cases.iter().any(|c| c.value == *n) => Ok(()),
+                    ScVal::U32(n) => Err(err(
+                        path,
+                        ScSpecValidationErrorKind::UnknownCase(n.to_string()),
+                    )),
+                    _ => Err(mismatch(path, ty)),
+                },
+                Some(ScSpecUdt::ErrorEnum(e)) => match val {
+                    ScVal::Error(ScError::Contract(n)) if e.cases.iter().any(|c| c.value == *n) => {
+                        Ok(())
+                    }
+                    ScVal::Error(ScError::Contract(n)) => Err(err(
+                        path,
+                        ScSpecValidationErrorKind::UnknownCase(n.to_string()),
+                    )),
+                    _ => Err(mismatch(path, ty)),
+                },
+                None => Err(err(
+                    path,
+                    ScSpecValidationErrorKind::UnknownType(u.name.to_utf8_string_lossy()),
+                )),
+            },
+            _ => Err(mismatch(path, ty)),
+        }
+    }
+
+    /// Validates that the values are each of the corresponding type.
+    fn validate_elements(
+        &self,
+        vals: &[ScVal],
+        types: &[ScSpecTypeDef],
+        path: &str,
+    ) -> Result<(), ScSpecValidationError> {
+        if vals.len() != types.len() {
+            return Err(length_mismatch(path, types.len(), vals.len()));
+        }
+        for (i, (v, t)) in vals.iter().zip(types).enumerate() {
+            self.validate(v, t, &index_path(path, i))?;
+        }
+        Ok(())
+    }
+
+    fn validate_struct(
+        &self,
+        val: &ScVal,
+        s: &ScSpecUdtStructV0,
+        path: &str,
+        ty: &ScSpecTypeDef,
+    ) -> Result<(), ScSpecValidationError> {
+        if s.is_tuple() {
+            let ScVal::Vec(Some(vec)) = val else {
+                return Err(mismatch(path, ty));
+            };
+            let types = s.fields.iter().map(|f| f.type_.clone()).collect::<Vec<_>>();
+            return self.validate_elements(vec, &types, path);
+        }
+        let ScVal::Map(Some(map)) = val else {
+            return Err(mismatch(path, ty));
+        };
+        if !is_sorted(map) {
+            return Err(err(path, ScSpecValidationErrorKind::UnsortedMap));
+        }
+        for (i, e) in map.iter().enumerate() {
+            let ScVal::Symbol(key) = &e.key else {
+                return Err(mismatch(&format!("{path}[{i}].key"), ScSpecTypeDef::Symbol));
+            };
+            let Some(f) = s
+                .fields
+                .iter()
+                .find(|f| f.name.as_slice() == key.as_slice())
+            else {
+                return Err(err(
+                    path,
+                    ScSpecValidationErrorKind::UnknownField(key.to_utf8_string_lossy()),
+                ));
+            };
+            self.validate(&e.val, &f.type_, &format!("{path}.{}", f.name))?;
+        }
+        if let Some(f) = s.fields.iter().find(|f| {
+            !map.iter()
+                .any(|e| matches!(&e.key, ScVal::Symbol(k) if k.as_slice() == f.name.as_slice()))
+        }) {
+            return Err(err(
+                path,
+                ScSpecValidationErrorKind::MissingField(f.name.to_utf8_string_lossy()),
+            ));
+        }
+        Ok(())
+    }
+
+    fn validate_union(
+        &self,
+        val: &ScVal,
+        u: &ScSpecUdtUnionV0,
+        path: &str,
+        ty: &ScSpecTypeDef,
+    ) -> Result<(), ScSpecValidationError> {
+        let ScVal::Vec(Some(vec)) = val else {
+            return Err(mismatch(path, ty));
+        };
+        let Some((ScVal::Symbol(name), values)) = vec.split_first() else {
+            return Err(mismatch(path, ty));
+        };
+        let case = u
+            .cases
+            .iter()
+            .find(|c| match c {
+                ScSpecUdtUnionCaseV0::VoidV0(c) => c.name.as_slice() == name.as_slice(),
+                ScSpecUdtUnionCaseV0::TupleV0(c) => c.name.as_slice() == name.as_slice(),
+            })
+            .ok_or_else(|| {
+                err(
+                    &index_path(path, 0),
+                    ScSpecValidationErrorKind::UnknownCase(name.to_utf8_string_lossy()),
+                )
+            })?;
+        let types: &[ScSpecTypeDef] = match case {
+            ScSpecUdtUnionCaseV0::VoidV0(_) => &[],
+            ScSpecUdtUnionCaseV0::TupleV0(c) => &c.type_,
+        };
+        if values.len() != types.len() {
+            return Err(length_mismatch(path, types.len() + 1, vec.len()));
+        }
+        for (i, (v, t)) in values.iter().zip(types).enumerate() {
+            self.validate(v, t, &index_path(path, i + 1))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,541 @@
+#![cfg(feature = "alloc")]
+#[cfg(feature = "cap_0085_executable_ref")]
+use super::ContractExecutableExternalRef;
+use super::{
+    ContractExecutable, Duration, Hash, Int128Parts, Int256Parts, ScAddress, ScBytes,
+    ScContractInstance, ScError, ScErrorCode, ScErrorType, ScMap, ScMapEntry, ScNonceKey, ScString,
+    ScSymbol, ScVal, ScVec, TimePoint, UInt128Parts, UInt256Parts,
+};
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use core::{fmt, str::FromStr};
+
+/// Error returned when parsing the text notation of a `ScVal` fails.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScValParseError {
+    /// The byte offset in the text at which parsing failed.
+    pub offset: usize,
+    /// What was expected at the offset.
+    pub expected: &'static str,
+}
+
+impl fmt::Display for ScValParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "expected {} at offset {}", self.expected, self.offset)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ScValParseError {}
+
+/// Formats the `ScVal` in a compact text notation, that is parsed by the
+/// `FromStr` impl back into the same `ScVal`.
+///
+/// The notation for each type is:
+///
+/// - `Bool`: `true` or `false`. `Void`: `void`.
+/// - Integers: the decimal number suffixed with its type, e.g. `1u32`,
+///   `-2i64`, `3u128`, `-4i256`.
+/// - `Timepoint` and `Duration`: `timepoint:1`, `duration:1`.
+/// - `Error`: `error:Contract:1`, or for other error types the type and the
+///   code, e.g. `error:Storage:MissingValue`.
+/// - `Bytes`: `0x` followed by hex, e.g. `0xdeadbeef`.
+/// - `String`: a quoted string, e.g. `"abc"`. `Symbol`: a quoted string
+///   prefixed with `sym:`, e.g. `sym:"abc"`. Bytes that are not printable
+///   ASCII, and quotes, are escaped, e.g. `\n`, `\\`, `\x22`.
+/// - `Vec`: `[1u32, 2u32]`, or `vec:none` if absent.
+/// - `Map`: `{sym:"a": 1u32, sym:"b": 2u32}` in the order of the entries, or
+///   `map:none` if absent.
+/// - `Address`: the strkey, e.g. `G...`, `C...`, `M...`.
+/// - `ContractInstance`: `contract_instance(wasm:<hash hex>, {...})` or
+///   `contract_instance(stellar_asset, map:none)`.
+/// - `LedgerKeyContractInstance`: `ledger_key_contract_instance`.
+/// - `LedgerKeyNonce`: `ledger_key_nonce:1`.
+impl fmt::Display for ScVal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScVal::Bool(v) => write!(f, "{v}"),
+            ScVal::Void => f.write_str("void"),
+            ScVal::Error(e) => write_error(f, e),
+            ScVal::U32(v) => write!(f, "{v}u32"),
+            ScVal::I32(v) => write!(f, "{v}i32"),
+            ScVal::U64(v) => write!(f, "{v}u64"),
+            ScVal::I64(v) => write!(f, "{v}i64"),
+            ScVal::Timepoint(TimePoint(v)) => write!(f, "timepoint:{v}"),
+            ScVal::Duration(Duration(v)) => write!(f, "duration:{v}"),
+            ScVal::U128(v) => write!(f, "{v}u128"),
+            ScVal::I128(v) => write!(f, "{v}i128"),
+            ScVal::U256(v) => write!(f, "{v}u256"),
+            ScVal::I256(v) => write!(f, "{v}i256"),
+            ScVal::Bytes(v) => {
+                f.write_str("0x")?;
+                for b in v.iter() {
+                    write!(f, "{b:02x}")?;
+                }
+                Ok(())
+            }
+            ScVal::String(ScString(s)) => write_string(f, s),
+            ScVal::Symbol(ScSymbol(s)) => {
+                f.write_str("sym:")?;
+                write_string(f, s)
+            }
+            ScVal::Vec(None) => f.write_str("vec:none"),
+            ScVal::Vec(Some(vec)) => {
+                f.write_str("[")?;
+                for (i, v) in vec.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{v}")?;
+                }
+                f.write_str("]")
+            }
+            ScVal::Map(map) => write_map(f, map.as_ref()),
+            ScVal::Address(a) => write!(f, "{a}"),
+            ScVal::ContractInstance(ScContractInstance {
+                executable,
+                storage,
+            }) => {
+                f.write_str("contract_instance(")?;
+                match executable {
+                    ContractExecutable::Wasm(hash) => write!(f, "wasm:{hash}")?,
+                    ContractExecutable::StellarAsset => f.write_str("stellar_asset")?,
+                    #[cfg(feature = "cap_0085_executable_ref")]
+                    ContractExecutable::ExternalRef(ContractExecutableExternalRef {
+                        executable_owner,
+                        tag,
+                    }) => {
+                        write!(f, "external_ref({executable_owner}, ")?;
+                        write_string(f, tag)?;
+                        f.write_str(")")?;
+                    }
+                }
+                f.write_str(", ")?;
+                write_map(f, storage.as_ref())?;
+                f.write_str(")")
+            }
+            ScVal::LedgerKeyContractInstance => f.write_str("ledger_key_contract_instance"),
+            ScVal::LedgerKeyNonce(ScNonceKey { nonce }) => write!(f, "ledger_key_nonce:{nonce}"),
+            #[cfg(feature = "cap_0085_executable_ref")]
+            ScVal::ExecutableTag(ScString(s)) => {
+                f.write_str("executable_tag:")?;
+                write_string(f, s)
+            }
+        }
+    }
+}
+
+fn write_error(f: &mut fmt::Formatter, e: &ScError) -> fmt::Result {
+    let code = match e {
+        ScError::Contract(code) => return write!(f, "error:Contract:{code}"),
+        ScError::WasmVm(code)
+        | ScError::Context(code)
+        | ScError::Storage(code)
+        | ScError::Object(code)
+        | ScError::Crypto(code)
+        | ScError::Events(code)
+        | ScError::Budget(code)
+        | ScError::Value(code)
+        | ScError::Auth(code) => code,
+    };
+    write!(f, "error:{}:{}", e.name(), code.name())
+}
+
+fn write_string(f: &mut fmt::Formatter, s: &[u8]) -> fmt::Result {
+    f.write_str("\"")?;
+    for b in escape_bytes::Escape::new(s) {
+        // Quotes are escaped so that the end of the string is unambiguous.
+        if b == b'"' {
+            f.write_str("\\x22")?;
+        } else {
+            write!(f, "{}", b as char)?;
+        }
+    }
+    f.write_str("\"")
+}
+
+fn write_map(f: &mut fmt::Formatter, map: Option<&ScMap>) -> fmt::Result {
+    let Some(map) = map else {
+        return f.write_str("map:none");
+    };
+    f.write_str("{")?;
+    for (i, ScMapEntry { key, val }) in map.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "{key}: {val}")?;
+    }
+    f.write_str("}")
+}
+
+/// Parses the text notation of a `ScVal` produced by its `Display` impl.
+///
+/// Whitespace is permitted between values and punctuation. Strings may also
+/// contain unescaped UTF-8, which is stored as its bytes. Values may be nested
+/// at most 200 deep.
+impl FromStr for ScVal {
+    type Err = ScValParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            text: s.as_bytes(),
+            pos: 0,
+            depth: 0,
+        };
+        let v = parser.value()?;
+        parser.skip_whitespace();
+        if parser.pos < parser.text.len() {
+            return Err(parser.error("end of input"));
+        }
+        Ok(v)
+    }
+}
+
+/// Maximum depth of nested values, to bound the recursion on malicious
+/// input. Deeper than the host permits values to be nested.
+const MAX_DEPTH: usize = 200;
+
+struct Parser<'a> {
+    text: &'a [u8],
+    pos: usize,
+    /// The number of values being parsed that contain the value being parsed.
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, expected: &'static str) -> ScValParseError {
+        ScValParseError {
+            offset: self.pos,
+            expected,
+        }
+    }
+
+    fn error_at(offset: usize, expected: &'static str) -> ScValParseError {
+        ScValParseError { offset, expected }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.text.get(self.pos).is_some_and(u8::is_ascii_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    /// Consumes the byte if it is next, ignoring whitespace.
+    fn eat(&mut self, b: u8) -> bool {
+        self.skip_whitespace();
+        if self.text.get(self.pos) == Some(&b) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, b: u8, expected: &'static str) -> Result<(), ScValParseError> {
+        if self.eat(b) {
+            Ok(())
+        } else {
+            Err(self.error(expected))
+        }
+    }
+
+    /// Consumes the bytes while they match the predicate.
+    fn take_while(&mut self, predicate: impl Fn(u8) -> bool) -> &'a str {
+        let start = self.pos;
+        while self.text.get(self.pos).is_some_and(|b| predicate(*b)) {
+            self.pos += 1;
+        }
+        // The predicates only match ASCII, so the bytes are valid UTF-8.
+        core::str::from_utf8(&self.text[start..self.pos]).unwrap_or_default()
+    }
+
+    /// Consumes a word of ASCII letters, digits, and underscores.
+    fn word(&mut self) -> &'a str {
+        self.skip_whitespace();
+        self.take_while(|b| b.is_ascii_alphanumeric() || b == b'_')
+    }
+
+    fn keyword(&mut self, keyword: &'static str) -> Result<(), ScValParseError> {
+        let start = self.pos;
+        if self.word() == keyword {
+            Ok(())
+        } else {
+            Err(Self::error_at(start, keyword))
+        }
+    }
+
+    /// Consumes a decimal number, with an optional minus sign.
+    fn number<T: FromStr>(&mut self, expected: &'static str) -> Result<T, ScValParseError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        if self.text.get(self.pos) == Some(&b'-') {
+            self.pos += 1;
+        }
+        self.take_while(|b| b.is_ascii_digit());
+        core::str::from_utf8(&self.text[start..self.pos])
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .ok_or(Self::error_at(start, expected))
+    }
+
+    fn value(&mut self) -> Result<ScVal, ScValParseError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(self.error("less deeply nested values"));
+        }
+        self.depth += 1;
+        let v = self.value_inner();
+        self.depth -= 1;
+        v
+    }
+
+    fn value_inner(&mut self) -> Result<ScVal, ScValParseError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        match self.text.get(self.pos) {
+            Some(b'"') => {
+                let s = self.string()?;
+                return Ok(ScVal::String(ScString(
+                    s.try_into().map_err(|_| Self::error_at(start, "string"))?,
+                )));
+            }
+            Some(b'[') => return self.vec(),
+            Some(b'{') => return self.map().map(|m| ScVal::Map(Some(m))),
+            Some(b'0') if self.text.get(self.pos + 1) == Some(&b'x') => return self.bytes(),
+            Some(b'-' | b'0'..=b'9') => return self.integer(),
+            _ => {}
+        }
+        let v = match self.word() {
+            "true" => ScVal::Bool(true),
+            "false" => ScVal::Bool(false),
+            "void" => ScVal::Void,
+            "sym" => {
+                self.expect(b':', "`:`")?;
+                let start = self.pos;
+                ScVal::Symbol(ScSymbol(
+                    self.string()?
+                        .try_into()
+                        .map_err(|_| Self::error_at(start, "symbol of at most 32 bytes"))?,
+                ))
+            }
+            "timepoint" => {
+                self.expect(b':', "`:`")?;
+                ScVal::Timepoint(TimePoint(self.number("u64")?))
+            }
+            "duration" => {
+                self.expect(b':', "`:`")?;
+                ScVal::Duration(Duration(self.number("u64")?))
+            }
+            "error" => ScVal::Error(self.sc_error()?),
+            "vec" => {
+                self.expect(b':', "`:`")?;
+                self.keyword("none")?;
+                ScVal::Vec(None)
+            }
+            "map" => {
+                self.expect(b':', "`:`")?;
+                self.keyword("none")?;
+                ScVal::Map(None)
+            }
+            "contract_instance" => ScVal::ContractInstance(self.contract_instance()?),
+            "ledger_key_contract_instance" => ScVal::LedgerKeyContractInstance,
+            "ledger_key_nonce" => {
+                self.expect(b':', "`:`")?;
+                ScVal::LedgerKeyNonce(ScNonceKey {
+                    nonce: self.number("i64")?,
+                })
+            }
+            #[cfg(feature = "cap_0085_executable_ref")]
+            "executable_tag" => {
+                self.expect(b':', "`:`")?;
+                let start = self.pos;
+                ScVal::ExecutableTag(ScString(
+                    self.string()?
+                        .try_into()
+                        .map_err(|_| Self::error_at(start, "string"))?,
+                ))
+            }
+            word => ScVal::Address(
+                ScAddress::from_str(word).map_err(|_| Self::error_at(start, "value"))?,
+            ),
+        };
+        Ok(v)
+    }
+
+    fn integer(&mut self) -> Result<ScVal, ScValParseError> {
+        let start = self.pos;
+        if self.text.get(self.pos) == Some(&b'-') {
+            self.pos += 1;
+        }
+        self.take_while(|b| b.is_ascii_digit());
+        let digits = core::str::from_utf8(&self.text[start..self.pos]).unwrap_or_default();
+        let suffix_start = self.pos;
+        let suffix = self.take_while(|b| b.is_ascii_alphanumeric());
+        let out_of_range = || Self::error_at(start, "integer in range of its type");
+        let v = match suffix {
+            "u32" => ScVal::U32(digits.parse().map_err(|_| out_of_range())?),
+            "i32" => ScVal::I32(digits.parse().map_err(|_| out_of_range())?),
+            "u64" => ScVal::U64(digits.parse().map_err(|_| out_of_range())?),
+            "i64" => ScVal::I64(digits.parse().map_err(|_| out_of_range())?),
+            "u128" => ScVal::U128(UInt128Parts::from_str(digits).map_err(|_| out_of_range())?),
+            "i128" => ScVal::I128(Int128Parts::from_str(digits).map_err(|_| out_of_range())?),
+            "u256" => ScVal::U256(UInt256Parts::from_str(digits).map_err(|_| out_of_range())?),
+            "i256" => ScVal::I256(Int256Parts::from_str(digits).map_err(|_| out_of_range())?),
+            _ => return Err(Self::error_at(suffix_start, "integer type suffix")),
+        };
+        Ok(v)
+    }
+
+    fn bytes(&mut self) -> Result<ScVal, ScValParseError> {
+        self.pos += 2;
+        let start = self.pos;
+        let digits = self.take_while(|b| b.is_ascii_hexdigit());
+        let bytes =
+            hex::decode(digits).map_err(|_| Self::error_at(start, "pairs of hex digits"))?;
+        Ok(ScVal::Bytes(ScBytes(
+            bytes
+                .try_into()
+                .map_err(|_| Self::error_at(start, "bytes"))?,
+        )))
+    }
+
+    /// Consumes a quoted string, returning its unescaped bytes.
+    fn string(&mut self) -> Result<Vec<u8>, ScValParseError> {
+        self.expect(b'"', "`\"`")?;
+        let start = self.pos;
+        loop {
+            match self.text.get(self.pos) {
+                None => return Err(self.error("closing `\"`")),
+                Some(b'"') => break,
+                Some(b'\\') => self.pos += 2,
+                Some(_) => self.pos += 1,
+            }
+        }
+        let escaped = &self.text[start..self.pos];
+        self.pos += 1;
+        escape_bytes::unescape(escaped).map_err(|_| Self::error_at(start, "valid escape sequences"))
+    }
+
+    fn vec(&mut self) -> Result<ScVal, ScValParseError> {
+        self.expect(b'[', "`[`")?;
+        let start = self.pos;
+        let mut items = Vec::new();
+        if !self.eat(b']') {
+            loop {
+                items.push(self.value()?);
+                if !self.eat(b',') {
+                    self.expect(b']', "`,` or `]`")?;
+                    break;
+                }
+            }
+        }
+        Ok(ScVal::Vec(Some(ScVec(
+            items.try_into().map_err(|_| Self::error_at(start, "vec"))?,
+        ))))
+    }
+
+    fn map(&mut self) -> Result<ScMap, ScValParseError> {
+        self.expect(b'{', "`{`")?;
+        let start = self.pos;
+        let mut entries = Vec::new();
+        if !self.eat(b'}') {
+            loop {
+                let key = self.value()?;
+                self.expect(b':', "`:`")?;
+                let val = self.value()?;
+                entries.push(ScMapEntry { key, val });
+                if !self.eat(b',') {
+                    self.expect(b'}', "`,` or `}`")?;
+                    break;
+                }
+            }
+        }
+        Ok(ScMap(
+            entries
+                .try_into()
+                .map_err(|_| Self::error_at(start, "map"))?,
+        ))
+    }
+
+    fn sc_error(&mut self) -> Result<ScError, ScValParseError> {
+        self.expect(b':', "`:`")?;
+        let start = self.pos;
+        let type_ = self.word();
+        let type_ = ScErrorType::VARIANTS
+            .into_iter()
+            .find(|t| t.name() == type_)
+            .ok_or(Self::error_at(start, "error type"))?;
+        self.expect(b':', "`:`")?;
+        Ok(match type_ {
+            ScErrorType::Contract => ScError::Contract(self.number("u32")?),
+            ScErrorType::WasmVm => ScError::WasmVm(self.sc_error_code()?),
+            ScErrorType::Context => ScError::Context(self.sc_error_code()?),
+            ScErrorType::Storage => ScError::Storage(self.sc_error_code()?),
+            ScErrorType::Object => ScError::Object(self.sc_error_code()?),
+            ScErrorType::Crypto => ScError::Crypto(self.sc_error_code()?),
+            ScErrorType::Events => ScError::Events(self.sc_error_code()?),
+            ScErrorType::Budget => ScError::Budget(self.sc_error_code()?),
+            ScErrorType::Value => ScError::Value(self.sc_error_code()?),
+            ScErrorType::Auth => ScError::Auth(self.sc_error_code()?),
+        })
+    }
+
+    fn sc_error_code(&mut self) -> Result<ScErrorCode, ScValParseError> {
+        let start = self.pos;
+        let code = self.word();
+        ScErrorCode::VARIANTS
+            .into_iter()
+            .find(|c| c.name() == code)
+            .ok_or(Self::error_at(start, "error code"))
+    }
+
+    fn contract_instance(&mut self) -> Result<ScContractInstance, ScValParseError> {
+        self.expect(b'(', "`(`")?;
+        let start = self.pos;
+        let executable = match self.word() {
+            "wasm" => {
+                self.expect(b':', "`:`")?;
+                let start = self.pos;
+                let hash = self.take_while(|b| b.is_ascii_hexdigit());
+                ContractExecutable::Wasm(
+                    Hash::from_str(hash).map_err(|_| Self::error_at(start, "wasm hash"))?,
+                )
+            }
+            "stellar_asset" => ContractExecutable::StellarAsset,
+            #[cfg(feature = "cap_0085_executable_ref")]
+            "external_ref" => {
+                self.expect(b'(', "`(`")?;
+                let start = self.pos;
+                let executable_owner = ScAddress::from_str(self.word())
+                    .map_err(|_| Self::error_at(start, "address"))?;
+                self.expect(b',', "`,`")?;
+                let start = self.pos;
+                let tag = ScString(
+                    self.string()?
+                        .try_into()
+                        .map_err(|_| Self::error_at(start, "string"))?,
+                );
+                self.expect(b')', "`)`")?;
+                ContractExecutable::ExternalRef(ContractExecutableExternalRef {
+                    executable_owner,
+                    tag,
+                })
+            }
+            _ => return Err(Self::error_at(start, "contract executable")),
+        };
+        self.expect(b',', "`,`")?;
+        self.skip_whitespace();
+        let start = self.pos;
+        let ScVal::Map(storage) = self.value()? else {
+            return Err(Self::error_at(start, "map"));
+        };
+        self.expect(b')', "`)`")?;
+        Ok(ScContractInstance {
+            executable,
+            storage,
+        })
+    }
+}
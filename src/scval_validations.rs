@@ -1,6 +1,7 @@
 #![allow(clippy::missing_errors_doc)]
 
 use super::{Error, ScMap, ScVal};
+use core::cmp::Ordering;
 
 pub trait Validate {
     type Error;
@@ -67,9 +68,12 @@ impl Validate for ScMap {
             pair.key.validate()?;
             pair.val.validate()?;
         }
-        // Check the map is sorted by key, and there are no keys that are
-        // duplicates.
-        if self.windows(2).all(|w| w[0].key < w[1].key) {
+        // Check the map is sorted by key, in the order of the host, and there
+        // are no keys that are duplicates.
+        if self
+            .windows(2)
+            .all(|w| w[0].key.host_cmp(&w[1].key) == Ordering::Less)
+        {
             Ok(())
         } else {
             Err(Error::Invalid)
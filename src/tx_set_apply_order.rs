@@ -0,0 +1,260 @@
+use super::{
+    AccountId, Error, GeneralizedTransactionSet, Limits, ParallelTxsComponent, TransactionEnvelope,
+    TransactionPhase, TransactionSet, TxSetComponent, WriteXdr,
+};
+
+use sha2::{Digest, Sha256};
+use std::{cmp::Ordering, collections::BTreeMap};
+
+/// A group of transactions in a transaction set that share the same base fee.
+///
+/// A `base_fee` of `None` indicates that the transactions are not discounted
+/// and pay the fee they bid. A `base_fee` of `Some` indicates that the
+/// transactions are charged the base fee per operation, regardless of the fee
+/// they bid.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TxSetComponentBaseFee<'a> {
+    /// The index of the phase that contains the transactions.
+    pub phase: usize,
+    pub base_fee: Option<i64>,
+    pub txs: Vec<&'a TransactionEnvelope>,
+}
+
+impl GeneralizedTransactionSet {
+    /// Returns the transactions of the transaction set in the order that the
+    /// network applies them.
+    ///
+    /// Phases are applied in order, the classic phase first followed by the
+    /// Soroban phase. See [`TransactionPhase::apply_order`] for the order of
+    /// transactions within each phase.
+    ///
+    /// # Errors
+    ///
+    /// If there is any issue serializing to XDR.
+    pub fn apply_order(&self) -> Result<Vec<&TransactionEnvelope>, Error> {
+        let tx_set_hash = self.hash()?;
+        let mut txs = Vec::new();
+        match self {
+            GeneralizedTransactionSet::V1(v1) => {
+                for phase in &v1.phases {
+                    txs.extend(phase.apply_order(&tx_set_hash)?);
+                }
+            }
+        }
+        Ok(txs)
+    }
+
+    /// Returns the base fee of every component of the transaction set, and the
+    /// transactions the base fee applies to.
+    #[must_use]
+    pub fn base_fees(&self) -> Vec<TxSetComponentBaseFee<'_>> {
+        match self {
+            GeneralizedTransactionSet::V1(v1) => v1
+                .phases
+                .iter()
+                .enumerate()
+                .flat_map(|(i, phase)| phase.base_fees(i))
+                .collect(),
+        }
+    }
+}
+
+impl TransactionSet {
+    /// Returns the transactions of the legacy transaction set in the order
+    /// that the network applies them.
+    ///
+    /// See [`TransactionPhase::apply_order`] for how the order is determined
+    /// for sequentially applied transactions.
+    ///
+    /// # Errors
+    ///
+    /// If there is any issue serializing to XDR.
+    pub fn apply_order(&self) -> Result<Vec<&TransactionEnvelope>, Error> {
+        let tx_set_hash = self.hash()?;
+        apply_order_sequential(self.txs.iter(), &tx_set_hash)
+    }
+}
+
+impl TransactionPhase {
+    /// Returns the transactions of the phase in the order that the network
+    /// applies them, shuffled using the hash of the transaction set that
+    /// contains the phase.
+    ///
+    /// Transactions in a sequential phase are grouped by source account and
+    /// ordered by sequence number. The first transaction of every account
+    /// forms the first batch, the second transaction of every account forms
+    /// the second batch, and so on. Batches are applied in order, and the
+    /// transactions within a batch are ordered by the hash of their envelope
+    /// XOR'd with the transaction set hash.
+    ///
+    /// Transactions in a parallel phase are applied stage by stage. Within
+    /// each cluster transactions are ordered by the hash of their envelope
+    /// XOR'd with the transaction set hash, and clusters and stages are
+    /// ordered by their first transaction using the same ordering.
+    ///
+    /// # Errors
+    ///
+    /// If there is any issue serializing to XDR.
+    pub fn apply_order(&self, tx_set_hash: &[u8; 32]) -> Result<Vec<&TransactionEnvelope>, Error> {
+        match self {
+            TransactionPhase::V0(components) => {
+                apply_order_sequential(components.iter().flat_map(TxSetComponent::txs), tx_set_hash)
+            }
+            TransactionPhase::V1(parallel) => apply_order_parallel(parallel, tx_set_hash),
+        }
+    }
+
+    /// Returns the base fee of every component of the phase, and the
+    /// transactions the base fee applies to.
+    #[must_use]
+    pub fn base_fees(&self, phase: usize) -> Vec<TxSetComponentBaseFee<'_>> {
+        match self {
+            TransactionPhase::V0(components) => components
+                .iter()
+                .map(|c| match c {
+                    TxSetComponent::TxsetCompTxsMaybeDiscountedFee(c) => TxSetComponentBaseFee {
+                        phase,
+                        base_fee: c.base_fee,
+                        txs: c.txs.iter().collect(),
+                    },
+                })
+                .collect(),
+            TransactionPhase::V1(parallel) => [TxSetComponentBaseFee {
+                phase,
+                base_fee: parallel.base_fee,
+                txs: parallel
+                    .execution_stages
+                    .iter()
+                    .flat_map(|stage| stage.iter())
+                    .flat_map(|cluster| cluster.iter())
+                    .collect(),
+            }]
+            .into(),
+        }
+    }
+}
+
+impl TxSetComponent {
+    fn txs(&self) -> core::slice::Iter<'_, TransactionEnvelope> {
+        match self {
+            TxSetComponent::TxsetCompTxsMaybeDiscountedFee(c) => c.txs.iter(),
+        }
+    }
+}
+
+/// A transaction envelope paired with the hash of its XDR, the hash that the
+/// network uses for shuffling transactions into apply order.
+struct HashedTx<'a> {
+    full_hash: [u8; 32],
+    tx: &'a TransactionEnvelope,
+}
+
+impl<'a> HashedTx<'a> {
+    fn new(tx: &'a TransactionEnvelope) -> Result<Self, Error> {
+        let full_hash = Sha256::digest(tx.to_xdr(Limits::none())?).into();
+        Ok(Self { full_hash, tx })
+    }
+
+    fn cmp_xored(&self, other: &Self, tx_set_hash: &[u8; 32]) -> Ordering {
+        let l = self.full_hash.iter().zip(tx_set_hash).map(|(a, x)| a ^ x);
+        let r = other.full_hash.iter().zip(tx_set_hash).map(|(a, x)| a ^ x);
+        l.cmp(r)
+    }
+}
+
+fn apply_order_sequential<'a>(
+    txs: impl Iterator<Item = &'a TransactionEnvelope>,
+    tx_set_hash: &[u8; 32],
+) -> Result<Vec<&'a TransactionEnvelope>, Error> {
+    // Queue the transactions of each source account in sequence order.
+    let mut queues = BTreeMap::<AccountId, Vec<(i64, HashedTx<'a>)>>::new();
+    for tx in txs {
+        let (source, seq_num) = source_and_seq_num(tx);
+        queues
+            .entry(source)
+            .or_default()
+            .push((seq_num, HashedTx::new(tx)?));
+    }
+    let mut queues = queues
+        .into_values()
+        .map(|mut q| {
+            q.sort_by_key(|(seq_num, _)| *seq_num);
+            q.into_iter().map(|(_, tx)| tx)
+        })
+        .collect::<Vec<_>>();
+
+    // Take one transaction from each queue into a batch, until all queues are
+    // drained, shuffling each batch.
+    let mut ordered = Vec::new();
+    loop {
+        let mut batch = queues
+            .iter_mut()
+            .filter_map(Iterator::next)
+            .collect::<Vec<_>>();
+        if batch.is_empty() {
+            break;
+        }
+        batch.sort_by(|a, b| a.cmp_xored(b, tx_set_hash));
+        ordered.extend(batch.into_iter().map(|h| h.tx));
+    }
+    Ok(ordered)
+}
+
+fn apply_order_parallel<'a>(
+    parallel: &'a ParallelTxsComponent,
+    tx_set_hash: &[u8; 32],
+) -> Result<Vec<&'a TransactionEnvelope>, Error> {
+    // Compares groups of transactions by their first transaction. Groups are
+    // never empty in valid transaction sets, but an empty group is ordered
+    // first rather than causing a panic.
+    let cmp_first = |a: Option<&HashedTx>, b: Option<&HashedTx>| match (a, b) {
+        (Some(a), Some(b)) => a.cmp_xored(b, tx_set_hash),
+        (a, b) => a.is_some().cmp(&b.is_some()),
+    };
+    let mut stages = Vec::with_capacity(parallel.execution_stages.len());
+    for stage in &parallel.execution_stages {
+        let mut clusters = Vec::with_capacity(stage.len());
+        for cluster in stage.iter() {
+            let mut txs = cluster
+                .iter()
+                .map(HashedTx::new)
+                .collect::<Result<Vec<_>, _>>()?;
+            txs.sort_by(|a, b| a.cmp_xored(b, tx_set_hash));
+            clusters.push(txs);
+        }
+        clusters.sort_by(|a, b| cmp_first(a.first(), b.first()));
+        stages.push(clusters);
+    }
+    stages.sort_by(|a, b| {
+        cmp_first(
+            a.first().and_then(|c| c.first()),
+            b.first().and_then(|c| c.first()),
+        )
+    });
+    Ok(stages
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|h| h.tx)
+        .collect())
+}
+
+/// Returns the account that is the source of the sequence number consumed by
+/// the transaction, and the sequence number.
+fn source_and_seq_num(tx: &TransactionEnvelope) -> (AccountId, i64) {
+    match tx {
+        TransactionEnvelope::TxV0(e) => (
+            AccountId(super::PublicKey::PublicKeyTypeEd25519(
+                e.tx.source_account_ed25519.clone(),
+            )),
+            e.tx.seq_num.0,
+        ),
+        TransactionEnvelope::Tx(e) => (e.tx.source_account.clone().account_id(), e.tx.seq_num.0),
+        TransactionEnvelope::TxFeeBump(e) => match &e.tx.inner_tx {
+            super::FeeBumpTransactionInnerTx::Tx(inner) => (
+                inner.tx.source_account.clone().account_id(),
+                inner.tx.seq_num.0,
+            ),
+        },
+    }
+}
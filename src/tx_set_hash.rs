@@ -0,0 +1,69 @@
+use super::{GeneralizedTransactionSet, Limits, TransactionResultSet, TransactionSet, WriteXdr};
+
+use sha2::{Digest, Sha256};
+
+impl GeneralizedTransactionSet {
+    /// Computes the hash of the generalized transaction set.
+    ///
+    /// This is the value committed to in `StellarValue.tx_set_hash` of the
+    /// ledger header that applied the transaction set.
+    ///
+    /// # Returns
+    ///
+    /// The transaction set hash.
+    ///
+    /// # Errors
+    ///
+    /// If there is any issue serializing to XDR.
+    pub fn hash(&self) -> Result<[u8; 32], super::Error> {
+        let payload = self.to_xdr(Limits::none())?;
+        let hash = Sha256::digest(payload);
+        Ok(hash.into())
+    }
+}
+
+impl TransactionSet {
+    /// Computes the hash of the legacy transaction set.
+    ///
+    /// Unlike the generalized transaction set, the legacy transaction set is
+    /// not hashed as a whole. The hash is computed over the previous ledger
+    /// hash followed by each transaction envelope in the order they appear in
+    /// the set. This is the value committed to in `StellarValue.tx_set_hash`
+    /// for ledgers prior to protocol 20.
+    ///
+    /// # Returns
+    ///
+    /// The transaction set hash.
+    ///
+    /// # Errors
+    ///
+    /// If there is any issue serializing to XDR.
+    pub fn hash(&self) -> Result<[u8; 32], super::Error> {
+        let mut hasher = Sha256::new();
+        hasher.update(self.previous_ledger_hash.0);
+        for tx in &self.txs {
+            hasher.update(tx.to_xdr(Limits::none())?);
+        }
+        Ok(hasher.finalize().into())
+    }
+}
+
+impl TransactionResultSet {
+    /// Computes the hash of the transaction result set.
+    ///
+    /// This is the value committed to in `LedgerHeader.tx_set_result_hash` of
+    /// the ledger header that produced the results.
+    ///
+    /// # Returns
+    ///
+    /// The transaction result set hash.
+    ///
+    /// # Errors
+    ///
+    /// If there is any issue serializing to XDR.
+    pub fn hash(&self) -> Result<[u8; 32], super::Error> {
+        let payload = self.to_xdr(Limits::none())?;
+        let hash = Sha256::digest(payload);
+        Ok(hash.into())
+    }
+}
@@ -0,0 +1,215 @@
+use super::{Error, Limited, Limits, ReadXdr, ScEnvMetaEntry, ScMetaEntry, ScSpecEntry};
+
+use core::fmt;
+use std::io::Cursor;
+
+/// The name of the Wasm custom section containing a contract's
+/// `ScSpecEntry` stream.
+pub const SC_SPEC_WASM_SECTION: &str = "contractspecv0";
+
+/// The name of the Wasm custom section containing a contract's `ScMetaEntry`
+/// stream.
+pub const SC_META_WASM_SECTION: &str = "contractmetav0";
+
+/// The name of the Wasm custom section containing a contract's
+/// `ScEnvMetaEntry` stream.
+pub const SC_ENV_META_WASM_SECTION: &str = "contractenvmetav0";
+
+const WASM_MAGIC: [u8; 4] = *b"\0asm";
+const WASM_VERSION: u32 = 1;
+const WASM_CUSTOM_SECTION_ID: u8 = 0;
+
+/// Error returned when extracting XDR from the custom sections of a Wasm
+/// binary fails.
+#[derive(Debug, PartialEq)]
+pub enum WasmError {
+    /// The contents of a custom section could not be decoded.
+    Xdr(Error),
+    /// The bytes do not start with the Wasm magic number.
+    NotWasm,
+    /// The Wasm binary has a version other than 1.
+    UnsupportedVersion(u32),
+    /// The Wasm binary ends in the middle of a section.
+    UnexpectedEnd,
+    /// A LEB128 encoded integer is malformed or too large.
+    InvalidLeb128,
+    /// The name of a custom section is not valid UTF-8.
+    InvalidSectionName,
+    /// The Wasm binary does not contain the custom section.
+    MissingSection(&'static str),
+}
+
+impl fmt::Display for WasmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Xdr(e) => write!(f, "{e}"),
+            Self::NotWasm => write!(f, "not a wasm binary"),
+            Self::UnsupportedVersion(v) => write!(f, "unsupported wasm version {v}"),
+            Self::UnexpectedEnd => write!(f, "unexpected end of wasm binary"),
+            Self::InvalidLeb128 => write!(f, "invalid leb128 integer in wasm binary"),
+            Self::InvalidSectionName => write!(f, "invalid custom section name in wasm binary"),
+            Self::MissingSection(name) => {
+                write!(f, "wasm binary has no {name} custom section")
+            }
+        }
+    }
+}
+
+impl std::error::Error for WasmError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Xdr(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for WasmError {
+    fn from(e: Error) -> Self {
+        Self::Xdr(e)
+    }
+}
+
+/// A custom section of a Wasm binary.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WasmCustomSection<'a> {
+    pub name: &'a str,
+    pub data: &'a [u8],
+}
+
+/// Returns the custom sections of a Wasm binary, in the order they appear.
+///
+/// Only the structure of the binary's sections is parsed. The contents of
+/// sections other than custom sections are skipped without being validated.
+///
+/// # Errors
+///
+/// If the bytes are not a Wasm binary, or if the binary's sections are
+/// malformed.
+pub fn wasm_custom_sections(wasm: &[u8]) -> Result<Vec<WasmCustomSection<'_>>, WasmError> {
+    let mut r = wasm;
+    if take(&mut r, 4)? != WASM_MAGIC {
+        return Err(WasmError::NotWasm);
+    }
+    let mut version = [0u8; 4];
+    version.copy_from_slice(take(&mut r, 4)?);
+    let version = u32::from_le_bytes(version);
+    if version != WASM_VERSION {
+        return Err(WasmError::UnsupportedVersion(version));
+    }
+    let mut sections = Vec::new();
+    while let Some((&id, rest)) = r.split_first() {
+        r = rest;
+        let len = read_leb128_u32(&mut r)?;
+        let mut section = take(&mut r, len as usize)?;
+        if id == WASM_CUSTOM_SECTION_ID {
+            let name_len = read_leb128_u32(&mut section)?;
+            let name = take(&mut section, name_len as usize)?;
+            let name = core::str::from_utf8(name).map_err(|_| WasmError::InvalidSectionName)?;
+            sections.push(WasmCustomSection {
+                name,
+                data: section,
+            });
+        }
+    }
+    Ok(sections)
+}
+
+/// Returns the contents of every custom section with the name, concatenated
+/// in the order they appear, or `None` if there is no section with the name.
+fn wasm_custom_section(wasm: &[u8], name: &str) -> Result<Option<Vec<u8>>, WasmError> {
+    let mut data: Option<Vec<u8>> = None;
+    for s in wasm_custom_sections(wasm)? {
+        if s.name == name {
+            data.get_or_insert_with(Vec::new).extend_from_slice(s.data);
+        }
+    }
+    Ok(data)
+}
+
+/// Reads the stream of XDR values stored in the custom section with the name.
+fn read_wasm_section_stream<T: ReadXdr>(
+    wasm: &[u8],
+    name: &'static str,
+    limits: Limits,
+) -> Result<Vec<T>, WasmError> {
+    let data = wasm_custom_section(wasm, name)?.ok_or(WasmError::MissingSection(name))?;
+    let mut r = Limited::new(Cursor::new(data), limits);
+    let entries = T::read_xdr_iter(&mut r).collect::<Result<Vec<_>, _>>()?;
+    Ok(entries)
+}
+
+fn take<'a>(r: &mut &'a [u8], len: usize) -> Result<&'a [u8], WasmError> {
+    if r.len() < len {
+        return Err(WasmError::UnexpectedEnd);
+    }
+    let (taken, rest) = r.split_at(len);
+    *r = rest;
+    Ok(taken)
+}
+
+fn read_leb128_u32(r: &mut &[u8]) -> Result<u32, WasmError> {
+    let mut result = 0u32;
+    for i in 0..5 {
+        let (&b, rest) = r.split_first().ok_or(WasmError::UnexpectedEnd)?;
+        *r = rest;
+        // The fifth byte may only use the 4 bits remaining of the 32-bit value.
+        if i == 4 && b & 0xf0 != 0 {
+            return Err(WasmError::InvalidLeb128);
+        }
+        result |= u32::from(b & 0x7f) << (i * 7);
+        if b & 0x80 == 0 {
+            return Ok(result);
+        }
+    }
+    Err(WasmError::InvalidLeb128)
+}
+
+impl ScSpecEntry {
+    /// Reads the contract spec from the `contractspecv0` custom section of a
+    /// contract's Wasm binary.
+    ///
+    /// # Errors
+    ///
+    /// If the Wasm binary is malformed, if it has no `contractspecv0` custom
+    /// section, or if the section cannot be decoded.
+    pub fn from_wasm(wasm: &[u8], limits: Limits) -> Result<Vec<Self>, WasmError> {
+        read_wasm_section_stream(wasm, SC_SPEC_WASM_SECTION, limits)
+    }
+}
+
+impl ScMetaEntry {
+    /// Reads the contract meta from the `contractmetav0` custom section of a
+    /// contract's Wasm binary.
+    ///
+    /// A contract without a `contractmetav0` custom section has no meta, and
+    /// an empty list is returned.
+    ///
+    /// # Errors
+    ///
+    /// If the Wasm binary is malformed, or if the section cannot be decoded.
+    pub fn from_wasm(wasm: &[u8], limits: Limits) -> Result<Vec<Self>, WasmError> {
+        match read_wasm_section_stream(wasm, SC_META_WASM_SECTION, limits) {
+            Err(WasmError::MissingSection(_)) => Ok(Vec::new()),
+            r => r,
+        }
+    }
+}
+
+impl ScEnvMetaEntry {
+    /// Reads the environment meta, the interface version the contract was
+    /// built for, from the `contractenvmetav0` custom section of a contract's
+    /// Wasm binary.
+    ///
+    /// # Errors
+    ///
+    /// If the Wasm binary is malformed, if it has no `contractenvmetav0`
+    /// custom section or the section is empty, or if the section cannot be
+    /// decoded.
+    pub fn from_wasm(wasm: &[u8], limits: Limits) -> Result<Self, WasmError> {
+        read_wasm_section_stream(wasm, SC_ENV_META_WASM_SECTION, limits)?
+            .into_iter()
+            .next()
+            .ok_or(WasmError::MissingSection(SC_ENV_META_WASM_SECTION))
+    }
+}
@@ -0,0 +1,285 @@
+#![cfg(feature = "std")]
+
+use std::io::Cursor;
+
+use stellar_xdr::{
+    merge_buckets, AccountEntry, AccountId, BucketEntry, BucketError, BucketFileEntry,
+    BucketListType, BucketMetadata, BucketMetadataExt, ConfigSettingId, HotArchiveBucketEntry,
+    HotArchiveBucketReader, HotArchiveBucketWriter, LedgerEntry, LedgerEntryData, LedgerKey,
+    LedgerKeyAccount, LedgerKeyConfigSetting, LedgerKeyOffer, LedgerKeyTtl, Limits,
+    LiveBucketReader, LiveBucketWriter, PublicKey, Uint256,
+};
+
+use sha2::{Digest, Sha256};
+
+fn account_id(n: u8) -> AccountId {
+    AccountId(PublicKey::PublicKeyTypeEd25519(Uint256([n; 32])))
+}
+
+fn account_entry(n: u8, balance: i64) -> LedgerEntry {
+    LedgerEntry {
+        data: LedgerEntryData::Account(AccountEntry {
+            account_id: account_id(n),
+            balance,
+            ..AccountEntry::default()
+        }),
+        ..LedgerEntry::default()
+    }
+}
+
+fn account_key(n: u8) -> LedgerKey {
+    LedgerKey::Account(LedgerKeyAccount {
+        account_id: account_id(n),
+    })
+}
+
+fn metadata(ledger_version: u32, bucket_list_type: Option<BucketListType>) -> BucketMetadata {
+    BucketMetadata {
+        ledger_version,
+        ext: match bucket_list_type {
+            None => BucketMetadataExt::V0,
+            Some(t) => BucketMetadataExt::V1(t),
+        },
+    }
+}
+
+fn live_bucket(metadata: Option<BucketMetadata>, entries: &[BucketEntry]) -> Vec<u8> {
+    let mut writer = LiveBucketWriter::new(Vec::new(), Limits::none(), metadata).unwrap();
+    for e in entries {
+        writer.push(e).unwrap();
+    }
+    writer.finish().unwrap().0
+}
+
+fn merge_live(
+    old: &[u8],
+    new: &[u8],
+    keep_tombstone_entries: bool,
+) -> Result<(Vec<u8>, [u8; 32]), BucketError> {
+    merge_buckets(
+        LiveBucketReader::new(Cursor::new(old), Limits::none())?,
+        LiveBucketReader::new(Cursor::new(new), Limits::none())?,
+        Vec::new(),
+        Limits::none(),
+        keep_tombstone_entries,
+    )
+}
+
+#[test]
+fn test_entry_id_cmp() {
+    let keys = [
+        account_key(1),
+        account_key(2),
+        LedgerKey::Offer(LedgerKeyOffer {
+            seller_id: account_id(1),
+            offer_id: 2,
+        }),
+        LedgerKey::Offer(LedgerKeyOffer {
+            seller_id: account_id(1),
+            offer_id: 10,
+        }),
+        LedgerKey::Offer(LedgerKeyOffer {
+            seller_id: account_id(2),
+            offer_id: 1,
+        }),
+        LedgerKey::ConfigSetting(LedgerKeyConfigSetting {
+            config_setting_id: ConfigSettingId::ContractMaxSizeBytes,
+        }),
+        LedgerKey::ConfigSetting(LedgerKeyConfigSetting {
+            config_setting_id: ConfigSettingId::ContractComputeV0,
+        }),
+        LedgerKey::Ttl(LedgerKeyTtl {
+            key_hash: [0; 32].into(),
+        }),
+    ];
+    for (i, l) in keys.iter().enumerate() {
+        for (j, r) in keys.iter().enumerate() {
+            assert_eq!(l.entry_id_cmp(r), i.cmp(&j), "{l:?} {r:?}");
+        }
+    }
+}
+
+#[test]
+fn test_merge_live() -> Result<(), BucketError> {
+    let old = live_bucket(
+        Some(metadata(22, None)),
+        &[
+            BucketEntry::Initentry(account_entry(1, 1)),
+            BucketEntry::Initentry(account_entry(2, 1)),
+            BucketEntry::Deadentry(account_key(3)),
+            BucketEntry::Liveentry(account_entry(4, 1)),
+            BucketEntry::Liveentry(account_entry(5, 1)),
+            BucketEntry::Initentry(account_entry(7, 1)),
+        ],
+    );
+    let new = live_bucket(
+        Some(metadata(23, Some(BucketListType::Live))),
+        &[
+            BucketEntry::Liveentry(account_entry(1, 2)),
+            BucketEntry::Deadentry(account_key(2)),
+            BucketEntry::Initentry(account_entry(3, 2)),
+            BucketEntry::Deadentry(account_key(4)),
+            BucketEntry::Initentry(account_entry(6, 2)),
+        ],
+    );
+
+    let (merged, hash) = merge_live(&old, &new, true)?;
+    let expected = live_bucket(
+        Some(metadata(23, Some(BucketListType::Live))),
+        &[
+            // Old INIT and new LIVE merge into INIT with the new value.
+            BucketEntry::Initentry(account_entry(1, 2)),
+            // Old INIT and new DEAD annihilate, leaving no entry for 2.
+            // Old DEAD and new INIT merge into LIVE with the new value.
+            BucketEntry::Liveentry(account_entry(3, 2)),
+            // Otherwise the new entry is kept.
+            BucketEntry::Deadentry(account_key(4)),
+            // Entries in only one bucket are kept.
+            BucketEntry::Liveentry(account_entry(5, 1)),
+            BucketEntry::Initentry(account_entry(6, 2)),
+            BucketEntry::Initentry(account_entry(7, 1)),
+        ],
+    );
+    assert_eq!(merged, expected);
+    assert_eq!(hash, <[u8; 32]>::from(Sha256::digest(&expected)));
+
+    // The merged bucket reads back and has the expected hash.
+    let mut reader = LiveBucketReader::new(Cursor::new(&merged), Limits::none())?;
+    assert_eq!(reader.by_ref().count(), 6);
+    reader.verify_hash(&hash.into())?;
+
+    // Tombstones are dropped when not kept.
+    let (merged, _) = merge_live(&old, &new, false)?;
+    let reader = LiveBucketReader::new(Cursor::new(&merged), Limits::none())?;
+    let keys = reader
+        .map(|e| e.map(|e| e.key().unwrap()))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(
+        keys,
+        [1, 3, 5, 6, 7].map(account_key),
+        "dead entry for 4 is dropped"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_merge_live_metadata() -> Result<(), BucketError> {
+    let entries = [BucketEntry::Liveentry(account_entry(1, 1))];
+
+    // Buckets before protocol 11 have no metadata.
+    let old = live_bucket(None, &entries);
+    let (merged, _) = merge_live(&old, &old, true)?;
+    assert_eq!(merged, old);
+
+    // Buckets before protocol 23 do not record the bucket list type.
+    let new = live_bucket(Some(metadata(12, None)), &entries);
+    let (merged, _) = merge_live(&old, &new, true)?;
+    assert_eq!(merged, new);
+    Ok(())
+}
+
+#[test]
+fn test_merge_live_empty() -> Result<(), BucketError> {
+    let old = live_bucket(None, &[BucketEntry::Initentry(account_entry(1, 1))]);
+    let new = live_bucket(None, &[BucketEntry::Deadentry(account_key(1))]);
+    let (merged, hash) = merge_live(&old, &new, true)?;
+    assert_eq!(merged, Vec::<u8>::new());
+    assert_eq!(hash, [0; 32]);
+    Ok(())
+}
+
+#[test]
+fn test_merge_live_invalid_init() {
+    let old = live_bucket(None, &[BucketEntry::Liveentry(account_entry(1, 1))]);
+    let new = live_bucket(None, &[BucketEntry::Initentry(account_entry(1, 2))]);
+    assert_eq!(
+        merge_live(&old, &new, true),
+        Err(BucketError::InvalidInitEntry {
+            key: Box::new(account_key(1)),
+        })
+    );
+}
+
+#[test]
+fn test_merge_hot_archive() -> Result<(), BucketError> {
+    let bucket = |entries: &[HotArchiveBucketEntry]| {
+        let mut writer = HotArchiveBucketWriter::new(
+            Vec::new(),
+            Limits::none(),
+            Some(metadata(23, Some(BucketListType::HotArchive))),
+        )
+        .unwrap();
+        for e in entries {
+            writer.push(e).unwrap();
+        }
+        writer.finish().unwrap().0
+    };
+    let old = bucket(&[
+        HotArchiveBucketEntry::Archived(account_entry(1, 1)),
+        HotArchiveBucketEntry::Archived(account_entry(2, 1)),
+    ]);
+    let new = bucket(&[
+        HotArchiveBucketEntry::Live(account_key(1)),
+        HotArchiveBucketEntry::Archived(account_entry(2, 2)),
+        HotArchiveBucketEntry::Archived(account_entry(3, 2)),
+    ]);
+    let merge = |keep_tombstone_entries| {
+        merge_buckets(
+            HotArchiveBucketReader::new(Cursor::new(&old), Limits::none())?,
+            HotArchiveBucketReader::new(Cursor::new(&new), Limits::none())?,
+            Vec::new(),
+            Limits::none(),
+            keep_tombstone_entries,
+        )
+    };
+
+    let (merged, _) = merge(true)?;
+    assert_eq!(
+        merged,
+        bucket(&[
+            HotArchiveBucketEntry::Live(account_key(1)),
+            HotArchiveBucketEntry::Archived(account_entry(2, 2)),
+            HotArchiveBucketEntry::Archived(account_entry(3, 2)),
+        ])
+    );
+    let (merged, _) = merge(false)?;
+    assert_eq!(
+        merged,
+        bucket(&[
+            HotArchiveBucketEntry::Archived(account_entry(2, 2)),
+            HotArchiveBucketEntry::Archived(account_entry(3, 2)),
+        ])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_writer_errors() -> Result<(), BucketError> {
+    assert!(matches!(
+        LiveBucketWriter::new(
+            Vec::new(),
+            Limits::none(),
+            Some(metadata(23, Some(BucketListType::HotArchive))),
+        ),
+        Err(BucketError::BucketListTypeMismatch { .. })
+    ));
+
+    let mut writer = LiveBucketWriter::new(Vec::new(), Limits::none(), None)?;
+    writer.push(&BucketEntry::Liveentry(account_entry(2, 1)))?;
+    assert_eq!(
+        writer.push(&BucketEntry::Deadentry(account_key(1))),
+        Err(BucketError::UnsortedKeys {
+            previous: Box::new(account_key(2)),
+            key: Box::new(account_key(1)),
+        })
+    );
+    assert_eq!(
+        writer.push(&BucketEntry::Metaentry(metadata(23, None))),
+        Err(BucketError::MisplacedMetadata)
+    );
+    assert_eq!(
+        writer.finish()?.0,
+        live_bucket(None, &[BucketEntry::Liveentry(account_entry(2, 1))])
+    );
+    Ok(())
+}
@@ -0,0 +1,198 @@
+#![cfg(feature = "std")]
+
+use std::io::Cursor;
+
+use stellar_xdr::{
+    AccountEntry, AccountId, BucketEntry, BucketError, BucketListType, BucketMetadata,
+    BucketMetadataExt, Error, Hash, HotArchiveBucketEntry, HotArchiveBucketReader, LedgerEntry,
+    LedgerEntryData, LedgerKey, LedgerKeyAccount, Limits, LiveBucketReader, PublicKey, Uint256,
+    WriteXdr,
+};
+
+use sha2::{Digest, Sha256};
+
+fn account_id(n: u8) -> AccountId {
+    AccountId(PublicKey::PublicKeyTypeEd25519(Uint256([n; 32])))
+}
+
+fn account_entry(n: u8) -> LedgerEntry {
+    LedgerEntry {
+        data: LedgerEntryData::Account(AccountEntry {
+            account_id: account_id(n),
+            ..AccountEntry::default()
+        }),
+        ..LedgerEntry::default()
+    }
+}
+
+fn account_key(n: u8) -> LedgerKey {
+    LedgerKey::Account(LedgerKeyAccount {
+        account_id: account_id(n),
+    })
+}
+
+fn metadata(bucket_list_type: Option<BucketListType>) -> BucketMetadata {
+    BucketMetadata {
+        ledger_version: 23,
+        ext: match bucket_list_type {
+            None => BucketMetadataExt::V0,
+            Some(t) => BucketMetadataExt::V1(t),
+        },
+    }
+}
+
+fn framed(entries: &[impl WriteXdr]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for e in entries {
+        let xdr = e.to_xdr(Limits::none()).unwrap();
+        let mark = u32::try_from(xdr.len()).unwrap() | 0x8000_0000;
+        bytes.extend(mark.to_be_bytes());
+        bytes.extend(xdr);
+    }
+    bytes
+}
+
+#[test]
+fn test_read_live() -> Result<(), BucketError> {
+    let entries = vec![
+        BucketEntry::Metaentry(metadata(Some(BucketListType::Live))),
+        BucketEntry::Initentry(account_entry(1)),
+        BucketEntry::Deadentry(account_key(2)),
+        BucketEntry::Liveentry(account_entry(3)),
+    ];
+    let bytes = framed(&entries);
+    let expected_hash: [u8; 32] = Sha256::digest(&bytes).into();
+
+    let mut reader = LiveBucketReader::new(Cursor::new(bytes), Limits::none())?;
+    assert_eq!(reader.ledger_version(), Some(23));
+    assert_eq!(reader.bucket_list_type(), BucketListType::Live);
+    let read = reader.by_ref().collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(read, entries[1..]);
+    assert_eq!(reader.hash(), expected_hash);
+    reader.verify_hash(&Hash(expected_hash))?;
+    assert!(matches!(
+        reader.verify_hash(&Hash([1; 32])),
+        Err(BucketError::HashMismatch { .. })
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_read_without_metadata() -> Result<(), BucketError> {
+    let entries = vec![
+        BucketEntry::Liveentry(account_entry(1)),
+        BucketEntry::Liveentry(account_entry(2)),
+    ];
+    let mut reader = LiveBucketReader::new(Cursor::new(framed(&entries)), Limits::none())?;
+    assert_eq!(reader.metadata(), None);
+    assert_eq!(reader.by_ref().collect::<Result<Vec<_>, _>>()?, entries);
+    Ok(())
+}
+
+#[test]
+fn test_read_empty() -> Result<(), BucketError> {
+    let mut reader = LiveBucketReader::new(Cursor::new(vec![]), Limits::none())?;
+    assert_eq!(reader.next(), None);
+    assert_eq!(reader.hash(), [0; 32]);
+    Ok(())
+}
+
+#[test]
+fn test_read_hot_archive() -> Result<(), BucketError> {
+    let entries = vec![
+        HotArchiveBucketEntry::Metaentry(metadata(Some(BucketListType::HotArchive))),
+        HotArchiveBucketEntry::Archived(account_entry(1)),
+        HotArchiveBucketEntry::Live(account_key(2)),
+    ];
+    let mut reader = HotArchiveBucketReader::new(Cursor::new(framed(&entries)), Limits::none())?;
+    assert_eq!(
+        reader.metadata().and_then(BucketMetadata::bucket_list_type),
+        Some(BucketListType::HotArchive)
+    );
+    assert_eq!(
+        reader.by_ref().collect::<Result<Vec<_>, _>>()?,
+        entries[1..]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_read_bucket_list_type_mismatch() {
+    let entries = [HotArchiveBucketEntry::Metaentry(metadata(Some(
+        BucketListType::HotArchive,
+    )))];
+    assert!(matches!(
+        LiveBucketReader::new(Cursor::new(framed(&entries)), Limits::none()),
+        Err(BucketError::BucketListTypeMismatch {
+            expected: BucketListType::Live,
+            actual: BucketListType::HotArchive,
+        })
+    ));
+}
+
+#[test]
+fn test_read_unsorted() -> Result<(), BucketError> {
+    let entries = vec![
+        BucketEntry::Metaentry(metadata(None)),
+        BucketEntry::Liveentry(account_entry(2)),
+        BucketEntry::Deadentry(account_key(1)),
+        BucketEntry::Liveentry(account_entry(3)),
+    ];
+    let mut reader = LiveBucketReader::new(Cursor::new(framed(&entries)), Limits::none())?;
+    assert_eq!(reader.next(), Some(Ok(entries[1].clone())));
+    assert_eq!(
+        reader.next(),
+        Some(Err(BucketError::UnsortedKeys {
+            previous: Box::new(account_key(2)),
+            key: Box::new(account_key(1)),
+        }))
+    );
+    // Iteration stops after an error.
+    assert_eq!(reader.next(), None);
+
+    // Duplicate keys are also unsorted.
+    let entries = vec![
+        BucketEntry::Liveentry(account_entry(1)),
+        BucketEntry::Deadentry(account_key(1)),
+    ];
+    let reader = LiveBucketReader::new(Cursor::new(framed(&entries)), Limits::none())?;
+    assert!(matches!(
+        reader.collect::<Result<Vec<_>, _>>(),
+        Err(BucketError::UnsortedKeys { .. })
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_read_misplaced_metadata() -> Result<(), BucketError> {
+    let entries = vec![
+        BucketEntry::Liveentry(account_entry(1)),
+        BucketEntry::Metaentry(metadata(None)),
+    ];
+    let reader = LiveBucketReader::new(Cursor::new(framed(&entries)), Limits::none())?;
+    assert_eq!(
+        reader.collect::<Result<Vec<_>, _>>(),
+        Err(BucketError::MisplacedMetadata)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_read_frame_errors() {
+    let entries = vec![BucketEntry::Liveentry(account_entry(1))];
+
+    // A record mark with a length that does not match the entry.
+    let mut bytes = framed(&entries);
+    bytes[3] += 4;
+    let reader = LiveBucketReader::new(Cursor::new(bytes), Limits::none());
+    assert!(matches!(
+        reader,
+        Err(BucketError::Xdr(Error::LengthMismatch))
+    ));
+
+    // A truncated entry.
+    let mut bytes = framed(&entries);
+    bytes.truncate(bytes.len() - 1);
+    let reader = LiveBucketReader::new(Cursor::new(bytes), Limits::none());
+    assert!(matches!(reader, Err(BucketError::Xdr(Error::Io(_)))));
+}
@@ -0,0 +1,97 @@
+#![cfg(feature = "std")]
+
+use stellar_xdr::{
+    ContractDataDurability, ContractDataEntry, ContractExecutable, ExtensionPoint, Hash, LedgerKey,
+    LedgerKeyContractData, ScAddress, ScContractInstance, ScMap, ScNonceKey, ScVal,
+};
+
+const ACCOUNT: &str = "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ";
+const CONTRACT: &str = "CAAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQC526";
+
+fn symbol(s: &str) -> ScVal {
+    ScVal::Symbol(s.try_into().unwrap())
+}
+
+#[test]
+fn test_keys() {
+    let contract: ScAddress = CONTRACT.parse().unwrap();
+    let account: ScAddress = ACCOUNT.parse().unwrap();
+
+    let key: LedgerKey = LedgerKeyContractData::contract_instance(contract.clone()).into();
+    assert_eq!(
+        key,
+        LedgerKey::ContractData(LedgerKeyContractData {
+            contract: contract.clone(),
+            key: ScVal::LedgerKeyContractInstance,
+            durability: ContractDataDurability::Persistent,
+        })
+    );
+
+    assert_eq!(
+        LedgerKeyContractData::nonce(account.clone(), -3),
+        LedgerKeyContractData {
+            contract: account.clone(),
+            key: ScVal::LedgerKeyNonce(ScNonceKey { nonce: -3 }),
+            durability: ContractDataDurability::Temporary,
+        }
+    );
+
+    assert_eq!(
+        LedgerKeyContractData::token_balance(contract.clone(), account.clone()),
+        LedgerKeyContractData {
+            contract,
+            key: ScVal::Vec(Some(
+                vec![symbol("Balance"), ScVal::Address(account)]
+                    .try_into()
+                    .unwrap()
+            )),
+            durability: ContractDataDurability::Persistent,
+        }
+    );
+}
+
+#[test]
+fn test_instance_storage() {
+    let storage = ScMap::sorted_from([
+        (symbol("Admin"), ScVal::Address(ACCOUNT.parse().unwrap())),
+        (symbol("Decimals"), ScVal::U32(7)),
+        (ScVal::U32(1), ScVal::Bool(true)),
+        (ScVal::I64(-1), ScVal::Void),
+    ])
+    .unwrap();
+    let instance = ScContractInstance {
+        executable: ContractExecutable::Wasm(Hash([1; 32])),
+        storage: Some(storage.clone()),
+    };
+    let entry = ContractDataEntry {
+        ext: ExtensionPoint::V0,
+        contract: CONTRACT.parse().unwrap(),
+        key: ScVal::LedgerKeyContractInstance,
+        durability: ContractDataDurability::Persistent,
+        val: ScVal::ContractInstance(instance.clone()),
+    };
+
+    assert_eq!(entry.contract_instance(), Some(&instance));
+    assert_eq!(entry.instance_storage(), Some(&storage));
+    assert_eq!(
+        instance.storage_get(&symbol("Decimals")),
+        Some(&ScVal::U32(7))
+    );
+    assert_eq!(storage.get_by_key(&ScVal::U32(1)), Some(&ScVal::Bool(true)));
+    assert_eq!(storage.get_by_key(&ScVal::I64(-1)), Some(&ScVal::Void));
+    assert_eq!(storage.get_by_key(&symbol("Missing")), None);
+    assert_eq!(storage.get_by_key(&ScVal::U32(2)), None);
+
+    let no_storage = ScContractInstance {
+        executable: ContractExecutable::StellarAsset,
+        storage: None,
+    };
+    assert_eq!(no_storage.storage_get(&symbol("Admin")), None);
+
+    let other = ContractDataEntry {
+        key: symbol("Admin"),
+        ..entry
+    };
+    assert_eq!(other.contract_instance(), None);
+    assert_eq!(other.instance_storage(), None);
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "std")]
+
+use bytes_lit::bytes;
+
+use stellar_xdr::{
+    Asset, ContractExecutable, ContractIdPreimage, CreateContractArgs, Hash, HashIdPreimage,
+    HashIdPreimageSorobanAuthorization, HashIdPreimageSorobanAuthorizationWithAddress, LedgerKey,
+    LedgerKeyContractCode, LedgerKeyTtl, ScVal, SorobanAddressCredentials,
+    SorobanAuthorizationEntry, SorobanAuthorizedFunction, SorobanAuthorizedInvocation,
+    SorobanCredentials,
+};
+
+const NETWORK_ID: [u8; 32] =
+    // SHA256("Test SDF Network ; September 2015")
+    bytes!(0xcee0302d59844d32bdca915c8203dd44b33fbb7edc19051ea37abedf28ecd472);
+
+#[test]
+fn test_contract_id() -> Result<(), stellar_xdr::Error> {
+    // The Stellar Asset Contract for the native asset on testnet.
+    let id = ContractIdPreimage::Asset(Asset::Native).contract_id(NETWORK_ID)?;
+    assert_eq!(
+        id.to_string(),
+        "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_ttl_key() -> Result<(), stellar_xdr::Error> {
+    let key = LedgerKey::ContractCode(LedgerKeyContractCode {
+        hash: Hash([1; 32]),
+    });
+    let expected = bytes!(0xb23a7523df5007be33f86b6eeba74debadbb3743806af27f5c1cdf9d3b6e6cfe);
+    assert_eq!(key.ttl_key_hash()?, expected);
+    assert_eq!(
+        key.ttl_key()?,
+        LedgerKeyTtl {
+            key_hash: Hash(expected)
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn test_auth_signature_payload() -> Result<(), stellar_xdr::Error> {
+    let credentials = SorobanAddressCredentials {
+        address: "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ".parse()?,
+        nonce: 1,
+        signature_expiration_ledger: 2,
+        signature: ScVal::Void,
+    };
+    let mut entry = SorobanAuthorizationEntry {
+        credentials: SorobanCredentials::SourceAccount,
+        root_invocation: SorobanAuthorizedInvocation {
+            function: SorobanAuthorizedFunction::CreateContractHostFn(CreateContractArgs {
+                contract_id_preimage: ContractIdPreimage::Asset(Asset::Native),
+                executable: ContractExecutable::StellarAsset,
+            }),
+            sub_invocations: [].try_into()?,
+        },
+    };
+    assert_eq!(entry.signature_payload(NETWORK_ID)?, None);
+
+    entry.credentials = SorobanCredentials::Address(credentials.clone());
+    let preimage = HashIdPreimage::SorobanAuthorization(HashIdPreimageSorobanAuthorization {
+        network_id: Hash(NETWORK_ID),
+        nonce: 1,
+        signature_expiration_ledger: 2,
+        invocation: entry.root_invocation.clone(),
+    });
+    assert_eq!(entry.signature_payload(NETWORK_ID)?, Some(preimage.hash()?));
+
+    // The V2 credentials also commit to the address.
+    entry.credentials = SorobanCredentials::AddressV2(credentials.clone());
+    let preimage = HashIdPreimage::SorobanAuthorizationWithAddress(
+        HashIdPreimageSorobanAuthorizationWithAddress {
+            network_id: Hash(NETWORK_ID),
+            nonce: 1,
+            signature_expiration_ledger: 2,
+            address: credentials.address,
+            invocation: entry.root_invocation.clone(),
+        },
+    );
+    assert_eq!(entry.signature_payload(NETWORK_ID)?, Some(preimage.hash()?));
+    Ok(())
+}
@@ -0,0 +1,140 @@
+#![cfg(feature = "std")]
+
+use std::io::Cursor;
+
+use stellar_xdr::{
+    LedgerCloseMeta, LedgerCloseMetaBatch, LedgerCloseMetaBatchError, LedgerCloseMetaBatchReader,
+    LedgerCloseMetaBatchWriter, LedgerCloseMetaV0, LedgerCloseMetaV2, Limits, WriteXdr,
+};
+
+fn meta_v0(ledger_seq: u32) -> LedgerCloseMeta {
+    let mut m = LedgerCloseMetaV0::default();
+    m.ledger_header.header.ledger_seq = ledger_seq;
+    LedgerCloseMeta::V0(m)
+}
+
+fn meta_v2(ledger_seq: u32) -> LedgerCloseMeta {
+    let mut m = LedgerCloseMetaV2::default();
+    m.ledger_header.header.ledger_seq = ledger_seq;
+    LedgerCloseMeta::V2(m)
+}
+
+fn batch(start_sequence: u32, end_sequence: u32, metas: Vec<LedgerCloseMeta>) -> Vec<u8> {
+    LedgerCloseMetaBatch {
+        start_sequence,
+        end_sequence,
+        ledger_close_metas: metas.try_into().unwrap(),
+    }
+    .to_xdr(Limits::none())
+    .unwrap()
+}
+
+#[test]
+fn test_read() -> Result<(), LedgerCloseMetaBatchError> {
+    let metas = vec![meta_v0(10), meta_v2(11), meta_v2(12)];
+    let bytes = batch(10, 12, metas.clone());
+
+    let mut reader = LedgerCloseMetaBatchReader::new(Cursor::new(bytes), Limits::none())?;
+    assert_eq!(reader.start_sequence(), 10);
+    assert_eq!(reader.end_sequence(), 12);
+    assert_eq!(reader.remaining(), 3);
+    assert_eq!(reader.next(), Some(Ok(metas[0].clone())));
+    assert_eq!(reader.remaining(), 2);
+    let rest = reader.by_ref().collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(rest, metas[1..]);
+    assert_eq!(reader.next(), None);
+    Ok(())
+}
+
+#[test]
+fn test_read_length_mismatch() {
+    let bytes = batch(10, 12, vec![meta_v0(10), meta_v0(11)]);
+    assert!(matches!(
+        LedgerCloseMetaBatchReader::new(Cursor::new(bytes), Limits::none()),
+        Err(LedgerCloseMetaBatchError::LengthMismatch {
+            start_sequence: 10,
+            end_sequence: 12,
+            len: 2,
+        })
+    ));
+}
+
+#[test]
+fn test_read_sequence_mismatch() -> Result<(), LedgerCloseMetaBatchError> {
+    let bytes = batch(10, 12, vec![meta_v0(10), meta_v0(12), meta_v0(11)]);
+    let mut reader = LedgerCloseMetaBatchReader::new(Cursor::new(bytes), Limits::none())?;
+    assert_eq!(reader.next(), Some(Ok(meta_v0(10))));
+    assert_eq!(
+        reader.next(),
+        Some(Err(LedgerCloseMetaBatchError::SequenceMismatch {
+            expected: 11,
+            actual: 12,
+        }))
+    );
+    // Iteration stops after an error.
+    assert_eq!(reader.next(), None);
+    Ok(())
+}
+
+#[test]
+fn test_read_truncated() -> Result<(), LedgerCloseMetaBatchError> {
+    let mut bytes = batch(10, 11, vec![meta_v0(10), meta_v0(11)]);
+    bytes.truncate(bytes.len() - 4);
+    let reader = LedgerCloseMetaBatchReader::new(Cursor::new(bytes), Limits::none())?;
+    let results = reader.collect::<Vec<_>>();
+    assert_eq!(results.len(), 2);
+    assert!(results[0].is_ok());
+    assert!(matches!(results[1], Err(LedgerCloseMetaBatchError::Xdr(_))));
+    Ok(())
+}
+
+#[test]
+fn test_write() -> Result<(), LedgerCloseMetaBatchError> {
+    let metas = vec![meta_v0(10), meta_v2(11), meta_v2(12)];
+
+    let mut writer = LedgerCloseMetaBatchWriter::new(Vec::new(), Limits::none(), 10, 12)?;
+    for m in &metas {
+        assert_eq!(writer.next_sequence(), Some(m.ledger_seq()));
+        writer.push(m)?;
+    }
+    assert_eq!(writer.next_sequence(), None);
+    assert_eq!(
+        writer.push(&meta_v0(13)),
+        Err(LedgerCloseMetaBatchError::SequenceOutOfRange {
+            start_sequence: 10,
+            end_sequence: 12,
+            sequence: 13,
+        })
+    );
+    let bytes = writer.finish()?;
+
+    assert_eq!(bytes, batch(10, 12, metas));
+    Ok(())
+}
+
+#[test]
+fn test_write_errors() -> Result<(), LedgerCloseMetaBatchError> {
+    assert!(matches!(
+        LedgerCloseMetaBatchWriter::new(Vec::new(), Limits::none(), 12, 10),
+        Err(LedgerCloseMetaBatchError::LengthMismatch { .. })
+    ));
+
+    let mut writer = LedgerCloseMetaBatchWriter::new(Vec::new(), Limits::none(), 10, 12)?;
+    writer.push(&meta_v0(10))?;
+    assert_eq!(
+        writer.push(&meta_v0(12)),
+        Err(LedgerCloseMetaBatchError::SequenceMismatch {
+            expected: 11,
+            actual: 12,
+        })
+    );
+    writer.push(&meta_v0(11))?;
+    assert!(matches!(
+        writer.finish(),
+        Err(LedgerCloseMetaBatchError::Incomplete {
+            end_sequence: 12,
+            next_sequence: 12,
+        })
+    ));
+    Ok(())
+}
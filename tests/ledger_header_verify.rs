@@ -0,0 +1,155 @@
+#![cfg(feature = "std")]
+
+use stellar_xdr::{
+    verify_ledger_header_chain, GeneralizedTransactionSet, Hash, LedgerHeader,
+    LedgerHeaderChainVerifier, LedgerHeaderHistoryEntry, LedgerHeaderHistoryEntryExt,
+    LedgerHeaderVerifyError, Limits, TransactionPhase, TransactionResultSet, TransactionSetV1,
+    VecM, WriteXdr,
+};
+
+use sha2::{Digest, Sha256};
+
+fn entry(ledger_seq: u32, previous_ledger_hash: Hash) -> LedgerHeaderHistoryEntry {
+    let header = LedgerHeader {
+        ledger_seq,
+        previous_ledger_hash,
+        ledger_version: 23,
+        ..Default::default()
+    };
+    LedgerHeaderHistoryEntry {
+        hash: Hash(header.hash().unwrap()),
+        header,
+        ext: LedgerHeaderHistoryEntryExt::V0,
+    }
+}
+
+fn chain(len: u32) -> Vec<LedgerHeaderHistoryEntry> {
+    let mut entries: Vec<LedgerHeaderHistoryEntry> = Vec::new();
+    for ledger_seq in 100..100 + len {
+        let previous_hash = entries.last().map(|e| e.hash.clone()).unwrap_or_default();
+        entries.push(entry(ledger_seq, previous_hash));
+    }
+    entries
+}
+
+#[test]
+fn test_ledger_header_hash() -> Result<(), stellar_xdr::Error> {
+    let header = LedgerHeader::default();
+    let expected: [u8; 32] = Sha256::digest(header.to_xdr(Limits::none())?).into();
+    assert_eq!(header.hash()?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_valid_chain() {
+    let entries = chain(5);
+    assert_eq!(verify_ledger_header_chain(&entries), Ok(()));
+
+    let mut verifier = LedgerHeaderChainVerifier::new();
+    assert_eq!(verifier.last(), None);
+    for e in &entries {
+        verifier.push(e).unwrap();
+    }
+    assert_eq!(verifier.last(), Some((104, &entries[4].hash)));
+}
+
+#[test]
+fn test_empty_chain() {
+    assert_eq!(
+        verify_ledger_header_chain(Vec::<LedgerHeaderHistoryEntry>::new()),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_header_hash_mismatch() {
+    let mut entries = chain(3);
+    entries[1].header.total_coins = 1;
+    let actual = Hash(entries[1].header.hash().unwrap());
+    assert_eq!(
+        verify_ledger_header_chain(&entries),
+        Err(LedgerHeaderVerifyError::HeaderHashMismatch {
+            ledger_seq: 101,
+            expected: entries[1].hash.clone(),
+            actual,
+        })
+    );
+}
+
+#[test]
+fn test_ledger_seq_gap() {
+    let entries = chain(3);
+    let skipped = [&entries[0], &entries[2]];
+    assert_eq!(
+        verify_ledger_header_chain(skipped),
+        Err(LedgerHeaderVerifyError::LedgerSeqNotSequential {
+            ledger_seq: 102,
+            previous_ledger_seq: 100,
+        })
+    );
+}
+
+#[test]
+fn test_previous_ledger_hash_mismatch() {
+    let entries = chain(2);
+    let forked = entry(101, Hash([7; 32]));
+    assert_eq!(
+        forked.verify_follows(&entries[0]),
+        Err(LedgerHeaderVerifyError::PreviousLedgerHashMismatch {
+            ledger_seq: 101,
+            expected: entries[0].hash.clone(),
+            actual: Hash([7; 32]),
+        })
+    );
+    assert_eq!(entries[1].verify_follows(&entries[0]), Ok(()));
+
+    // A failed push leaves the verifier anchored on the last good entry.
+    let mut verifier = LedgerHeaderChainVerifier::new();
+    verifier.push(&entries[0]).unwrap();
+    assert!(verifier.push(&forked).is_err());
+    assert_eq!(verifier.push(&entries[1]), Ok(()));
+}
+
+#[test]
+fn test_tx_set_and_result_commitments() -> Result<(), stellar_xdr::Error> {
+    let tx_set = GeneralizedTransactionSet::V1(TransactionSetV1 {
+        previous_ledger_hash: Hash([1; 32]),
+        phases: [
+            TransactionPhase::V0(VecM::default()),
+            TransactionPhase::V0(VecM::default()),
+        ]
+        .try_into()?,
+    });
+    let result_set = TransactionResultSet::default();
+
+    let mut header = LedgerHeader {
+        ledger_seq: 7,
+        ..Default::default()
+    };
+    header.scp_value.tx_set_hash = Hash(tx_set.hash()?);
+    header.tx_set_result_hash = Hash(result_set.hash()?);
+    assert_eq!(
+        header.tx_set_result_hash.0,
+        <[u8; 32]>::from(Sha256::digest(result_set.to_xdr(Limits::none())?))
+    );
+
+    assert_eq!(header.verify_tx_set(&tx_set), Ok(()));
+    assert_eq!(header.verify_tx_set_result(&result_set), Ok(()));
+
+    let other_tx_set = GeneralizedTransactionSet::V1(TransactionSetV1::default());
+    assert_eq!(
+        header.verify_tx_set(&other_tx_set),
+        Err(LedgerHeaderVerifyError::TxSetHashMismatch {
+            ledger_seq: 7,
+            expected: header.scp_value.tx_set_hash.clone(),
+            actual: Hash(other_tx_set.hash()?),
+        })
+    );
+
+    header.tx_set_result_hash = Hash([0; 32]);
+    assert!(matches!(
+        header.verify_tx_set_result(&result_set),
+        Err(LedgerHeaderVerifyError::TxSetResultHashMismatch { ledger_seq: 7, .. })
+    ));
+    Ok(())
+}
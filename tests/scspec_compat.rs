@@ -0,0 +1,487 @@
+#![cfg(feature = "std")]
+
+use stellar_xdr::{
+    compare_contract_specs, ScSpecChange, ScSpecChangeImpact, ScSpecEntry, ScSpecEventDataFormat,
+    ScSpecEventParamLocationV0, ScSpecEventParamV0, ScSpecEventV0, ScSpecFunctionInputV0,
+    ScSpecFunctionV0, ScSpecTypeDef, ScSpecTypeUdt, ScSpecUdtEnumCaseV0, ScSpecUdtEnumV0,
+    ScSpecUdtErrorEnumCaseV0, ScSpecUdtErrorEnumV0, ScSpecUdtStructFieldV0, ScSpecUdtStructV0,
+    ScSpecUdtUnionCaseTupleV0, ScSpecUdtUnionCaseV0, ScSpecUdtUnionCaseVoidV0, ScSpecUdtUnionV0,
+};
+
+fn udt(name: &str) -> ScSpecTypeDef {
+    ScSpecTypeDef::Udt(ScSpecTypeUdt {
+        name: name.try_into().unwrap(),
+    })
+}
+
+fn function(name: &str, inputs: &[(&str, ScSpecTypeDef)], output: &[ScSpecTypeDef]) -> ScSpecEntry {
+    ScSpecEntry::FunctionV0(ScSpecFunctionV0 {
+        doc: "".try_into().unwrap(),
+        name: name.try_into().unwrap(),
+        inputs: inputs
+            .iter()
+            .map(|(name, type_)| ScSpecFunctionInputV0 {
+                doc: "".try_into().unwrap(),
+                name: (*name).try_into().unwrap(),
+                type_: type_.clone(),
+            })
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap(),
+        outputs: output.to_vec().try_into().unwrap(),
+    })
+}
+
+fn structure(name: &str, fields: &[(&str, ScSpecTypeDef)]) -> ScSpecEntry {
+    ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+        doc: "".try_into().unwrap(),
+        lib: "".try_into().unwrap(),
+        name: name.try_into().unwrap(),
+        fields: fields
+            .iter()
+            .map(|(name, type_)| ScSpecUdtStructFieldV0 {
+                doc: "".try_into().unwrap(),
+                name: (*name).try_into().unwrap(),
+                type_: type_.clone(),
+            })
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap(),
+    })
+}
+
+fn union(name: &str, cases: &[(&str, Option<&[ScSpecTypeDef]>)]) -> ScSpecEntry {
+    ScSpecEntry::UdtUnionV0(ScSpecUdtUnionV0 {
+        doc: "".try_into().unwrap(),
+        lib: "".try_into().unwrap(),
+        name: name.try_into().unwrap(),
+        cases: cases
+            .iter()
+            .map(|(name, types)| match types {
+                None => ScSpecUdtUnionCaseV0::VoidV0(ScSpecUdtUnionCaseVoidV0 {
+                    doc: "".try_into().unwrap(),
+                    name: (*name).try_into().unwrap(),
+                }),
+                Some(types) => ScSpecUdtUnionCaseV0::TupleV0(ScSpecUdtUnionCaseTupleV0 {
+                    doc: "".try_into().unwrap(),
+                    name: (*name).try_into().unwrap(),
+                    type_: types.to_vec().try_into().unwrap(),
+                }),
+            })
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap(),
+    })
+}
+
+fn enumeration(name: &str, cases: &[(&str, u32)]) -> ScSpecEntry {
+    ScSpecEntry::UdtEnumV0(ScSpecUdtEnumV0 {
+        doc: "".try_into().unwrap(),
+        lib: "".try_into().unwrap(),
+        name: name.try_into().unwrap(),
+        cases: cases
+            .iter()
+            .map(|(name, value)| ScSpecUdtEnumCaseV0 {
+                doc: "".try_into().unwrap(),
+                name: (*name).try_into().unwrap(),
+                value: *value,
+            })
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap(),
+    })
+}
+
+fn error_enum(name: &str, cases: &[(&str, u32)]) -> ScSpecEntry {
+    ScSpecEntry::UdtErrorEnumV0(ScSpecUdtErrorEnumV0 {
+        doc: "".try_into().unwrap(),
+        lib: "".try_into().unwrap(),
+        name: name.try_into().unwrap(),
+        cases: cases
+            .iter()
+            .map(|(name, value)| ScSpecUdtErrorEnumCaseV0 {
+                doc: "".try_into().unwrap(),
+                name: (*name).try_into().unwrap(),
+                value: *value,
+            })
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap(),
+    })
+}
+
+fn event(
+    name: &str,
+    prefix_topics: &[&str],
+    params: &[(&str, ScSpecTypeDef, ScSpecEventParamLocationV0)],
+    data_format: ScSpecEventDataFormat,
+) -> ScSpecEntry {
+    ScSpecEntry::EventV0(ScSpecEventV0 {
+        doc: "".try_into().unwrap(),
+        lib: "".try_into().unwrap(),
+        name: name.try_into().unwrap(),
+        prefix_topics: prefix_topics
+            .iter()
+            .map(|t| (*t).try_into().unwrap())
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap(),
+        params: params
+            .iter()
+            .map(|(name, type_, location)| ScSpecEventParamV0 {
+                doc: "".try_into().unwrap(),
+                name: (*name).try_into().unwrap(),
+                type_: type_.clone(),
+                location: *location,
+            })
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap(),
+        data_format,
+    })
+}
+
+#[test]
+fn test_unchanged() {
+    let spec = [
+        function(
+            "hello",
+            &[("to", ScSpecTypeDef::Symbol)],
+            &[ScSpecTypeDef::Symbol],
+        ),
+        structure("Point", &[("x", ScSpecTypeDef::I32)]),
+    ];
+    assert_eq!(compare_contract_specs(&spec, &spec), Vec::new());
+}
+
+#[test]
+fn test_functions() {
+    let old = [
+        function(
+            "hello",
+            &[("to", ScSpecTypeDef::Symbol)],
+            &[ScSpecTypeDef::Symbol],
+        ),
+        function(
+            "transfer",
+            &[
+                ("from", ScSpecTypeDef::Address),
+                ("to", ScSpecTypeDef::Address),
+                ("amount", ScSpecTypeDef::I128),
+            ],
+            &[],
+        ),
+        function(
+            "balance",
+            &[("id", ScSpecTypeDef::Address)],
+            &[ScSpecTypeDef::I128],
+        ),
+        function("burn", &[("amount", ScSpecTypeDef::I128)], &[]),
+    ];
+    let new = [
+        function(
+            "greet",
+            &[("to", ScSpecTypeDef::Symbol)],
+            &[ScSpecTypeDef::Symbol],
+        ),
+        function(
+            "transfer",
+            &[
+                ("from", ScSpecTypeDef::Address),
+                ("recipient", ScSpecTypeDef::MuxedAddress),
+            ],
+            &[],
+        ),
+        function(
+            "balance",
+            &[("id", ScSpecTypeDef::Address)],
+            &[ScSpecTypeDef::U64],
+        ),
+        function("mint", &[("amount", ScSpecTypeDef::U64)], &[]),
+    ];
+    let changes = compare_contract_specs(&old, &new);
+    assert_eq!(
+        changes,
+        [
+            ScSpecChange::FunctionRenamed {
+                old_function: "hello".to_string(),
+                new_function: "greet".to_string(),
+            },
+            ScSpecChange::FunctionInputRenamed {
+                function: "transfer".to_string(),
+                old_input: "to".to_string(),
+                new_input: "recipient".to_string(),
+            },
+            ScSpecChange::FunctionInputTypeChanged {
+                function: "transfer".to_string(),
+                input: "recipient".to_string(),
+                old_type: ScSpecTypeDef::Address,
+                new_type: ScSpecTypeDef::MuxedAddress,
+            },
+            ScSpecChange::FunctionInputRemoved {
+                function: "transfer".to_string(),
+                input: "amount".to_string(),
+            },
+            ScSpecChange::FunctionOutputChanged {
+                function: "balance".to_string(),
+                old_type: Some(ScSpecTypeDef::I128),
+                new_type: Some(ScSpecTypeDef::U64),
+            },
+            ScSpecChange::FunctionRemoved {
+                function: "burn".to_string(),
+            },
+            ScSpecChange::FunctionAdded {
+                function: "mint".to_string(),
+            },
+        ]
+    );
+    assert_eq!(
+        changes.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        [
+            "function hello renamed to greet",
+            "function transfer input to renamed to recipient",
+            "function transfer input recipient type changed from Address to MuxedAddress",
+            "function transfer input amount removed",
+            "function balance output changed from i128 to u64",
+            "function burn removed",
+            "function mint added",
+        ]
+    );
+    assert!(changes[..6].iter().all(ScSpecChange::is_breaking));
+    assert_eq!(changes[6].impact(), ScSpecChangeImpact::Additive);
+}
+
+#[test]
+fn test_udts() {
+    let old = [
+        structure(
+            "Point",
+            &[("x", ScSpecTypeDef::I32), ("y", ScSpecTypeDef::I32)],
+        ),
+        union(
+            "Shape",
+            &[
+                ("Empty", None),
+                ("Line", Some(&[udt("Point"), udt("Point")])),
+                ("Dot", Some(&[])),
+            ],
+        ),
+        enumeration("Color", &[("Red", 1), ("Green", 2)]),
+        error_enum("Failure", &[("Insufficient", 1), ("Expired", 2)]),
+        structure("Old", &[]),
+        structure("Mode", &[]),
+    ];
+    let new = [
+        structure(
+            "Point",
+            &[("x", ScSpecTypeDef::I64), ("z", ScSpecTypeDef::I32)],
+        ),
+        union(
+            "Shape",
+            &[
+                ("Empty", None),
+                ("Line", Some(&[udt("Point")])),
+                ("Dot", None),
+                ("Circle", Some(&[udt("Point"), ScSpecTypeDef::U32])),
+            ],
+        ),
+        enumeration("Color", &[("Red", 1), ("Green", 3), ("Blue", 4)]),
+        error_enum("Failure", &[("Insufficient", 10), ("Overflow", 3)]),
+        enumeration("Mode", &[]),
+        structure("New", &[]),
+    ];
+    let changes = compare_contract_specs(&old, &new);
+    assert_eq!(
+        changes.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        [
+            "struct Point field x type changed from i32 to i64",
+            "struct Point field y removed",
+            "struct Point field z added",
+            "union Shape case Line types changed",
+            "union Shape case Dot types changed",
+            "union Shape case Circle added",
+            "enum Color case Green value changed from 2 to 3",
+            "enum Color case Blue added",
+            "error enum Failure case Insufficient code changed from 1 to 10",
+            "error enum Failure case Expired removed",
+            "error enum Failure case Overflow added",
+            "type Old removed",
+            "type Mode kind changed",
+            "type New added",
+        ]
+    );
+    assert_eq!(
+        changes
+            .iter()
+            .filter(|c| !c.is_breaking())
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        [
+            "union Shape case Circle added",
+            "enum Color case Blue added",
+            "error enum Failure case Overflow added",
+            "type New added",
+        ]
+    );
+}
+
+#[test]
+fn test_events() {
+    let data = ScSpecEventParamLocationV0::Data;
+    let topic = ScSpecEventParamLocationV0::TopicList;
+    let old = [
+        event(
+            "Transfer",
+            &["transfer"],
+            &[
+                ("from", ScSpecTypeDef::Address, topic),
+                ("amount", ScSpecTypeDef::I128, data),
+            ],
+            ScSpecEventDataFormat::SingleValue,
+        ),
+        event("Burn", &["burn"], &[], ScSpecEventDataFormat::Map),
+    ];
+    let new = [
+        event(
+            "Transfer",
+            &["xfer"],
+            &[
+                ("from", ScSpecTypeDef::Address, topic),
+                ("amount", ScSpecTypeDef::I128, topic),
+                ("memo", ScSpecTypeDef::String, data),
+            ],
+            ScSpecEventDataFormat::Map,
+        ),
+        event("Mint", &["mint"], &[], ScSpecEventDataFormat::Map),
+    ];
+    let changes = compare_contract_specs(&old, &new);
+    assert_eq!(
+        changes,
+        [
+            ScSpecChange::EventPrefixTopicsChanged {
+                event: "Transfer".to_string()
+            },
+            ScSpecChange::EventDataFormatChanged {
+                event: "Transfer".to_string()
+            },
+            ScSpecChange::EventParamChanged {
+                event: "Transfer".to_string(),
+                param: "amount".to_string(),
+            },
+            ScSpecChange::EventParamAdded {
+                event: "Transfer".to_string(),
+                param: "memo".to_string(),
+            },
+            ScSpecChange::EventRemoved {
+                event: "Burn".to_string()
+            },
+            ScSpecChange::EventAdded {
+                event: "Mint".to_string()
+            },
+        ]
+    );
+    assert_eq!(
+        changes.iter().filter(|c| c.is_breaking()).count(),
+        5,
+        "only the added event is additive"
+    );
+}
+
+#[test]
+fn test_functions_with_no_inputs_or_outputs_are_not_renamed() {
+    let old = [
+        function("pause", &[], &[]),
+        function("get", &[("key", ScSpecTypeDef::Symbol)], &[]),
+        function("fetch", &[("key", ScSpecTypeDef::Symbol)], &[]),
+    ];
+    let new = [
+        function("version", &[], &[]),
+        function("load", &[("key", ScSpecTypeDef::Symbol)], &[]),
+        function("read", &[("key", ScSpecTypeDef::Symbol)], &[]),
+    ];
+    let changes = compare_contract_specs(&old, &new);
+    assert_eq!(
+        changes.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        [
+            "function pause removed",
+            "function get removed",
+            "function fetch removed",
+            "function version added",
+            "function load added",
+            "function read added",
+        ]
+    );
+}
+
+#[test]
+fn test_cases_added_to_output_types() {
+    let old = [
+        function(
+            "shape",
+            &[],
+            &[ScSpecTypeDef::Vec(Box::new(stellar_xdr::ScSpecTypeVec {
+                element_type: Box::new(udt("Drawing")),
+            }))],
+        ),
+        function("paint", &[("color", udt("Color"))], &[]),
+        structure("Drawing", &[("shape", udt("Shape"))]),
+        union("Shape", &[("Empty", None)]),
+        enumeration("Color", &[("Red", 1)]),
+        enumeration("Status", &[("Ok", 1)]),
+        event(
+            "Status",
+            &["status"],
+            &[("status", udt("Status"), ScSpecEventParamLocationV0::Data)],
+            ScSpecEventDataFormat::SingleValue,
+        ),
+    ];
+    let new = [
+        function(
+            "shape",
+            &[],
+            &[ScSpecTypeDef::Vec(Box::new(stellar_xdr::ScSpecTypeVec {
+                element_type: Box::new(udt("Drawing")),
+            }))],
+        ),
+        function("paint", &[("color", udt("Color"))], &[]),
+        structure("Drawing", &[("shape", udt("Shape"))]),
+        union("Shape", &[("Empty", None), ("Dot", None)]),
+        enumeration("Color", &[("Red", 1), ("Blue", 2)]),
+        enumeration("Status", &[("Ok", 1), ("Failed", 2)]),
+        event(
+            "Status",
+            &["status"],
+            &[("status", udt("Status"), ScSpecEventParamLocationV0::Data)],
+            ScSpecEventDataFormat::SingleValue,
+        ),
+    ];
+    let changes = compare_contract_specs(&old, &new);
+    assert_eq!(
+        changes,
+        [
+            ScSpecChange::UnionCaseAdded {
+                type_name: "Shape".to_string(),
+                case: "Dot".to_string(),
+                output: true,
+            },
+            ScSpecChange::EnumCaseAdded {
+                type_name: "Color".to_string(),
+                case: "Blue".to_string(),
+                output: false,
+            },
+            ScSpecChange::EnumCaseAdded {
+                type_name: "Status".to_string(),
+                case: "Failed".to_string(),
+                output: true,
+            },
+        ]
+    );
+    assert_eq!(
+        changes.iter().map(ScSpecChange::impact).collect::<Vec<_>>(),
+        [
+            ScSpecChangeImpact::Breaking,
+            ScSpecChangeImpact::Additive,
+            ScSpecChangeImpact::Breaking,
+        ]
+    );
+}
@@ -0,0 +1,282 @@
+#![cfg(feature = "std")]
+
+use stellar_xdr::{
+    ContractEvent, ContractEventBody, ContractEventType, ContractEventV0, DecodedContractEvent,
+    ExtensionPoint, Int128Parts, ScAddress, ScMap, ScSpecEntry, ScSpecEventDataFormat,
+    ScSpecEventError, ScSpecEventParamLocationV0, ScSpecEventParamV0, ScSpecEventV0, ScSpecTypeDef,
+    ScSpecValidationError, ScSpecValidationErrorKind, ScVal,
+};
+
+fn event(
+    name: &str,
+    prefix_topics: &[&str],
+    params: &[(&str, ScSpecTypeDef, ScSpecEventParamLocationV0)],
+    data_format: ScSpecEventDataFormat,
+) -> ScSpecEntry {
+    ScSpecEntry::EventV0(ScSpecEventV0 {
+        doc: "".try_into().unwrap(),
+        lib: "".try_into().unwrap(),
+        name: name.try_into().unwrap(),
+        prefix_topics: prefix_topics
+            .iter()
+            .map(|t| (*t).try_into().unwrap())
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap(),
+        params: params
+            .iter()
+            .map(|(name, type_, location)| ScSpecEventParamV0 {
+                doc: "".try_into().unwrap(),
+                name: (*name).try_into().unwrap(),
+                type_: type_.clone(),
+                location: *location,
+            })
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap(),
+        data_format,
+    })
+}
+
+fn spec() -> Vec<ScSpecEntry> {
+    use ScSpecEventParamLocationV0::{Data, TopicList};
+    vec![
+        event(
+            "Transfer",
+            &["transfer"],
+            &[
+                ("from", ScSpecTypeDef::Address, TopicList),
+                ("to", ScSpecTypeDef::Address, TopicList),
+                ("amount", ScSpecTypeDef::I128, Data),
+            ],
+            ScSpecEventDataFormat::SingleValue,
+        ),
+        event(
+            "Approve",
+            &["approve"],
+            &[
+                ("from", ScSpecTypeDef::Address, TopicList),
+                ("amount", ScSpecTypeDef::I128, Data),
+                ("live_until", ScSpecTypeDef::U32, Data),
+            ],
+            ScSpecEventDataFormat::Vec,
+        ),
+        event(
+            "Config",
+            &["config", "set"],
+            &[
+                ("key", ScSpecTypeDef::Symbol, Data),
+                ("value", ScSpecTypeDef::U64, Data),
+            ],
+            ScSpecEventDataFormat::Map,
+        ),
+        event(
+            "Burn",
+            &["burn"],
+            &[("amount", ScSpecTypeDef::I128, Data)],
+            ScSpecEventDataFormat::SingleValue,
+        ),
+        event(
+            "BurnFrom",
+            &["burn"],
+            &[
+                ("from", ScSpecTypeDef::Address, TopicList),
+                ("amount", ScSpecTypeDef::I128, Data),
+            ],
+            ScSpecEventDataFormat::SingleValue,
+        ),
+        event(
+            "Paused",
+            &["paused"],
+            &[],
+            ScSpecEventDataFormat::SingleValue,
+        ),
+    ]
+}
+
+fn contract_event(topics: Vec<ScVal>, data: ScVal) -> ContractEvent {
+    ContractEvent {
+        ext: ExtensionPoint::V0,
+        contract_id: None,
+        type_: ContractEventType::Contract,
+        body: ContractEventBody::V0(ContractEventV0 {
+            topics: topics.try_into().unwrap(),
+            data,
+        }),
+    }
+}
+
+fn symbol(s: &str) -> ScVal {
+    ScVal::Symbol(s.try_into().unwrap())
+}
+
+fn address(s: &str) -> ScVal {
+    ScVal::Address(s.parse::<ScAddress>().unwrap())
+}
+
+const ALICE: &str = "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ";
+const BOB: &str = "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF";
+
+fn amount(n: u64) -> ScVal {
+    ScVal::I128(Int128Parts { hi: 0, lo: n })
+}
+
+fn decoded(name: &str, params: &[(&str, ScVal)]) -> DecodedContractEvent {
+    DecodedContractEvent {
+        name: name.to_string(),
+        params: params
+            .iter()
+            .map(|(n, v)| ((*n).to_string(), v.clone()))
+            .collect(),
+    }
+}
+
+#[test]
+fn test_decode() {
+    let spec = spec();
+    let cases = [
+        (
+            contract_event(
+                vec![symbol("transfer"), address(ALICE), address(BOB)],
+                amount(5),
+            ),
+            decoded(
+                "Transfer",
+                &[
+                    ("from", address(ALICE)),
+                    ("to", address(BOB)),
+                    ("amount", amount(5)),
+                ],
+            ),
+        ),
+        (
+            contract_event(
+                vec![symbol("approve"), address(ALICE)],
+                ScVal::Vec(Some(vec![amount(7), ScVal::U32(100)].try_into().unwrap())),
+            ),
+            decoded(
+                "Approve",
+                &[
+                    ("from", address(ALICE)),
+                    ("amount", amount(7)),
+                    ("live_until", ScVal::U32(100)),
+                ],
+            ),
+        ),
+        (
+            contract_event(
+                vec![symbol("config"), symbol("set")],
+                ScVal::Map(Some(
+                    ScMap::sorted_from([
+                        (symbol("value"), ScVal::U64(3)),
+                        (symbol("key"), symbol("fee")),
+                    ])
+                    .unwrap(),
+                )),
+            ),
+            decoded(
+                "Config",
+                &[("key", symbol("fee")), ("value", ScVal::U64(3))],
+            ),
+        ),
+        (
+            contract_event(vec![symbol("burn")], amount(2)),
+            decoded("Burn", &[("amount", amount(2))]),
+        ),
+        (
+            contract_event(vec![symbol("burn"), address(ALICE)], amount(2)),
+            decoded(
+                "BurnFrom",
+                &[("from", address(ALICE)), ("amount", amount(2))],
+            ),
+        ),
+        (
+            contract_event(vec![symbol("paused")], ScVal::Void),
+            decoded("Paused", &[]),
+        ),
+    ];
+    for (event, expected) in cases {
+        assert_eq!(event.decode_with_spec(&spec), Ok(expected));
+    }
+}
+
+#[test]
+fn test_decode_errors() {
+    let spec = spec();
+    let cases = [
+        (
+            contract_event(vec![symbol("mint")], amount(1)),
+            ScSpecEventError::UnknownEvent,
+        ),
+        (
+            contract_event(vec![symbol("transfer"), address(ALICE)], amount(1)),
+            ScSpecEventError::TopicCountMismatch {
+                event: "Transfer".to_string(),
+                expected: 3,
+                actual: 2,
+            },
+        ),
+        (
+            contract_event(vec![symbol("approve"), address(ALICE)], amount(1)),
+            ScSpecEventError::DataFormatMismatch {
+                event: "Approve".to_string(),
+                expected: ScSpecEventDataFormat::Vec,
+            },
+        ),
+        (
+            contract_event(
+                vec![symbol("config"), symbol("set")],
+                ScVal::Map(Some(
+                    ScMap::sorted_from([
+                        (symbol("val"), ScVal::U64(3)),
+                        (symbol("key"), symbol("fee")),
+                    ])
+                    .unwrap(),
+                )),
+            ),
+            ScSpecEventError::MissingParam {
+                event: "Config".to_string(),
+                param: "value".to_string(),
+            },
+        ),
+        (
+            contract_event(
+                vec![symbol("transfer"), address(ALICE), ScVal::U32(1)],
+                amount(1),
+            ),
+            ScSpecEventError::InvalidParam {
+                event: "Transfer".to_string(),
+                param: "to".to_string(),
+                error: ScSpecValidationError {
+                    path: "$".to_string(),
+                    kind: ScSpecValidationErrorKind::TypeMismatch {
+                        expected: "Address".to_string(),
+                    },
+                },
+            },
+        ),
+        (
+            contract_event(
+                vec![symbol("burn"), address(ALICE), address(BOB)],
+                amount(2),
+            ),
+            ScSpecEventError::TopicCountMismatch {
+                event: "BurnFrom".to_string(),
+                expected: 2,
+                actual: 3,
+            },
+        ),
+    ];
+    for (event, expected) in cases {
+        assert_eq!(event.decode_with_spec(&spec), Err(expected));
+    }
+
+    assert_eq!(
+        ScSpecEventError::DataFormatMismatch {
+            event: "Approve".to_string(),
+            expected: ScSpecEventDataFormat::Vec,
+        }
+        .to_string(),
+        "event Approve expects data in the Vec format"
+    );
+}
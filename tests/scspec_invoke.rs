@@ -0,0 +1,241 @@
+#![cfg(feature = "std")]
+
+use stellar_xdr::{
+    ContractExecutable, ContractId, ContractIdPreimage, ContractIdPreimageFromAddress,
+    CreateContractArgs, DecodedContractInvocation, Hash, HostFunction, Int128Parts,
+    InvokeContractArgs, InvokeHostFunctionOp, ScAddress, ScSpecEntry, ScSpecFunctionInputV0,
+    ScSpecFunctionV0, ScSpecInvokeError, ScSpecTypeDef, ScSpecValidationError,
+    ScSpecValidationErrorKind, ScVal, Uint256,
+};
+
+fn input(name: &str, type_: ScSpecTypeDef) -> ScSpecFunctionInputV0 {
+    ScSpecFunctionInputV0 {
+        doc: "".try_into().unwrap(),
+        name: name.try_into().unwrap(),
+        type_,
+    }
+}
+
+fn transfer() -> ScSpecFunctionV0 {
+    ScSpecFunctionV0 {
+        doc: "".try_into().unwrap(),
+        name: "transfer".try_into().unwrap(),
+        inputs: [
+            input("from", ScSpecTypeDef::Address),
+            input("to", ScSpecTypeDef::Address),
+            input("amount", ScSpecTypeDef::I128),
+        ]
+        .try_into()
+        .unwrap(),
+        outputs: [].try_into().unwrap(),
+    }
+}
+
+fn spec() -> Vec<ScSpecEntry> {
+    vec![ScSpecEntry::FunctionV0(transfer())]
+}
+
+fn contract() -> ScAddress {
+    ScAddress::Contract(ContractId(Hash([1; 32])))
+}
+
+fn address(s: &str) -> ScVal {
+    ScVal::Address(s.parse::<ScAddress>().unwrap())
+}
+
+const ALICE: &str = "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ";
+const BOB: &str = "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF";
+
+fn amount(n: u64) -> ScVal {
+    ScVal::I128(Int128Parts { hi: 0, lo: n })
+}
+
+fn invoke_args(args: Vec<ScVal>) -> InvokeContractArgs {
+    InvokeContractArgs {
+        contract_address: contract(),
+        function_name: "transfer".try_into().unwrap(),
+        args: args.try_into().unwrap(),
+    }
+}
+
+#[test]
+fn test_build_and_decode() {
+    let args = InvokeContractArgs::from_spec_args(
+        contract(),
+        &transfer(),
+        &[
+            ("amount", amount(5)),
+            ("to", address(BOB)),
+            ("from", address(ALICE)),
+        ],
+        &spec(),
+    )
+    .unwrap();
+    assert_eq!(
+        args,
+        invoke_args(vec![address(ALICE), address(BOB), amount(5)])
+    );
+
+    let op = InvokeHostFunctionOp::from(args);
+    assert!(op.auth.is_empty());
+    let decoded = op.decode_with_spec(&spec()).unwrap();
+    assert_eq!(
+        decoded,
+        DecodedContractInvocation {
+            contract_address: contract(),
+            function: "transfer".to_string(),
+            args: vec![
+                ("from".to_string(), address(ALICE)),
+                ("to".to_string(), address(BOB)),
+                ("amount".to_string(), amount(5)),
+            ],
+        }
+    );
+
+    // The decoded args build the same invocation.
+    let rebuilt = InvokeContractArgs::from_spec_args(
+        decoded.contract_address,
+        &transfer(),
+        &decoded.args,
+        &spec(),
+    )
+    .unwrap();
+    assert_eq!(HostFunction::InvokeContract(rebuilt), op.host_function);
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn test_build_from_json() {
+    let serde_json::Value::Object(json) = serde_json::json!({
+        "from": ALICE,
+        "to": BOB,
+        "amount": "5",
+    }) else {
+        unreachable!()
+    };
+    assert_eq!(
+        InvokeContractArgs::from_spec_json_args(contract(), &transfer(), &json, &spec()),
+        Ok(invoke_args(vec![address(ALICE), address(BOB), amount(5)]))
+    );
+
+    let serde_json::Value::Object(json) = serde_json::json!({
+        "from": ALICE,
+        "to": BOB,
+        "amount": true,
+    }) else {
+        unreachable!()
+    };
+    assert_eq!(
+        InvokeContractArgs::from_spec_json_args(contract(), &transfer(), &json, &spec())
+            .unwrap_err()
+            .to_string(),
+        "function transfer arg amount is invalid: $: expected i128"
+    );
+}
+
+#[test]
+fn test_build_errors() {
+    let cases = [
+        (
+            vec![("from", address(ALICE)), ("amount", amount(5))],
+            ScSpecInvokeError::MissingArg {
+                function: "transfer".to_string(),
+                arg: "to".to_string(),
+            },
+        ),
+        (
+            vec![
+                ("from", address(ALICE)),
+                ("to", address(BOB)),
+                ("amount", amount(5)),
+                ("memo", ScVal::U32(1)),
+            ],
+            ScSpecInvokeError::UnknownArg {
+                function: "transfer".to_string(),
+                arg: "memo".to_string(),
+            },
+        ),
+        (
+            vec![
+                ("from", address(ALICE)),
+                ("to", address(BOB)),
+                ("to", address(BOB)),
+                ("amount", amount(5)),
+            ],
+            ScSpecInvokeError::DuplicateArg {
+                function: "transfer".to_string(),
+                arg: "to".to_string(),
+            },
+        ),
+        (
+            vec![
+                ("from", address(ALICE)),
+                ("to", address(BOB)),
+                ("amount", ScVal::U64(5)),
+            ],
+            ScSpecInvokeError::InvalidArg {
+                function: "transfer".to_string(),
+                arg: "amount".to_string(),
+                error: ScSpecValidationError {
+                    path: "$".to_string(),
+                    kind: ScSpecValidationErrorKind::TypeMismatch {
+                        expected: "i128".to_string(),
+                    },
+                },
+            },
+        ),
+    ];
+    for (args, expected) in cases {
+        assert_eq!(
+            InvokeContractArgs::from_spec_args(contract(), &transfer(), &args, &spec()),
+            Err(expected)
+        );
+    }
+}
+
+#[test]
+fn test_decode_errors() {
+    let mut unknown = invoke_args(vec![]);
+    unknown.function_name = "mint".try_into().unwrap();
+    let create = InvokeHostFunctionOp {
+        host_function: HostFunction::CreateContract(CreateContractArgs {
+            contract_id_preimage: ContractIdPreimage::Address(ContractIdPreimageFromAddress {
+                address: contract(),
+                salt: Uint256([0; 32]),
+            }),
+            executable: ContractExecutable::StellarAsset,
+        }),
+        auth: [].try_into().unwrap(),
+    };
+    let cases = [
+        (
+            unknown.into(),
+            ScSpecInvokeError::UnknownFunction("mint".to_string()),
+        ),
+        (
+            invoke_args(vec![address(ALICE), address(BOB)]).into(),
+            ScSpecInvokeError::ArgCountMismatch {
+                function: "transfer".to_string(),
+                expected: 3,
+                actual: 2,
+            },
+        ),
+        (
+            invoke_args(vec![address(ALICE), amount(5), amount(5)]).into(),
+            ScSpecInvokeError::InvalidArg {
+                function: "transfer".to_string(),
+                arg: "to".to_string(),
+                error: ScSpecValidationError {
+                    path: "$".to_string(),
+                    kind: ScSpecValidationErrorKind::TypeMismatch {
+                        expected: "Address".to_string(),
+                    },
+                },
+            },
+        ),
+        (create, ScSpecInvokeError::NotInvokeContract),
+    ];
+    for (op, expected) in cases {
+        assert_eq!(op.decode_with_spec(&spec()), Err(expected));
+    }
+}
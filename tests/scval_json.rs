@@ -0,0 +1,203 @@
+#![cfg(feature = "serde_json")]
+
+use serde_json::json;
+use stellar_xdr::{
+    Error, Int128Parts, ScError, ScMap, ScMapEntry, ScNonceKey, ScString, ScVal, ScValType,
+    TimePoint, UInt256Parts,
+};
+
+const ACCOUNT: &str = "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ";
+
+fn symbol(s: &str) -> ScVal {
+    ScVal::Symbol(s.try_into().unwrap())
+}
+
+fn string(s: &str) -> ScVal {
+    ScVal::String(ScString(s.try_into().unwrap()))
+}
+
+fn vec(items: impl IntoIterator<Item = ScVal>) -> ScVal {
+    ScVal::Vec(Some(
+        items.into_iter().collect::<Vec<_>>().try_into().unwrap(),
+    ))
+}
+
+fn map(entries: impl IntoIterator<Item = (ScVal, ScVal)>) -> ScVal {
+    ScVal::Map(Some(ScMap::sorted_from(entries).unwrap()))
+}
+
+#[test]
+fn test_to_json_value() {
+    let cases = [
+        (ScVal::Bool(true), json!(true)),
+        (ScVal::Void, json!(null)),
+        (ScVal::U32(1), json!(1)),
+        (ScVal::I64(-2), json!(-2)),
+        (ScVal::Timepoint(TimePoint(3)), json!(3)),
+        (
+            ScVal::I128(Int128Parts {
+                hi: -1,
+                lo: u64::MAX,
+            }),
+            json!("-1"),
+        ),
+        (
+            ScVal::U256(UInt256Parts {
+                hi_hi: 0,
+                hi_lo: 0,
+                lo_hi: 1,
+                lo_lo: 0,
+            }),
+            json!("18446744073709551616"),
+        ),
+        (
+            ScVal::Bytes(vec![0xde, 0xad].try_into().unwrap()),
+            json!("dead"),
+        ),
+        (symbol("a"), json!("a")),
+        (ScVal::Address(ACCOUNT.parse().unwrap()), json!(ACCOUNT)),
+        (vec([ScVal::U32(1), string("b")]), json!([1, "b"])),
+        (
+            map([(symbol("a"), ScVal::U32(1)), (string("b"), ScVal::Void)]),
+            json!({"a": 1, "b": null}),
+        ),
+        (
+            map([(ScVal::U32(2), ScVal::Bool(false))]),
+            json!([[2, false]]),
+        ),
+        (ScVal::Vec(None), json!(null)),
+        (
+            ScVal::Error(ScError::Contract(1)),
+            json!("error:Contract:1"),
+        ),
+        (
+            ScVal::LedgerKeyNonce(ScNonceKey { nonce: 5 }),
+            json!("ledger_key_nonce:5"),
+        ),
+    ];
+    for (val, expected) in cases {
+        assert_eq!(val.to_json_value(), expected, "{val}");
+    }
+}
+
+#[test]
+fn test_from_json_value_inferred() {
+    let cases = [
+        (json!(null), ScVal::Void),
+        (json!(false), ScVal::Bool(false)),
+        (json!(1), ScVal::U32(1)),
+        (json!(-1), ScVal::I32(-1)),
+        (json!(5_000_000_000_u64), ScVal::U64(5_000_000_000)),
+        (json!(-5_000_000_000_i64), ScVal::I64(-5_000_000_000)),
+        (json!("abc"), string("abc")),
+        (json!(ACCOUNT), ScVal::Address(ACCOUNT.parse().unwrap())),
+        (
+            json!([1, [true]]),
+            vec([ScVal::U32(1), vec([ScVal::Bool(true)])]),
+        ),
+        (
+            json!({"b": 1, "a": "x"}),
+            map([(symbol("a"), string("x")), (symbol("b"), ScVal::U32(1))]),
+        ),
+    ];
+    for (json, expected) in cases {
+        assert_eq!(ScVal::from_json_value(&json, None), Ok(expected), "{json}");
+    }
+}
+
+#[test]
+fn test_from_json_value_typed() {
+    let cases = [
+        (json!(1), ScValType::U64, ScVal::U64(1)),
+        (json!("7"), ScValType::U32, ScVal::U32(7)),
+        (
+            json!("-1"),
+            ScValType::I128,
+            ScVal::I128(Int128Parts {
+                hi: -1,
+                lo: u64::MAX,
+            }),
+        ),
+        (
+            json!(3),
+            ScValType::I128,
+            ScVal::I128(Int128Parts { hi: 0, lo: 3 }),
+        ),
+        (
+            json!("dead"),
+            ScValType::Bytes,
+            ScVal::Bytes(vec![0xde, 0xad].try_into().unwrap()),
+        ),
+        (json!("abc"), ScValType::Symbol, symbol("abc")),
+        (json!(ACCOUNT), ScValType::String, string(ACCOUNT)),
+        (
+            json!([[2, "x"], [1, "y"]]),
+            ScValType::Map,
+            map([(ScVal::U32(1), string("y")), (ScVal::U32(2), string("x"))]),
+        ),
+        (
+            json!("error:Contract:1"),
+            ScValType::Error,
+            ScVal::Error(ScError::Contract(1)),
+        ),
+    ];
+    for (json, type_, expected) in cases {
+        assert_eq!(
+            ScVal::from_json_value(&json, Some(type_)),
+            Ok(expected),
+            "{json} {type_}"
+        );
+    }
+}
+
+#[test]
+fn test_from_json_value_errors() {
+    let cases = [
+        (json!(1.5), None, Error::Invalid),
+        (json!({"a b": 1}), None, Error::Invalid),
+        (json!(-1), Some(ScValType::U32), Error::Invalid),
+        (
+            json!(4_294_967_296_u64),
+            Some(ScValType::U32),
+            Error::Invalid,
+        ),
+        (json!(true), Some(ScValType::U32), Error::Invalid),
+        (json!("xyz"), Some(ScValType::Bytes), Error::InvalidHex),
+        (json!("a-b"), Some(ScValType::Symbol), Error::Invalid),
+        (
+            json!([[1, 2], [1, 3]]),
+            Some(ScValType::Map),
+            Error::Invalid,
+        ),
+        (json!([[1]]), Some(ScValType::Map), Error::Invalid),
+        (json!("true"), Some(ScValType::Bool), Error::Invalid),
+        (json!("1u32"), Some(ScValType::Error), Error::Invalid),
+    ];
+    for (json, type_, expected) in cases {
+        assert_eq!(
+            ScVal::from_json_value(&json, type_),
+            Err(expected),
+            "{json} {type_:?}"
+        );
+    }
+}
+
+#[test]
+fn test_round_trip_typed() {
+    let val = map([
+        (symbol("amount"), string("5")),
+        (symbol("to"), ScVal::Address(ACCOUNT.parse().unwrap())),
+    ]);
+    let json = val.to_json_value();
+    assert_eq!(ScVal::from_json_value(&json, Some(ScValType::Map)), Ok(val));
+
+    let entries = vec![ScMapEntry {
+        key: ScVal::U32(1),
+        val: ScVal::U32(2),
+    }];
+    let val = ScVal::Map(Some(ScMap(entries.try_into().unwrap())));
+    assert_eq!(
+        ScVal::from_json_value(&val.to_json_value(), Some(ScValType::Map)),
+        Ok(val)
+    );
+}
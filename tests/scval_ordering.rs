@@ -0,0 +1,236 @@
+#![cfg(feature = "std")]
+
+use std::cmp::Ordering;
+
+use stellar_xdr::{
+    AccountId, ClaimableBalanceId, ContractExecutable, ContractId, Duration, Hash, Int256Parts,
+    MuxedEd25519Account, PoolId, PublicKey, ScAddress, ScContractInstance, ScError, ScErrorCode,
+    ScMap, ScMapEntry, ScNonceKey, ScString, ScVal, ScValType, TimePoint, UInt128Parts,
+    UInt256Parts, Uint256, Validate,
+};
+
+fn symbol(s: &str) -> ScVal {
+    ScVal::Symbol(s.try_into().unwrap())
+}
+
+fn vec(items: impl IntoIterator<Item = ScVal>) -> ScVal {
+    ScVal::Vec(Some(
+        items.into_iter().collect::<Vec<_>>().try_into().unwrap(),
+    ))
+}
+
+fn map(entries: impl IntoIterator<Item = (ScVal, ScVal)>) -> ScMap {
+    ScMap(
+        entries
+            .into_iter()
+            .map(|(key, val)| ScMapEntry { key, val })
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap(),
+    )
+}
+
+fn i128(v: i128) -> ScVal {
+    v.into()
+}
+
+fn account(b: u8) -> ScAddress {
+    ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256([b; 32]))))
+}
+
+/// One value of every type, in the order of their types.
+fn values_of_every_type() -> Vec<ScVal> {
+    vec![
+        ScVal::Bool(true),
+        ScVal::Void,
+        ScVal::Error(ScError::Contract(1)),
+        ScVal::U32(u32::MAX),
+        ScVal::I32(i32::MIN),
+        ScVal::U64(0),
+        ScVal::I64(-1),
+        ScVal::Timepoint(TimePoint(1)),
+        ScVal::Duration(Duration(0)),
+        ScVal::U128(UInt128Parts { hi: 0, lo: 1 }),
+        i128(-1),
+        ScVal::U256(UInt256Parts {
+            hi_hi: 1,
+            hi_lo: 0,
+            lo_hi: 0,
+            lo_lo: 0,
+        }),
+        ScVal::I256(Int256Parts {
+            hi_hi: -1,
+            hi_lo: 0,
+            lo_hi: 0,
+            lo_lo: 0,
+        }),
+        ScVal::Bytes(vec![0xff].try_into().unwrap()),
+        ScVal::String(ScString("a".try_into().unwrap())),
+        symbol("a"),
+        vec([]),
+        ScVal::Map(Some(map([]))),
+        ScVal::Address(account(0)),
+        ScVal::ContractInstance(ScContractInstance {
+            executable: ContractExecutable::StellarAsset,
+            storage: None,
+        }),
+        ScVal::LedgerKeyContractInstance,
+        ScVal::LedgerKeyNonce(ScNonceKey { nonce: 0 }),
+    ]
+}
+
+#[test]
+fn test_cross_type() {
+    let values = values_of_every_type();
+    assert_eq!(
+        values.iter().map(ScVal::discriminant).collect::<Vec<_>>(),
+        ScValType::VARIANTS
+            .into_iter()
+            .filter(|t| values.iter().any(|v| v.discriminant() == *t))
+            .collect::<Vec<_>>(),
+    );
+    for (i, a) in values.iter().enumerate() {
+        for (j, b) in values.iter().enumerate() {
+            assert_eq!(a.host_cmp(b), i.cmp(&j), "{a} {b}");
+            // The host ordering is consistent with the derived ordering.
+            assert_eq!(a.host_cmp(b), a.cmp(b), "{a} {b}");
+        }
+    }
+}
+
+/// Asserts the values are in strictly ascending order.
+fn assert_ascending(values: &[ScVal]) {
+    for (i, a) in values.iter().enumerate() {
+        for (j, b) in values.iter().enumerate() {
+            assert_eq!(a.host_cmp(b), i.cmp(&j), "{a} {b}");
+        }
+    }
+}
+
+#[test]
+fn test_integers() {
+    assert_ascending(&[ScVal::I32(i32::MIN), ScVal::I32(-1), ScVal::I32(0)]);
+    assert_ascending(&[
+        i128(i128::MIN),
+        i128(-(1 << 64)),
+        i128(-1),
+        i128(0),
+        i128(1),
+        i128(1 << 64),
+        i128(i128::MAX),
+    ]);
+    assert_ascending(&[
+        ScVal::U128(UInt128Parts {
+            hi: 0,
+            lo: u64::MAX,
+        }),
+        ScVal::U128(UInt128Parts { hi: 1, lo: 0 }),
+    ]);
+    assert_ascending(&[
+        ScVal::I256(Int256Parts {
+            hi_hi: -1,
+            hi_lo: u64::MAX,
+            lo_hi: u64::MAX,
+            lo_lo: u64::MAX,
+        }),
+        ScVal::I256(Int256Parts {
+            hi_hi: 0,
+            hi_lo: 0,
+            lo_hi: 0,
+            lo_lo: 0,
+        }),
+        ScVal::I256(Int256Parts {
+            hi_hi: 0,
+            hi_lo: 0,
+            lo_hi: 1,
+            lo_lo: 0,
+        }),
+    ]);
+}
+
+#[test]
+fn test_containers() {
+    assert_ascending(&[
+        ScVal::Bytes(vec![].try_into().unwrap()),
+        ScVal::Bytes(vec![0].try_into().unwrap()),
+        ScVal::Bytes(vec![0, 0].try_into().unwrap()),
+        ScVal::Bytes(vec![1].try_into().unwrap()),
+    ]);
+    assert_ascending(&[symbol("A"), symbol("_"), symbol("a"), symbol("ab")]);
+    assert_ascending(&[
+        ScVal::Vec(None),
+        vec([]),
+        vec([i128(-1)]),
+        vec([i128(-1), ScVal::Void]),
+        vec([i128(0)]),
+    ]);
+    assert_ascending(&[
+        ScVal::Map(None),
+        ScVal::Map(Some(map([]))),
+        ScVal::Map(Some(map([(ScVal::U32(1), ScVal::U32(1))]))),
+        ScVal::Map(Some(map([(ScVal::U32(1), ScVal::U32(2))]))),
+        ScVal::Map(Some(map([(ScVal::U32(2), ScVal::U32(0))]))),
+    ]);
+}
+
+#[test]
+fn test_other_types() {
+    assert_ascending(&[
+        ScVal::Error(ScError::Contract(2)),
+        ScVal::Error(ScError::WasmVm(ScErrorCode::ArithDomain)),
+        ScVal::Error(ScError::WasmVm(ScErrorCode::IndexBounds)),
+        ScVal::Error(ScError::Auth(ScErrorCode::ArithDomain)),
+    ]);
+    assert_ascending(&[
+        ScVal::Address(account(0)),
+        ScVal::Address(account(1)),
+        ScVal::Address(ScAddress::Contract(ContractId(Hash([0; 32])))),
+        ScVal::Address(ScAddress::MuxedAccount(MuxedEd25519Account {
+            id: 0,
+            ed25519: Uint256([1; 32]),
+        })),
+        ScVal::Address(ScAddress::MuxedAccount(MuxedEd25519Account {
+            id: 1,
+            ed25519: Uint256([0; 32]),
+        })),
+        ScVal::Address(ScAddress::ClaimableBalance(
+            ClaimableBalanceId::ClaimableBalanceIdTypeV0(Hash([0; 32])),
+        )),
+        ScVal::Address(ScAddress::LiquidityPool(PoolId(Hash([0; 32])))),
+    ]);
+    assert_ascending(&[
+        ScVal::ContractInstance(ScContractInstance {
+            executable: ContractExecutable::Wasm(Hash([1; 32])),
+            storage: Some(map([])),
+        }),
+        ScVal::ContractInstance(ScContractInstance {
+            executable: ContractExecutable::StellarAsset,
+            storage: None,
+        }),
+        ScVal::ContractInstance(ScContractInstance {
+            executable: ContractExecutable::StellarAsset,
+            storage: Some(map([])),
+        }),
+    ]);
+    assert_eq!(
+        ScVal::LedgerKeyNonce(ScNonceKey { nonce: -1 })
+            .host_cmp(&ScVal::LedgerKeyNonce(ScNonceKey { nonce: 0 })),
+        Ordering::Less
+    );
+}
+
+#[test]
+fn test_scmap_sorted_and_validated() {
+    let mut values = values_of_every_type();
+    values.reverse();
+    let m = ScMap::sorted_from(values.iter().map(|v| (v.clone(), ScVal::Void))).unwrap();
+    values.reverse();
+    assert_eq!(m.iter().map(|e| e.key.clone()).collect::<Vec<_>>(), values);
+    assert_eq!(m.validate(), Ok(()));
+
+    let m = ScMap::sorted_from([(i128(1), ScVal::Void), (i128(-1), ScVal::Void)]).unwrap();
+    assert_eq!(m[0].key, i128(-1));
+
+    let unsorted = map([(i128(1), ScVal::Void), (i128(-1), ScVal::Void)]);
+    assert!(unsorted.validate().is_err());
+}
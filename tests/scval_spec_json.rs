@@ -0,0 +1,414 @@
+#![cfg(feature = "serde_json")]
+
+use serde_json::json;
+use stellar_xdr::{
+    Int128Parts, ScError, ScMap, ScSpecEntry, ScSpecJsonError, ScSpecJsonErrorKind,
+    ScSpecTypeBytesN, ScSpecTypeDef, ScSpecTypeMap, ScSpecTypeOption, ScSpecTypeResult,
+    ScSpecTypeTuple, ScSpecTypeUdt, ScSpecTypeVec, ScSpecUdtEnumCaseV0, ScSpecUdtEnumV0,
+    ScSpecUdtErrorEnumCaseV0, ScSpecUdtErrorEnumV0, ScSpecUdtStructFieldV0, ScSpecUdtStructV0,
+    ScSpecUdtUnionCaseTupleV0, ScSpecUdtUnionCaseV0, ScSpecUdtUnionCaseVoidV0, ScSpecUdtUnionV0,
+    ScString, ScVal, UInt128Parts,
+};
+
+fn udt(name: &str) -> ScSpecTypeDef {
+    ScSpecTypeDef::Udt(ScSpecTypeUdt {
+        name: name.try_into().unwrap(),
+    })
+}
+
+fn field(name: &str, type_: ScSpecTypeDef) -> ScSpecUdtStructFieldV0 {
+    ScSpecUdtStructFieldV0 {
+        doc: "".try_into().unwrap(),
+        name: name.try_into().unwrap(),
+        type_,
+    }
+}
+
+fn spec() -> Vec<ScSpecEntry> {
+    vec![
+        ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+            doc: "".try_into().unwrap(),
+            lib: "".try_into().unwrap(),
+            name: "Transfer".try_into().unwrap(),
+            fields: [
+                field("to", ScSpecTypeDef::Address),
+                field("amount", ScSpecTypeDef::I128),
+                field(
+                    "memo",
+                    ScSpecTypeDef::Option(Box::new(ScSpecTypeOption {
+                        value_type: Box::new(ScSpecTypeDef::String),
+                    })),
+                ),
+            ]
+            .try_into()
+            .unwrap(),
+        }),
+        ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+            doc: "".try_into().unwrap(),
+            lib: "".try_into().unwrap(),
+            name: "Point".try_into().unwrap(),
+            fields: [
+                field("0", ScSpecTypeDef::I32),
+                field("1", ScSpecTypeDef::I32),
+            ]
+            .try_into()
+            .unwrap(),
+        }),
+        ScSpecEntry::UdtUnionV0(ScSpecUdtUnionV0 {
+            doc: "".try_into().unwrap(),
+            lib: "".try_into().unwrap(),
+            name: "Shape".try_into().unwrap(),
+            cases: [
+                ScSpecUdtUnionCaseV0::VoidV0(ScSpecUdtUnionCaseVoidV0 {
+                    doc: "".try_into().unwrap(),
+                    name: "Empty".try_into().unwrap(),
+                }),
+                ScSpecUdtUnionCaseV0::TupleV0(ScSpecUdtUnionCaseTupleV0 {
+                    doc: "".try_into().unwrap(),
+                    name: "Line".try_into().unwrap(),
+                    type_: [udt("Point"), udt("Point")].try_into().unwrap(),
+                }),
+            ]
+            .try_into()
+            .unwrap(),
+        }),
+        ScSpecEntry::UdtEnumV0(ScSpecUdtEnumV0 {
+            doc: "".try_into().unwrap(),
+            lib: "".try_into().unwrap(),
+            name: "Color".try_into().unwrap(),
+            cases: [("Red", 1), ("Green", 2)]
+                .map(|(name, value)| ScSpecUdtEnumCaseV0 {
+                    doc: "".try_into().unwrap(),
+                    name: name.try_into().unwrap(),
+                    value,
+                })
+                .try_into()
+                .unwrap(),
+        }),
+        ScSpecEntry::UdtErrorEnumV0(ScSpecUdtErrorEnumV0 {
+            doc: "".try_into().unwrap(),
+            lib: "".try_into().unwrap(),
+            name: "Failure".try_into().unwrap(),
+            cases: [ScSpecUdtErrorEnumCaseV0 {
+                doc: "".try_into().unwrap(),
+                name: "Insufficient".try_into().unwrap(),
+                value: 7,
+            }]
+            .try_into()
+            .unwrap(),
+        }),
+    ]
+}
+
+const ADDRESS: &str = "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ";
+
+fn symbol(s: &str) -> ScVal {
+    ScVal::Symbol(s.try_into().unwrap())
+}
+
+fn vec(items: impl IntoIterator<Item = ScVal>) -> ScVal {
+    ScVal::Vec(Some(
+        items.into_iter().collect::<Vec<_>>().try_into().unwrap(),
+    ))
+}
+
+fn map(entries: impl IntoIterator<Item = (ScVal, ScVal)>) -> ScVal {
+    ScVal::Map(Some(ScMap::sorted_from(entries).unwrap()))
+}
+
+fn roundtrip(json: &serde_json::Value, type_def: &ScSpecTypeDef, val: &ScVal) {
+    let spec = spec();
+    assert_eq!(
+        &ScVal::from_json_with_spec(json, type_def, &spec).unwrap(),
+        val,
+        "{type_def}"
+    );
+    assert_eq!(
+        &val.to_json_with_spec(type_def, &spec).unwrap(),
+        json,
+        "{type_def}"
+    );
+}
+
+#[test]
+fn test_primitives() {
+    roundtrip(&json!(true), &ScSpecTypeDef::Bool, &ScVal::Bool(true));
+    roundtrip(&json!(null), &ScSpecTypeDef::Void, &ScVal::Void);
+    roundtrip(&json!(-5), &ScSpecTypeDef::I32, &ScVal::I32(-5));
+    roundtrip(&json!(u64::MAX), &ScSpecTypeDef::U64, &ScVal::U64(u64::MAX));
+    roundtrip(
+        &json!("340282366920938463463374607431768211455"),
+        &ScSpecTypeDef::U128,
+        &ScVal::U128(UInt128Parts {
+            hi: u64::MAX,
+            lo: u64::MAX,
+        }),
+    );
+    roundtrip(
+        &json!("010203"),
+        &ScSpecTypeDef::BytesN(ScSpecTypeBytesN { n: 3 }),
+        &ScVal::Bytes(vec![1, 2, 3].try_into().unwrap()),
+    );
+    roundtrip(
+        &json!("caf\\xc3\\xa9"),
+        &ScSpecTypeDef::String,
+        &ScVal::String(ScString("café".try_into().unwrap())),
+    );
+    roundtrip(&json!("hello"), &ScSpecTypeDef::Symbol, &symbol("hello"));
+    roundtrip(
+        &json!(ADDRESS),
+        &ScSpecTypeDef::Address,
+        &ScVal::Address(ADDRESS.parse().unwrap()),
+    );
+
+    // Integers are also accepted as strings.
+    assert_eq!(
+        ScVal::from_json_with_spec(&json!("42"), &ScSpecTypeDef::U32, &[]),
+        Ok(ScVal::U32(42))
+    );
+    assert_eq!(
+        ScVal::from_json_with_spec(&json!(-1), &ScSpecTypeDef::I128, &[]),
+        Ok(ScVal::I128(Int128Parts {
+            hi: -1,
+            lo: u64::MAX
+        }))
+    );
+}
+
+#[test]
+fn test_containers() {
+    let option = ScSpecTypeDef::Option(Box::new(ScSpecTypeOption {
+        value_type: Box::new(ScSpecTypeDef::U32),
+    }));
+    roundtrip(&json!(null), &option, &ScVal::Void);
+    roundtrip(&json!(1), &option, &ScVal::U32(1));
+
+    let result = ScSpecTypeDef::Result(Box::new(ScSpecTypeResult {
+        ok_type: Box::new(ScSpecTypeDef::U32),
+        error_type: Box::new(udt("Failure")),
+    }));
+    roundtrip(&json!({"ok": 1}), &result, &ScVal::U32(1));
+    roundtrip(
+        &json!({"error": 7}),
+        &result,
+        &ScVal::Error(ScError::Contract(7)),
+    );
+
+    let tuple = ScSpecTypeDef::Tuple(Box::new(ScSpecTypeTuple {
+        value_types: [ScSpecTypeDef::Symbol, ScSpecTypeDef::Bool]
+            .try_into()
+            .unwrap(),
+    }));
+    roundtrip(
+        &json!([["a", false]]),
+        &ScSpecTypeDef::Vec(Box::new(ScSpecTypeVec {
+            element_type: Box::new(tuple),
+        })),
+        &vec([vec([symbol("a"), ScVal::Bool(false)])]),
+    );
+
+    // Maps with keys that are strings in JSON are objects.
+    let map_type = |key_type| {
+        ScSpecTypeDef::Map(Box::new(ScSpecTypeMap {
+            key_type: Box::new(key_type),
+            value_type: Box::new(ScSpecTypeDef::U32),
+        }))
+    };
+    roundtrip(
+        &json!({"a": 1, "b": 2}),
+        &map_type(ScSpecTypeDef::Symbol),
+        &map([(symbol("b"), ScVal::U32(2)), (symbol("a"), ScVal::U32(1))]),
+    );
+    // Other maps are arrays of pairs.
+    roundtrip(
+        &json!([[1, 2], [3, 4]]),
+        &map_type(ScSpecTypeDef::U32),
+        &map([
+            (ScVal::U32(3), ScVal::U32(4)),
+            (ScVal::U32(1), ScVal::U32(2)),
+        ]),
+    );
+}
+
+#[test]
+fn test_udts() {
+    roundtrip(
+        &json!({"to": ADDRESS, "amount": "100", "memo": null}),
+        &udt("Transfer"),
+        &map([
+            (symbol("to"), ScVal::Address(ADDRESS.parse().unwrap())),
+            (
+                symbol("amount"),
+                ScVal::I128(Int128Parts { hi: 0, lo: 100 }),
+            ),
+            (symbol("memo"), ScVal::Void),
+        ]),
+    );
+    roundtrip(&json!("Empty"), &udt("Shape"), &vec([symbol("Empty")]));
+    roundtrip(
+        &json!({"Line": [[0, 0], [1, -1]]}),
+        &udt("Shape"),
+        &vec([
+            symbol("Line"),
+            vec([ScVal::I32(0), ScVal::I32(0)]),
+            vec([ScVal::I32(1), ScVal::I32(-1)]),
+        ]),
+    );
+    roundtrip(&json!(2), &udt("Color"), &ScVal::U32(2));
+    assert_eq!(
+        ScVal::from_json_with_spec(&json!("Green"), &udt("Color"), &spec()),
+        Ok(ScVal::U32(2))
+    );
+    assert_eq!(
+        ScVal::from_json_with_spec(&json!("Insufficient"), &udt("Failure"), &spec()),
+        Ok(ScVal::Error(ScError::Contract(7)))
+    );
+}
+
+#[test]
+fn test_value_errors() {
+    let spec = spec();
+    let from_json = |json, type_def: &ScSpecTypeDef| {
+        ScVal::from_json_with_spec(&json, type_def, &spec).map_err(|e| (e.path, e.kind))
+    };
+
+    assert_eq!(
+        from_json(json!("x"), &ScSpecTypeDef::Bool),
+        Err((
+            "$".to_string(),
+            ScSpecJsonErrorKind::TypeMismatch {
+                expected: "bool".to_string()
+            }
+        ))
+    );
+    assert_eq!(
+        from_json(json!(256), &ScSpecTypeDef::U32),
+        Ok(ScVal::U32(256))
+    );
+    assert_eq!(
+        from_json(json!("4294967296"), &ScSpecTypeDef::U32),
+        Err((
+            "$".to_string(),
+            ScSpecJsonErrorKind::OutOfRange {
+                expected: "u32".to_string()
+            }
+        ))
+    );
+    assert_eq!(
+        from_json(
+            json!("0102"),
+            &ScSpecTypeDef::BytesN(ScSpecTypeBytesN { n: 3 })
+        ),
+        Err((
+            "$".to_string(),
+            ScSpecJsonErrorKind::LengthMismatch {
+                expected: 3,
+                actual: 2
+            }
+        ))
+    );
+    assert_eq!(
+        from_json(json!("a b"), &ScSpecTypeDef::Symbol),
+        Err((
+            "$".to_string(),
+            ScSpecJsonErrorKind::InvalidValue {
+                expected: "Symbol".to_string()
+            }
+        ))
+    );
+}
+
+#[test]
+fn test_udt_errors() {
+    let spec = spec();
+    let from_json = |json, type_def: &ScSpecTypeDef| {
+        ScVal::from_json_with_spec(&json, type_def, &spec).map_err(|e| (e.path, e.kind))
+    };
+
+    assert_eq!(
+        from_json(
+            json!({"to": ADDRESS, "amount": "x", "memo": null}),
+            &udt("Transfer")
+        ),
+        Err((
+            "$.amount".to_string(),
+            ScSpecJsonErrorKind::TypeMismatch {
+                expected: "i128".to_string()
+            }
+        ))
+    );
+    assert_eq!(
+        from_json(json!({"to": ADDRESS, "memo": null}), &udt("Transfer")),
+        Err((
+            "$".to_string(),
+            ScSpecJsonErrorKind::MissingField("amount".to_string())
+        ))
+    );
+    assert_eq!(
+        from_json(
+            json!({"to": ADDRESS, "amount": 1, "memo": null, "fee": 1}),
+            &udt("Transfer")
+        ),
+        Err((
+            "$".to_string(),
+            ScSpecJsonErrorKind::UnknownField("fee".to_string())
+        ))
+    );
+    assert_eq!(
+        from_json(json!({"Line": [[0, 0], [1]]}), &udt("Shape")),
+        Err((
+            "$.Line[1]".to_string(),
+            ScSpecJsonErrorKind::LengthMismatch {
+                expected: 2,
+                actual: 1
+            }
+        ))
+    );
+    assert_eq!(
+        from_json(json!("Circle"), &udt("Shape")),
+        Err((
+            "$".to_string(),
+            ScSpecJsonErrorKind::UnknownCase("Circle".to_string())
+        ))
+    );
+    assert_eq!(
+        from_json(json!(3), &udt("Color")),
+        Err((
+            "$".to_string(),
+            ScSpecJsonErrorKind::UnknownCase("3".to_string())
+        ))
+    );
+    assert_eq!(
+        from_json(json!(null), &udt("Missing")),
+        Err((
+            "$".to_string(),
+            ScSpecJsonErrorKind::UnknownType("Missing".to_string())
+        ))
+    );
+    assert_eq!(
+        from_json(
+            json!([[1, 1], [1, 2]]),
+            &ScSpecTypeDef::Map(Box::new(ScSpecTypeMap {
+                key_type: Box::new(ScSpecTypeDef::U32),
+                value_type: Box::new(ScSpecTypeDef::U32),
+            }))
+        ),
+        Err(("$".to_string(), ScSpecJsonErrorKind::DuplicateKey))
+    );
+
+    assert_eq!(
+        ScVal::I32(1)
+            .to_json_with_spec(&udt("Point"), &spec)
+            .unwrap_err()
+            .to_string(),
+        "$: expected Point"
+    );
+    assert_eq!(
+        ScSpecJsonError {
+            path: "$[0]".to_string(),
+            kind: ScSpecJsonErrorKind::MissingField("to".to_string()),
+        }
+        .to_string(),
+        "$[0]: missing field to"
+    );
+}
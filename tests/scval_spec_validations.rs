@@ -0,0 +1,348 @@
+#![cfg(feature = "std")]
+
+use stellar_xdr::{
+    Int128Parts, MuxedEd25519Account, ScAddress, ScError, ScMap, ScMapEntry, ScSpecEntry,
+    ScSpecTypeBytesN, ScSpecTypeDef, ScSpecTypeMap, ScSpecTypeOption, ScSpecTypeResult,
+    ScSpecTypeUdt, ScSpecTypeVec, ScSpecUdtEnumCaseV0, ScSpecUdtEnumV0, ScSpecUdtErrorEnumCaseV0,
+    ScSpecUdtErrorEnumV0, ScSpecUdtStructFieldV0, ScSpecUdtStructV0, ScSpecUdtUnionCaseTupleV0,
+    ScSpecUdtUnionCaseV0, ScSpecUdtUnionCaseVoidV0, ScSpecUdtUnionV0, ScSpecValidationError,
+    ScSpecValidationErrorKind, ScVal, Uint256,
+};
+
+fn udt(name: &str) -> ScSpecTypeDef {
+    ScSpecTypeDef::Udt(ScSpecTypeUdt {
+        name: name.try_into().unwrap(),
+    })
+}
+
+fn field(name: &str, type_: ScSpecTypeDef) -> ScSpecUdtStructFieldV0 {
+    ScSpecUdtStructFieldV0 {
+        doc: "".try_into().unwrap(),
+        name: name.try_into().unwrap(),
+        type_,
+    }
+}
+
+fn spec() -> Vec<ScSpecEntry> {
+    vec![
+        ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+            doc: "".try_into().unwrap(),
+            lib: "".try_into().unwrap(),
+            name: "Transfer".try_into().unwrap(),
+            fields: [
+                field("amount", ScSpecTypeDef::I128),
+                field("to", ScSpecTypeDef::Address),
+            ]
+            .try_into()
+            .unwrap(),
+        }),
+        ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+            doc: "".try_into().unwrap(),
+            lib: "".try_into().unwrap(),
+            name: "Point".try_into().unwrap(),
+            fields: [
+                field("0", ScSpecTypeDef::I32),
+                field("1", ScSpecTypeDef::I32),
+            ]
+            .try_into()
+            .unwrap(),
+        }),
+        ScSpecEntry::UdtUnionV0(ScSpecUdtUnionV0 {
+            doc: "".try_into().unwrap(),
+            lib: "".try_into().unwrap(),
+            name: "Shape".try_into().unwrap(),
+            cases: [
+                ScSpecUdtUnionCaseV0::VoidV0(ScSpecUdtUnionCaseVoidV0 {
+                    doc: "".try_into().unwrap(),
+                    name: "Empty".try_into().unwrap(),
+                }),
+                ScSpecUdtUnionCaseV0::TupleV0(ScSpecUdtUnionCaseTupleV0 {
+                    doc: "".try_into().unwrap(),
+                    name: "Line".try_into().unwrap(),
+                    type_: [udt("Point"), udt("Point")].try_into().unwrap(),
+                }),
+            ]
+            .try_into()
+            .unwrap(),
+        }),
+        ScSpecEntry::UdtEnumV0(ScSpecUdtEnumV0 {
+            doc: "".try_into().unwrap(),
+            lib: "".try_into().unwrap(),
+            name: "Color".try_into().unwrap(),
+            cases: [ScSpecUdtEnumCaseV0 {
+                doc: "".try_into().unwrap(),
+                name: "Red".try_into().unwrap(),
+                value: 1,
+            }]
+            .try_into()
+            .unwrap(),
+        }),
+        ScSpecEntry::UdtErrorEnumV0(ScSpecUdtErrorEnumV0 {
+            doc: "".try_into().unwrap(),
+            lib: "".try_into().unwrap(),
+            name: "Failure".try_into().unwrap(),
+            cases: [ScSpecUdtErrorEnumCaseV0 {
+                doc: "".try_into().unwrap(),
+                name: "Insufficient".try_into().unwrap(),
+                value: 7,
+            }]
+            .try_into()
+            .unwrap(),
+        }),
+    ]
+}
+
+fn symbol(s: &str) -> ScVal {
+    ScVal::Symbol(s.try_into().unwrap())
+}
+
+fn vec(items: impl IntoIterator<Item = ScVal>) -> ScVal {
+    ScVal::Vec(Some(
+        items.into_iter().collect::<Vec<_>>().try_into().unwrap(),
+    ))
+}
+
+fn map(entries: impl IntoIterator<Item = (ScVal, ScVal)>) -> ScVal {
+    ScVal::Map(Some(ScMap::sorted_from(entries).unwrap()))
+}
+
+fn account() -> ScVal {
+    ScVal::Address(ScAddress::Account(
+        "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ"
+            .parse()
+            .unwrap(),
+    ))
+}
+
+fn point(x: i32, y: i32) -> ScVal {
+    vec([ScVal::I32(x), ScVal::I32(y)])
+}
+
+fn transfer() -> ScVal {
+    map([
+        (symbol("to"), account()),
+        (symbol("amount"), ScVal::I128(Int128Parts { hi: 0, lo: 5 })),
+    ])
+}
+
+fn validate(
+    val: &ScVal,
+    type_def: &ScSpecTypeDef,
+) -> Result<(), (String, ScSpecValidationErrorKind)> {
+    val.validate_against(type_def, &spec())
+        .map_err(|ScSpecValidationError { path, kind }| (path, kind))
+}
+
+#[test]
+fn test_valid() {
+    let option = |value_type| {
+        ScSpecTypeDef::Option(Box::new(ScSpecTypeOption {
+            value_type: Box::new(value_type),
+        }))
+    };
+    let result = ScSpecTypeDef::Result(Box::new(ScSpecTypeResult {
+        ok_type: Box::new(ScSpecTypeDef::Void),
+        error_type: Box::new(udt("Failure")),
+    }));
+    let cases = [
+        (ScVal::Void, option(ScSpecTypeDef::U32)),
+        (ScVal::U32(1), option(ScSpecTypeDef::U32)),
+        (ScVal::Void, result.clone()),
+        (ScVal::Error(ScError::Contract(7)), result),
+        (
+            ScVal::Bytes(vec![0; 32].try_into().unwrap()),
+            ScSpecTypeDef::BytesN(ScSpecTypeBytesN { n: 32 }),
+        ),
+        (account(), ScSpecTypeDef::Address),
+        (account(), ScSpecTypeDef::MuxedAddress),
+        (transfer(), udt("Transfer")),
+        (point(1, 2), udt("Point")),
+        (vec([symbol("Empty")]), udt("Shape")),
+        (
+            vec([symbol("Line"), point(0, 0), point(1, 1)]),
+            udt("Shape"),
+        ),
+        (ScVal::U32(1), udt("Color")),
+        (
+            map([(symbol("a"), vec([symbol("b")]))]),
+            ScSpecTypeDef::Map(Box::new(ScSpecTypeMap {
+                key_type: Box::new(ScSpecTypeDef::Symbol),
+                value_type: Box::new(ScSpecTypeDef::Vec(Box::new(ScSpecTypeVec {
+                    element_type: Box::new(ScSpecTypeDef::Symbol),
+                }))),
+            })),
+        ),
+        (transfer(), ScSpecTypeDef::Val),
+    ];
+    for (val, type_def) in cases {
+        assert_eq!(validate(&val, &type_def), Ok(()), "{type_def}");
+    }
+}
+
+fn muxed() -> ScVal {
+    ScVal::Address(ScAddress::MuxedAccount(MuxedEd25519Account {
+        id: 1,
+        ed25519: Uint256([0; 32]),
+    }))
+}
+
+fn expected(expected: &str) -> ScSpecValidationErrorKind {
+    ScSpecValidationErrorKind::TypeMismatch {
+        expected: expected.to_string(),
+    }
+}
+
+fn assert_invalid<const N: usize>(
+    cases: [(ScVal, ScSpecTypeDef, &str, ScSpecValidationErrorKind); N],
+) {
+    for (val, type_def, path, kind) in cases {
+        assert_eq!(
+            validate(&val, &type_def),
+            Err((path.to_string(), kind)),
+            "{type_def}"
+        );
+    }
+}
+
+#[test]
+fn test_invalid_values() {
+    assert_invalid([
+        (
+            ScVal::Bytes(vec![0; 31].try_into().unwrap()),
+            ScSpecTypeDef::BytesN(ScSpecTypeBytesN { n: 32 }),
+            "$",
+            ScSpecValidationErrorKind::LengthMismatch {
+                expected: 32,
+                actual: 31,
+            },
+        ),
+        (
+            muxed(),
+            ScSpecTypeDef::Address,
+            "$",
+            ScSpecValidationErrorKind::InvalidValue {
+                expected: "Address".to_string(),
+            },
+        ),
+        (
+            ScVal::U32(1),
+            ScSpecTypeDef::Option(Box::new(ScSpecTypeOption {
+                value_type: Box::new(ScSpecTypeDef::I32),
+            })),
+            "$",
+            expected("i32"),
+        ),
+    ]);
+}
+
+#[test]
+fn test_invalid_udts() {
+    let unsorted = ScVal::Map(Some(ScMap(
+        vec![
+            ScMapEntry {
+                key: symbol("to"),
+                val: account(),
+            },
+            ScMapEntry {
+                key: symbol("amount"),
+                val: ScVal::I128(Int128Parts { hi: 0, lo: 5 }),
+            },
+        ]
+        .try_into()
+        .unwrap(),
+    )));
+    assert_invalid([
+        (
+            map([(symbol("to"), muxed())]),
+            udt("Transfer"),
+            "$.to",
+            ScSpecValidationErrorKind::InvalidValue {
+                expected: "Address".to_string(),
+            },
+        ),
+        (
+            map([(symbol("to"), account())]),
+            udt("Transfer"),
+            "$",
+            ScSpecValidationErrorKind::MissingField("amount".to_string()),
+        ),
+        (
+            map([(symbol("from"), account())]),
+            udt("Transfer"),
+            "$",
+            ScSpecValidationErrorKind::UnknownField("from".to_string()),
+        ),
+        (
+            unsorted,
+            udt("Transfer"),
+            "$",
+            ScSpecValidationErrorKind::UnsortedMap,
+        ),
+        (
+            vec([symbol("Line"), point(0, 0), vec([ScVal::I32(1)])]),
+            udt("Shape"),
+            "$[2]",
+            ScSpecValidationErrorKind::LengthMismatch {
+                expected: 2,
+                actual: 1,
+            },
+        ),
+        (
+            vec([symbol("Empty"), point(0, 0)]),
+            udt("Shape"),
+            "$",
+            ScSpecValidationErrorKind::LengthMismatch {
+                expected: 1,
+                actual: 2,
+            },
+        ),
+        (
+            vec([symbol("Circle")]),
+            udt("Shape"),
+            "$[0]",
+            ScSpecValidationErrorKind::UnknownCase("Circle".to_string()),
+        ),
+        (
+            ScVal::U32(2),
+            udt("Color"),
+            "$",
+            ScSpecValidationErrorKind::UnknownCase("2".to_string()),
+        ),
+        (
+            ScVal::Error(ScError::Contract(8)),
+            udt("Failure"),
+            "$",
+            ScSpecValidationErrorKind::UnknownCase("8".to_string()),
+        ),
+        (ScVal::I32(1), udt("Color"), "$", expected("Color")),
+        (
+            ScVal::Void,
+            udt("Missing"),
+            "$",
+            ScSpecValidationErrorKind::UnknownType("Missing".to_string()),
+        ),
+        (
+            ScVal::Map(Some(ScMap(
+                vec![ScMapEntry {
+                    key: ScVal::U32(1),
+                    val: vec([symbol("a b")]),
+                }]
+                .try_into()
+                .unwrap(),
+            ))),
+            ScSpecTypeDef::Val,
+            "$[0].val[0]",
+            ScSpecValidationErrorKind::InvalidValue {
+                expected: "Val".to_string(),
+            },
+        ),
+    ]);
+}
+
+#[test]
+fn test_error_display() {
+    let err = map([(symbol("to"), ScVal::U32(1))])
+        .validate_against(&udt("Transfer"), &spec())
+        .unwrap_err();
+    assert_eq!(err.to_string(), "$.to: expected Address");
+}
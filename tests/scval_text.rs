@@ -0,0 +1,225 @@
+#![cfg(feature = "std")]
+
+use stellar_xdr::{
+    ContractExecutable, Duration, Hash, Int128Parts, Int256Parts, ScAddress, ScContractInstance,
+    ScError, ScErrorCode, ScMap, ScMapEntry, ScNonceKey, ScString, ScVal, ScValParseError,
+    TimePoint, UInt128Parts, UInt256Parts,
+};
+
+fn symbol(s: &str) -> ScVal {
+    ScVal::Symbol(s.try_into().unwrap())
+}
+
+fn string(s: &[u8]) -> ScVal {
+    ScVal::String(ScString(s.try_into().unwrap()))
+}
+
+fn vec(items: impl IntoIterator<Item = ScVal>) -> ScVal {
+    ScVal::Vec(Some(
+        items.into_iter().collect::<Vec<_>>().try_into().unwrap(),
+    ))
+}
+
+fn map(entries: impl IntoIterator<Item = (ScVal, ScVal)>) -> ScMap {
+    ScMap(
+        entries
+            .into_iter()
+            .map(|(key, val)| ScMapEntry { key, val })
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap(),
+    )
+}
+
+const ACCOUNT: &str = "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ";
+const CONTRACT: &str = "CAAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQC526";
+
+#[test]
+fn test_display_and_parse() {
+    let cases = [
+        (ScVal::Bool(true), "true"),
+        (ScVal::Void, "void"),
+        (ScVal::Error(ScError::Contract(7)), "error:Contract:7"),
+        (
+            ScVal::Error(ScError::Storage(ScErrorCode::MissingValue)),
+            "error:Storage:MissingValue",
+        ),
+        (ScVal::U32(1), "1u32"),
+        (ScVal::I32(-1), "-1i32"),
+        (ScVal::U64(u64::MAX), "18446744073709551615u64"),
+        (ScVal::I64(i64::MIN), "-9223372036854775808i64"),
+        (ScVal::Timepoint(TimePoint(2)), "timepoint:2"),
+        (ScVal::Duration(Duration(3)), "duration:3"),
+        (
+            ScVal::U128(UInt128Parts { hi: 1, lo: 0 }),
+            "18446744073709551616u128",
+        ),
+        (
+            ScVal::I128(Int128Parts {
+                hi: -1,
+                lo: u64::MAX,
+            }),
+            "-1i128",
+        ),
+        (
+            ScVal::U256(UInt256Parts {
+                hi_hi: 0,
+                hi_lo: 0,
+                lo_hi: 0,
+                lo_lo: 5,
+            }),
+            "5u256",
+        ),
+        (
+            ScVal::I256(Int256Parts {
+                hi_hi: -1,
+                hi_lo: u64::MAX,
+                lo_hi: u64::MAX,
+                lo_lo: u64::MAX - 1,
+            }),
+            "-2i256",
+        ),
+        (ScVal::Bytes(vec![0xde, 0xad].try_into().unwrap()), "0xdead"),
+        (ScVal::Bytes(vec![].try_into().unwrap()), "0x"),
+        (string(b"a \"b\"\n\xff"), r#""a \x22b\x22\n\xff""#),
+        (symbol("abc"), r#"sym:"abc""#),
+        (ScVal::Vec(None), "vec:none"),
+        (vec([]), "[]"),
+        (ScVal::Map(None), "map:none"),
+        (ScVal::Map(Some(map([]))), "{}"),
+        (
+            ScVal::Map(Some(map([
+                (symbol("a"), ScVal::U32(1)),
+                (
+                    string(b"b"),
+                    vec([ScVal::I128(Int128Parts { hi: 0, lo: 1 }), ScVal::Bool(true)]),
+                ),
+            ]))),
+            r#"{sym:"a": 1u32, "b": [1i128, true]}"#,
+        ),
+        (ScVal::Address(ACCOUNT.parse().unwrap()), ACCOUNT),
+        (ScVal::Address(CONTRACT.parse().unwrap()), CONTRACT),
+        (
+            ScVal::ContractInstance(ScContractInstance {
+                executable: ContractExecutable::Wasm(Hash([0xab; 32])),
+                storage: Some(map([(symbol("k"), ScVal::Void)])),
+            }),
+            r#"contract_instance(wasm:abababababababababababababababababababababababababababababababab, {sym:"k": void})"#,
+        ),
+        (
+            ScVal::ContractInstance(ScContractInstance {
+                executable: ContractExecutable::StellarAsset,
+                storage: None,
+            }),
+            "contract_instance(stellar_asset, map:none)",
+        ),
+        (
+            ScVal::LedgerKeyContractInstance,
+            "ledger_key_contract_instance",
+        ),
+        (
+            ScVal::LedgerKeyNonce(ScNonceKey { nonce: -4 }),
+            "ledger_key_nonce:-4",
+        ),
+    ];
+    for (val, text) in cases {
+        assert_eq!(val.to_string(), text);
+        assert_eq!(text.parse::<ScVal>(), Ok(val), "{text}");
+    }
+}
+
+#[test]
+fn test_parse_preserves_order() {
+    // Map entries are kept in the order given, even when unsorted or
+    // duplicated, so that the text round trips exactly.
+    let text = r#"{sym:"b": 1u32, sym:"a": 2u32, sym:"a": 3u32}"#;
+    let val = text.parse::<ScVal>().unwrap();
+    assert_eq!(
+        val,
+        ScVal::Map(Some(map([
+            (symbol("b"), ScVal::U32(1)),
+            (symbol("a"), ScVal::U32(2)),
+            (symbol("a"), ScVal::U32(3)),
+        ])))
+    );
+    assert_eq!(val.to_string(), text);
+}
+
+#[test]
+fn test_parse_whitespace_and_utf8() {
+    assert_eq!(
+        " { sym : \"a\" : [ 1u32 , \"ü\" ] } ".parse::<ScVal>(),
+        Ok(ScVal::Map(Some(map([(
+            symbol("a"),
+            vec([ScVal::U32(1), string("ü".as_bytes())]),
+        )]))))
+    );
+    assert_eq!(
+        format!("[{ACCOUNT},{ACCOUNT}]").parse::<ScVal>(),
+        Ok(vec([
+            ScVal::Address(ScAddress::Account(ACCOUNT.parse().unwrap())),
+            ScVal::Address(ScAddress::Account(ACCOUNT.parse().unwrap())),
+        ]))
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    let cases = [
+        ("", 0, "value"),
+        ("1", 1, "integer type suffix"),
+        ("1u8", 1, "integer type suffix"),
+        ("256u8", 3, "integer type suffix"),
+        ("-1u32", 0, "integer in range of its type"),
+        ("0xabc", 2, "pairs of hex digits"),
+        ("\"abc", 4, "closing `\"`"),
+        (r#""\q""#, 1, "valid escape sequences"),
+        (
+            r#"sym:"abcdefghijklmnopqrstuvwxyz0123456789""#,
+            4,
+            "symbol of at most 32 bytes",
+        ),
+        ("[1u32 2u32]", 6, "`,` or `]`"),
+        ("{1u32 2u32}", 6, "`:`"),
+        ("{1u32: 2u32", 11, "`,` or `}`"),
+        ("vec:some", 4, "none"),
+        ("error:Other:1", 6, "error type"),
+        ("error:Auth:Missing", 11, "error code"),
+        ("contract_instance(wasm:ab, map:none)", 23, "wasm hash"),
+        ("contract_instance(stellar_asset, 1u32)", 33, "map"),
+        ("GABC", 0, "value"),
+        ("true false", 5, "end of input"),
+    ];
+    for (text, offset, expected) in cases {
+        assert_eq!(
+            text.parse::<ScVal>(),
+            Err(ScValParseError { offset, expected }),
+            "{text}"
+        );
+    }
+    assert_eq!(
+        "[".parse::<ScVal>().unwrap_err().to_string(),
+        "expected value at offset 1"
+    );
+}
+
+#[test]
+fn test_parse_deeply_nested() {
+    let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+    let mut v = vec([]);
+    for _ in 1..200 {
+        v = vec([v]);
+    }
+    assert_eq!(nested(200).parse::<ScVal>(), Ok(v));
+    assert_eq!(
+        nested(201).parse::<ScVal>(),
+        Err(ScValParseError {
+            offset: 200,
+            expected: "less deeply nested values",
+        })
+    );
+    // Input nested far deeper than the limit is rejected without exhausting
+    // the stack.
+    assert!("[".repeat(100_000).parse::<ScVal>().is_err());
+    assert!("{1u32:".repeat(100_000).parse::<ScVal>().is_err());
+}
@@ -0,0 +1,264 @@
+#![cfg(feature = "std")]
+
+use std::collections::BTreeMap;
+
+use stellar_xdr::{
+    DependentTxCluster, FeeBumpTransaction, FeeBumpTransactionEnvelope, FeeBumpTransactionExt,
+    FeeBumpTransactionInnerTx, GeneralizedTransactionSet, Hash, LedgerHeader, Limits, Memo,
+    MuxedAccount, ParallelTxExecutionStage, ParallelTxsComponent, Preconditions, SequenceNumber,
+    Transaction, TransactionEnvelope, TransactionExt, TransactionPhase, TransactionSet,
+    TransactionSetV1, TransactionV1Envelope, TxSetComponent, TxSetComponentBaseFee,
+    TxSetComponentTxsMaybeDiscountedFee, Uint256, WriteXdr,
+};
+
+use sha2::{Digest, Sha256};
+
+fn tx(source: u8, seq_num: i64) -> TransactionEnvelope {
+    TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx: Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256([source; 32])),
+            fee: 100,
+            seq_num: SequenceNumber(seq_num),
+            cond: Preconditions::None,
+            memo: Memo::None,
+            operations: [].try_into().unwrap(),
+            ext: TransactionExt::V0,
+        },
+        signatures: [].try_into().unwrap(),
+    })
+}
+
+fn fee_bump(fee_source: u8, inner_source: u8, seq_num: i64) -> TransactionEnvelope {
+    let TransactionEnvelope::Tx(inner) = tx(inner_source, seq_num) else {
+        unreachable!()
+    };
+    TransactionEnvelope::TxFeeBump(FeeBumpTransactionEnvelope {
+        tx: FeeBumpTransaction {
+            fee_source: MuxedAccount::Ed25519(Uint256([fee_source; 32])),
+            fee: 200,
+            inner_tx: FeeBumpTransactionInnerTx::Tx(inner),
+            ext: FeeBumpTransactionExt::V0,
+        },
+        signatures: [].try_into().unwrap(),
+    })
+}
+
+fn source_and_seq(tx: &TransactionEnvelope) -> (u8, i64) {
+    match tx {
+        TransactionEnvelope::Tx(e) => match &e.tx.source_account {
+            MuxedAccount::Ed25519(k) => (k.0[0], e.tx.seq_num.0),
+            MuxedAccount::MuxedEd25519(_) => unreachable!(),
+        },
+        TransactionEnvelope::TxFeeBump(e) => {
+            let FeeBumpTransactionInnerTx::Tx(inner) = &e.tx.inner_tx;
+            source_and_seq(&TransactionEnvelope::Tx(inner.clone()))
+        }
+        TransactionEnvelope::TxV0(_) => unreachable!(),
+    }
+}
+
+fn xored_hash(tx: &TransactionEnvelope, tx_set_hash: &[u8; 32]) -> Vec<u8> {
+    let h: [u8; 32] = Sha256::digest(tx.to_xdr(Limits::none()).unwrap()).into();
+    h.iter().zip(tx_set_hash).map(|(a, b)| a ^ b).collect()
+}
+
+/// Asserts that the ordered transactions are a valid sequential apply order of
+/// the given transactions.
+fn assert_sequential_order(
+    txs: &[TransactionEnvelope],
+    ordered: &[&TransactionEnvelope],
+    tx_set_hash: &[u8; 32],
+) {
+    assert_eq!(ordered.len(), txs.len());
+    for tx in txs {
+        assert!(ordered.contains(&tx));
+    }
+
+    // Each account's transactions are applied in sequence order, and batch
+    // n contains the n-th transaction of each account.
+    let mut per_account = BTreeMap::<u8, Vec<i64>>::new();
+    let mut batches = Vec::<Vec<&TransactionEnvelope>>::new();
+    for tx in ordered {
+        let (source, seq) = source_and_seq(tx);
+        let seqs = per_account.entry(source).or_default();
+        if let Some(last) = seqs.last() {
+            assert!(*last < seq);
+        }
+        seqs.push(seq);
+        let batch = seqs.len() - 1;
+        if batches.len() <= batch {
+            batches.push(vec![]);
+        }
+        assert_eq!(batch, batches.len() - 1, "batches are applied in order");
+        batches[batch].push(tx);
+    }
+
+    // Each batch is ordered by the xored hashes.
+    for batch in batches {
+        let hashes = batch
+            .iter()
+            .map(|tx| xored_hash(tx, tx_set_hash))
+            .collect::<Vec<_>>();
+        assert!(hashes.windows(2).all(|w| w[0] < w[1]));
+    }
+}
+
+#[test]
+fn test_legacy_tx_set_hash() -> Result<(), stellar_xdr::Error> {
+    let tx_set = TransactionSet {
+        previous_ledger_hash: Hash([9; 32]),
+        txs: [tx(1, 1), tx(2, 1)].try_into()?,
+    };
+    let mut hasher = Sha256::new();
+    hasher.update([9; 32]);
+    hasher.update(tx_set.txs[0].to_xdr(Limits::none())?);
+    hasher.update(tx_set.txs[1].to_xdr(Limits::none())?);
+    let expected: [u8; 32] = hasher.finalize().into();
+    assert_eq!(tx_set.hash()?, expected);
+
+    let mut header = LedgerHeader::default();
+    assert!(header.verify_legacy_tx_set(&tx_set).is_err());
+    header.scp_value.tx_set_hash = Hash(expected);
+    assert_eq!(header.verify_legacy_tx_set(&tx_set), Ok(()));
+    Ok(())
+}
+
+#[test]
+fn test_legacy_tx_set_apply_order() -> Result<(), stellar_xdr::Error> {
+    let txs = vec![
+        tx(1, 3),
+        tx(1, 1),
+        tx(1, 2),
+        tx(2, 7),
+        tx(3, 1),
+        tx(3, 2),
+        fee_bump(4, 2, 8),
+        tx(5, 1),
+    ];
+    let tx_set = TransactionSet {
+        previous_ledger_hash: Hash([1; 32]),
+        txs: txs.clone().try_into()?,
+    };
+    let ordered = tx_set.apply_order()?;
+    assert_sequential_order(&txs, &ordered, &tx_set.hash()?);
+    Ok(())
+}
+
+#[test]
+fn test_generalized_tx_set_apply_order() -> Result<(), stellar_xdr::Error> {
+    let classic = vec![tx(1, 1), tx(1, 2), tx(2, 1), tx(3, 5), tx(2, 2)];
+    let soroban = [tx(10, 1), tx(11, 1), tx(12, 1), tx(13, 1), tx(14, 1)];
+    let tx_set = GeneralizedTransactionSet::V1(TransactionSetV1 {
+        previous_ledger_hash: Hash([2; 32]),
+        phases: [
+            TransactionPhase::V0(
+                [
+                    TxSetComponent::TxsetCompTxsMaybeDiscountedFee(
+                        TxSetComponentTxsMaybeDiscountedFee {
+                            base_fee: Some(100),
+                            txs: classic[..3].to_vec().try_into()?,
+                        },
+                    ),
+                    TxSetComponent::TxsetCompTxsMaybeDiscountedFee(
+                        TxSetComponentTxsMaybeDiscountedFee {
+                            base_fee: None,
+                            txs: classic[3..].to_vec().try_into()?,
+                        },
+                    ),
+                ]
+                .try_into()?,
+            ),
+            TransactionPhase::V1(ParallelTxsComponent {
+                base_fee: Some(250),
+                execution_stages: [
+                    ParallelTxExecutionStage(
+                        [
+                            DependentTxCluster(soroban[..2].to_vec().try_into()?),
+                            DependentTxCluster(soroban[2..3].to_vec().try_into()?),
+                        ]
+                        .try_into()?,
+                    ),
+                    ParallelTxExecutionStage(
+                        [DependentTxCluster(soroban[3..].to_vec().try_into()?)].try_into()?,
+                    ),
+                ]
+                .try_into()?,
+            }),
+        ]
+        .try_into()?,
+    });
+    let tx_set_hash = tx_set.hash()?;
+    let ordered = tx_set.apply_order()?;
+
+    // The classic phase is applied first, sequentially.
+    assert_sequential_order(&classic, &ordered[..classic.len()], &tx_set_hash);
+
+    // The soroban phase is applied stage by stage, cluster by cluster.
+    let soroban_ordered = &ordered[classic.len()..];
+    let h = |tx: &TransactionEnvelope| xored_hash(tx, &tx_set_hash);
+    let mut stage0_c0 = vec![&soroban[0], &soroban[1]];
+    stage0_c0.sort_by_key(|tx| h(tx));
+    let mut stage0 = [stage0_c0, vec![&soroban[2]]];
+    stage0.sort_by_key(|c| h(c[0]));
+    let mut stage1 = vec![&soroban[3], &soroban[4]];
+    stage1.sort_by_key(|tx| h(tx));
+    let mut expected = [stage0.concat(), stage1];
+    expected.sort_by_key(|s| h(s[0]));
+    assert_eq!(soroban_ordered, expected.concat());
+    Ok(())
+}
+
+#[test]
+fn test_base_fees() -> Result<(), stellar_xdr::Error> {
+    let tx_set = GeneralizedTransactionSet::V1(TransactionSetV1 {
+        previous_ledger_hash: Hash([2; 32]),
+        phases: [
+            TransactionPhase::V0(
+                [
+                    TxSetComponent::TxsetCompTxsMaybeDiscountedFee(
+                        TxSetComponentTxsMaybeDiscountedFee {
+                            base_fee: Some(100),
+                            txs: [tx(1, 1)].try_into()?,
+                        },
+                    ),
+                    TxSetComponent::TxsetCompTxsMaybeDiscountedFee(
+                        TxSetComponentTxsMaybeDiscountedFee {
+                            base_fee: None,
+                            txs: [tx(2, 1), tx(3, 1)].try_into()?,
+                        },
+                    ),
+                ]
+                .try_into()?,
+            ),
+            TransactionPhase::V1(ParallelTxsComponent {
+                base_fee: Some(250),
+                execution_stages: [ParallelTxExecutionStage(
+                    [DependentTxCluster([tx(4, 1)].try_into()?)].try_into()?,
+                )]
+                .try_into()?,
+            }),
+        ]
+        .try_into()?,
+    });
+    assert_eq!(
+        tx_set.base_fees(),
+        vec![
+            TxSetComponentBaseFee {
+                phase: 0,
+                base_fee: Some(100),
+                txs: vec![&tx(1, 1)],
+            },
+            TxSetComponentBaseFee {
+                phase: 0,
+                base_fee: None,
+                txs: vec![&tx(2, 1), &tx(3, 1)],
+            },
+            TxSetComponentBaseFee {
+                phase: 1,
+                base_fee: Some(250),
+                txs: vec![&tx(4, 1)],
+            },
+        ]
+    );
+    Ok(())
+}
@@ -0,0 +1,181 @@
+#![cfg(feature = "std")]
+
+use stellar_xdr::{
+    wasm_custom_sections, Limits, ScEnvMetaEntry, ScEnvMetaEntryInterfaceVersion, ScMetaEntry,
+    ScMetaV0, ScSpecEntry, ScSpecFunctionV0, ScSpecTypeDef, ScSpecUdtEnumCaseV0, ScSpecUdtEnumV0,
+    WasmCustomSection, WasmError, WriteXdr,
+};
+
+fn leb128(mut v: u32) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    loop {
+        let b = (v & 0x7f) as u8;
+        v >>= 7;
+        if v == 0 {
+            bytes.push(b);
+            return bytes;
+        }
+        bytes.push(b | 0x80);
+    }
+}
+
+fn section(id: u8, payload: &[u8]) -> Vec<u8> {
+    let mut bytes = vec![id];
+    bytes.extend(leb128(payload.len().try_into().unwrap()));
+    bytes.extend(payload);
+    bytes
+}
+
+fn custom_section(name: &str, data: &[u8]) -> Vec<u8> {
+    let mut payload = leb128(name.len().try_into().unwrap());
+    payload.extend(name.as_bytes());
+    payload.extend(data);
+    section(0, &payload)
+}
+
+fn wasm(sections: &[Vec<u8>]) -> Vec<u8> {
+    let mut bytes = b"\0asm".to_vec();
+    bytes.extend(1u32.to_le_bytes());
+    for s in sections {
+        bytes.extend(s);
+    }
+    bytes
+}
+
+fn stream(entries: &[impl WriteXdr]) -> Vec<u8> {
+    entries
+        .iter()
+        .flat_map(|e| e.to_xdr(Limits::none()).unwrap())
+        .collect()
+}
+
+fn spec() -> Vec<ScSpecEntry> {
+    vec![
+        ScSpecEntry::FunctionV0(ScSpecFunctionV0 {
+            doc: "Says hello.".try_into().unwrap(),
+            name: "hello".try_into().unwrap(),
+            inputs: [].try_into().unwrap(),
+            outputs: [ScSpecTypeDef::Symbol].try_into().unwrap(),
+        }),
+        ScSpecEntry::UdtEnumV0(ScSpecUdtEnumV0 {
+            doc: "A".repeat(200).try_into().unwrap(),
+            lib: "".try_into().unwrap(),
+            name: "Color".try_into().unwrap(),
+            cases: [ScSpecUdtEnumCaseV0 {
+                doc: "".try_into().unwrap(),
+                name: "Red".try_into().unwrap(),
+                value: 1,
+            }]
+            .try_into()
+            .unwrap(),
+        }),
+    ]
+}
+
+fn meta(key: &str, val: &str) -> ScMetaEntry {
+    ScMetaEntry::ScMetaV0(ScMetaV0 {
+        key: key.try_into().unwrap(),
+        val: val.try_into().unwrap(),
+    })
+}
+
+fn env_meta() -> ScEnvMetaEntry {
+    ScEnvMetaEntry::ScEnvMetaKindInterfaceVersion(ScEnvMetaEntryInterfaceVersion {
+        protocol: 23,
+        pre_release: 0,
+    })
+}
+
+#[test]
+fn test_from_wasm() -> Result<(), WasmError> {
+    let spec = spec();
+    let wasm = wasm(&[
+        // A type section, which is skipped.
+        section(1, &[0x01, 0x60, 0x00, 0x00]),
+        custom_section("contractenvmetav0", &stream(&[env_meta()])),
+        custom_section("contractmetav0", &stream(&[meta("rsver", "1.84.0")])),
+        custom_section("contractspecv0", &stream(&spec)),
+        custom_section("contractmetav0", &stream(&[meta("rssdkver", "23.0.0")])),
+    ]);
+
+    assert_eq!(ScSpecEntry::from_wasm(&wasm, Limits::none())?, spec);
+    assert_eq!(
+        ScMetaEntry::from_wasm(&wasm, Limits::none())?,
+        [meta("rsver", "1.84.0"), meta("rssdkver", "23.0.0")]
+    );
+    assert_eq!(
+        ScEnvMetaEntry::from_wasm(&wasm, Limits::none())?,
+        env_meta()
+    );
+
+    let sections = wasm_custom_sections(&wasm)?;
+    assert_eq!(
+        sections.iter().map(|s| s.name).collect::<Vec<_>>(),
+        [
+            "contractenvmetav0",
+            "contractmetav0",
+            "contractspecv0",
+            "contractmetav0"
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_from_wasm_missing_sections() -> Result<(), WasmError> {
+    let wasm = wasm(&[custom_section("name", b"contract")]);
+    assert_eq!(
+        wasm_custom_sections(&wasm)?,
+        [WasmCustomSection {
+            name: "name",
+            data: b"contract",
+        }]
+    );
+    assert_eq!(
+        ScSpecEntry::from_wasm(&wasm, Limits::none()),
+        Err(WasmError::MissingSection("contractspecv0"))
+    );
+    assert_eq!(ScMetaEntry::from_wasm(&wasm, Limits::none())?, []);
+    assert_eq!(
+        ScEnvMetaEntry::from_wasm(&wasm, Limits::none()),
+        Err(WasmError::MissingSection("contractenvmetav0"))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_wasm_errors() {
+    assert_eq!(
+        wasm_custom_sections(b"\0elf\x01\0\0\0"),
+        Err(WasmError::NotWasm)
+    );
+    assert_eq!(
+        wasm_custom_sections(b"\0asm\x02\0\0\0"),
+        Err(WasmError::UnsupportedVersion(2))
+    );
+
+    let mut truncated = wasm(&[custom_section("contractspecv0", &stream(&spec()))]);
+    truncated.pop();
+    assert_eq!(
+        wasm_custom_sections(&truncated),
+        Err(WasmError::UnexpectedEnd)
+    );
+
+    let overlong = wasm(&[vec![0, 0x80, 0x80, 0x80, 0x80, 0x10]]);
+    assert_eq!(
+        wasm_custom_sections(&overlong),
+        Err(WasmError::InvalidLeb128)
+    );
+
+    let invalid_name = wasm(&[section(0, &[2, 0xff, 0xfe])]);
+    assert_eq!(
+        wasm_custom_sections(&invalid_name),
+        Err(WasmError::InvalidSectionName)
+    );
+
+    let invalid_xdr = wasm(&[custom_section("contractspecv0", &[0, 0, 0, 99])]);
+    assert!(matches!(
+        ScSpecEntry::from_wasm(&invalid_xdr, Limits::none()),
+        Err(WasmError::Xdr(_))
+    ));
+}